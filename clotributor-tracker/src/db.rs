@@ -1,255 +1,1667 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 use async_trait::async_trait;
 use deadpool_postgres::Pool;
+use futures::stream::{BoxStream, StreamExt};
 #[cfg(test)]
 use mockall::automock;
+use postgres_types::{Json, ToSql};
+use serde_json::{Value, json};
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+use tokio_postgres::Row;
+use tracing::warn;
 use uuid::Uuid;
 
-use crate::tracker::{Issue, Repository};
+use crate::tracker::{
+    BeginnerLeaderboardCfg, ClosedIssue, Issue, IssueReference, Label, Repository,
+    RepositoryMetadata, SpotlightCfg, Topic, TrackFailureCategory,
+};
 
 /// Type alias to represent a DB trait object.
 pub(crate) type DynDB = Arc<dyn DB + Send + Sync>;
 
+/// Minimum database schema version required by the tracker. It must be bumped
+/// every time a schema migration the tracker depends on is added.
+const MIN_SCHEMA_VERSION: i32 = 47;
+
+/// Columns added by recent schema migrations that the tracker can do without
+/// when running in compatibility mode (table, column and type).
+const OPTIONAL_COLUMNS: &[(&str, &str, &str)] = &[
+    ("repository", "owner_followers", "integer"),
+    ("repository", "head_sha", "text"),
+    ("repository", "raw_response_digest", "text"),
+    ("repository", "watch_star_ratio", "real"),
+    ("repository", "badges", "jsonb"),
+    ("repository", "issues_page_size", "integer"),
+    ("repository", "is_private", "boolean"),
+    ("repository", "issue_close_rate", "real"),
+    ("issue", "is_available", "boolean"),
+    ("issue", "needs_triage", "boolean"),
+    ("issue", "first_response_seconds", "bigint"),
+    ("issue", "converted_to_discussion_url", "text"),
+];
+
+/// Repository columns updated with the data fetched from GitHub (name and
+/// type).
+const REPOSITORY_GH_DATA_COLUMNS: &[(&str, &str)] = &[
+    ("description", "text"),
+    ("homepage_url", "text"),
+    ("languages", "text[]"),
+    ("stars", "integer"),
+    ("topics", "text[]"),
+    ("digest", "text"),
+    ("open_issues_total", "integer"),
+    ("closed_issues_total", "integer"),
+    ("default_branch_protected", "boolean"),
+    ("query_version", "integer"),
+    ("is_active", "boolean"),
+    ("requires_cla", "boolean"),
+    ("top_contributors", "jsonb"),
+    ("primary_language", "text"),
+    ("primary_language_color", "text"),
+    ("commit_activity", "integer[]"),
+    ("has_issues_enabled", "boolean"),
+    ("open_milestones", "integer"),
+    ("is_empty", "boolean"),
+    ("latest_release_at", "timestamptz"),
+    ("owner_followers", "integer"),
+    ("head_sha", "text"),
+    ("raw_response_digest", "text"),
+    ("watch_star_ratio", "real"),
+    ("badges", "jsonb"),
+];
+
+/// Issue columns that are not updated when the issue is registered again.
+const ISSUE_IMMUTABLE_COLUMNS: &[&str] = &["issue_id", "number", "published_at", "repository_id"];
+
+/// Minimum time (in seconds) since a repository was last tracked for it to be
+/// tracked again.
+const REPOSITORY_TRACK_INTERVAL: u32 = 1800;
+
+/// Maximum clock skew (in seconds) tolerated when checking if a repository
+/// needs to be tracked again.
+const MAX_CLOCK_SKEW_TOLERANCE: u32 = REPOSITORY_TRACK_INTERVAL / 2;
+
+/// Repository columns read when getting the repositories to track.
+const REPOSITORY_COLUMNS: &[&str] = &[
+    "repository_id",
+    "name",
+    "description",
+    "url",
+    "homepage_url",
+    "topics",
+    "languages",
+    "primary_language",
+    "primary_language_color",
+    "stars",
+    "digest",
+    "issues_filter_label",
+    "open_issues_total",
+    "closed_issues_total",
+    "default_branch_protected",
+    "query_version",
+    "is_active",
+    "issues_cursor",
+    "issues_page_size",
+    "requires_cla",
+    "top_contributors",
+    "commit_activity",
+    "has_issues_enabled",
+    "open_milestones",
+    "is_empty",
+    "is_private",
+    "latest_release_at",
+    "owner_followers",
+    "head_sha",
+    "raw_response_digest",
+    "watch_star_ratio",
+    "badges",
+    "issue_close_rate",
+    "metadata_category",
+    "metadata_subcategory",
+    "metadata_project_name",
+];
+
+/// Issue columns read when getting the issues of a repository.
+const ISSUE_COLUMNS: &[&str] = &[
+    "issue_id",
+    "title",
+    "url",
+    "number",
+    "labels",
+    "published_at",
+    "has_linked_prs",
+    "linked_pr_url",
+    "linked_pr_state",
+    "last_activity_at",
+    "reactions_by_type",
+    "participants",
+    "is_pinned",
+    "is_available",
+    "needs_triage",
+    "first_response_seconds",
+    "digest",
+    "area",
+    "kind",
+    "difficulty",
+    "mentor_available",
+    "mentor",
+    "good_first_issue",
+];
+
 /// Trait that defines some operations a DB implementation must support.
 #[async_trait]
+#[allow(clippy::ref_option_ref)]
 #[cfg_attr(test, automock)]
 pub(crate) trait DB {
-    /// Get repositories that need to be tracked.
-    async fn get_repositories_to_track(&self) -> Result<Vec<Repository>>;
+    /// Check if the column provided exists (only optional columns may be
+    /// missing when running in compatibility mode).
+    fn has_column(&self, table: &str, column: &str) -> bool;
+
+    /// Disable the repositories that haven't been tracked successfully in the
+    /// number of days provided, optionally limited to the ones in the group
+    /// provided. The urls of the repositories disabled are returned.
+    async fn disable_untracked_repositories(
+        &self,
+        max_age_days: u32,
+        group: Option<&String>,
+    ) -> Result<Vec<String>>;
+
+    /// Get repositories that need to be tracked, optionally limited to the
+    /// ones in the group provided, tolerating the clock skew given (in
+    /// seconds) when checking their staleness.
+    async fn get_repositories_to_track(
+        &self,
+        group: Option<&String>,
+        clock_skew_tolerance: u32,
+    ) -> Result<Vec<Repository>>;
+
+    /// Get repositories tracked within the window provided (or never tracked)
+    /// that need to be repaired, optionally limited to the ones in the group
+    /// provided. The window is widened by the clock skew given (in seconds).
+    async fn get_repositories_to_repair(
+        &self,
+        group: Option<&String>,
+        since: OffsetDateTime,
+        until: OffsetDateTime,
+        clock_skew_tolerance: u32,
+    ) -> Result<Vec<Repository>>;
+
+    /// Stream repositories that need to be tracked, optionally limited to the
+    /// ones in the group provided, tolerating the clock skew given (in
+    /// seconds) when checking their staleness.
+    async fn stream_repositories_to_track(
+        &self,
+        group: Option<&String>,
+        clock_skew_tolerance: u32,
+    ) -> Result<BoxStream<'static, Result<Repository>>>;
 
     /// Get repository's issues.
     async fn get_repository_issues(&self, repository_id: Uuid) -> Result<Vec<Issue>>;
 
+    /// Get the digests of the issues of all the repositories to track (or the
+    /// ones in the group provided), keyed by repository.
+    async fn get_issues_digests(
+        &self,
+        group: Option<&String>,
+    ) -> Result<HashMap<Uuid, Vec<(i64, String)>>>;
+
     /// Register issue provided in the database.
     async fn register_issue(&self, repository: &Repository, issue: &Issue) -> Result<()>;
 
+    /// Record the labels provided in the issue's labels history.
+    async fn record_issue_labels(
+        &self,
+        issue_id: i64,
+        labels: &[String],
+        recorded_at: OffsetDateTime,
+    ) -> Result<()>;
+
+    /// Update the score of the issues provided.
+    async fn update_issues_scores(&self, scores: &[(i64, f64)]) -> Result<()>;
+
+    /// Flag the issue provided as converted into the discussion given.
+    async fn flag_issue_converted_to_discussion(
+        &self,
+        issue_id: i64,
+        discussion_url: &str,
+    ) -> Result<()>;
+
+    /// Unregister the issues converted to discussions that were flagged more
+    /// than the number of days provided ago, optionally limited to the ones
+    /// in the group provided. The number of issues unregistered is returned.
+    async fn unregister_converted_issues(
+        &self,
+        max_age_days: u32,
+        group: Option<&String>,
+    ) -> Result<u64>;
+
     /// Unregister issue provided from the database.
     async fn unregister_issue(&self, issue_id: i64) -> Result<()>;
 
+    /// Unregister the issues provided from the database.
+    async fn unregister_issues(&self, issues_ids: &[i64]) -> Result<()>;
+
+    /// Register the recently closed issues of the repository provided, pruning
+    /// from the feed the ones closed before the timestamp given.
+    async fn update_recently_closed_issues(
+        &self,
+        repository_id: Uuid,
+        issues: &[ClosedIssue],
+        since: OffsetDateTime,
+    ) -> Result<()>;
+
+    /// Replace the references of the issues provided with the ones given.
+    async fn update_issues_references(
+        &self,
+        issues_ids: &[i64],
+        references: &[IssueReference],
+    ) -> Result<()>;
+
+    /// Register the repository's labels definitions provided in the database.
+    async fn register_repository_labels(&self, repository_id: Uuid, labels: &[Label])
+    -> Result<()>;
+
     /// Update repository's GitHub data in the database.
     async fn update_repository_gh_data(&self, repository: &Repository) -> Result<()>;
 
+    /// Update the GitHub data of the repositories provided in a single
+    /// multi-row update.
+    async fn batch_update_repositories(&self, repositories: &[Repository]) -> Result<()>;
+
+    /// Update repository's stars and the watch to star ratio derived from
+    /// them.
+    async fn update_repository_stars(
+        &self,
+        repository_id: Uuid,
+        stars: Option<i32>,
+        watch_star_ratio: Option<f32>,
+    ) -> Result<()>;
+
     /// Update repository's last track timestamp.
     async fn update_repository_last_track_ts(&self, repository_id: Uuid) -> Result<()>;
+
+    /// Record a failure tracking the repository provided.
+    async fn record_track_failure(
+        &self,
+        repository_id: Uuid,
+        category: TrackFailureCategory,
+        status: Option<i32>,
+        message: &str,
+        failed_at: OffsetDateTime,
+    ) -> Result<()>;
+
+    /// Update repository's issues pagination cursor.
+    async fn update_repository_issues_cursor(
+        &self,
+        repository_id: Uuid,
+        issues_cursor: Option<&String>,
+    ) -> Result<()>;
+
+    /// Update repository's issues close rate (nothing is written when the
+    /// column is missing in compatibility mode).
+    async fn update_repository_issue_close_rate(
+        &self,
+        repository_id: Uuid,
+        issue_close_rate: Option<f32>,
+    ) -> Result<()>;
+
+    /// Update repository's visibility (nothing is written when the column is
+    /// missing in compatibility mode).
+    async fn update_repository_visibility(
+        &self,
+        repository_id: Uuid,
+        is_private: bool,
+    ) -> Result<()>;
+
+    /// Update repository's issues page size (nothing is written when the
+    /// column is missing in compatibility mode).
+    async fn update_repository_issues_page_size(
+        &self,
+        repository_id: Uuid,
+        issues_page_size: i32,
+    ) -> Result<()>;
+
+    /// Update repository's projects metadata.
+    async fn update_repository_metadata(
+        &self,
+        repository_id: Uuid,
+        metadata: Option<&RepositoryMetadata>,
+    ) -> Result<()>;
+
+    /// Get topics used by repositories that need to be tracked.
+    async fn get_topics_to_track(&self) -> Result<Vec<String>>;
+
+    /// Register topic provided in the database.
+    async fn register_topic(&self, topic: &Topic) -> Result<()>;
+
+    /// Replace the spotlight issues with the most compelling issues across
+    /// all repositories, selected using the configuration provided.
+    async fn update_spotlight_issues(&self, cfg: &SpotlightCfg) -> Result<()>;
+
+    /// Replace the beginner leaderboard with the best good first issues,
+    /// ranked using the configuration provided.
+    async fn update_beginner_leaderboard(&self, cfg: &BeginnerLeaderboardCfg) -> Result<()>;
+
+    /// Replace the topics statistics (number of repositories and total stars)
+    /// with the ones computed from the repositories tracked.
+    async fn update_topic_stats(&self) -> Result<()>;
+
+    /// Replace the organizations statistics (number of repositories, total
+    /// stars, open issues and most active repository) with the ones computed
+    /// from the repositories tracked.
+    async fn update_org_stats(&self) -> Result<()>;
 }
 
 /// DB implementation backed by PostgreSQL.
 pub(crate) struct PgDB {
     pool: Pool,
+    missing_columns: Vec<(&'static str, &'static str, &'static str)>,
 }
 
 impl PgDB {
     /// Create a new PgDB instance.
     pub(crate) fn new(pool: Pool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            missing_columns: vec![],
+        }
     }
-}
 
-#[async_trait]
-impl DB for PgDB {
-    async fn get_repositories_to_track(&self) -> Result<Vec<Repository>> {
+    /// Enable the compatibility mode, used when the database schema may be
+    /// partially migrated. The optional columns not found in the database are
+    /// skipped, writing nothing to them and reading them as null.
+    pub(crate) async fn enable_compatibility_mode(&mut self) -> Result<()> {
         let db = self.pool.get().await?;
-        let repositories = db
+        let existing_columns: Vec<(String, String)> = db
             .query(
                 "
-                select
-                    r.repository_id,
-                    r.name,
-                    r.description,
-                    r.url,
-                    r.homepage_url,
-                    r.topics,
-                    r.languages,
-                    r.stars,
-                    r.digest,
-                    r.issues_filter_label,
-                    p.name as project_name,
-                    p.foundation_id
-                from repository r
-                join project p using (project_id)
-                where r.tracked_at is null
-                or r.tracked_at < current_timestamp - '30 minutes'::interval
-                order by r.url asc;
+                select table_name::text, column_name::text
+                from information_schema.columns
+                where table_schema = current_schema()
+                and table_name in ('repository', 'issue');
                 ",
                 &[],
             )
             .await?
             .iter()
-            .map(|row| Repository {
-                repository_id: row.get("repository_id"),
-                name: row.get("name"),
-                description: row.get("description"),
-                url: row.get("url"),
-                homepage_url: row.get("homepage_url"),
-                topics: row.get("topics"),
-                languages: row.get("languages"),
-                stars: row.get("stars"),
-                digest: row.get("digest"),
-                issues_filter_label: row.get("issues_filter_label"),
-                project_name: row.get("project_name"),
-                foundation_id: row.get("foundation_id"),
+            .map(|row| (row.get(0), row.get(1)))
+            .collect();
+        self.missing_columns = OPTIONAL_COLUMNS
+            .iter()
+            .filter(|(table, column, _)| {
+                !existing_columns
+                    .iter()
+                    .any(|(t, c)| t == table && c == column)
             })
+            .copied()
             .collect();
-        Ok(repositories)
+        for (table, column, _) in &self.missing_columns {
+            warn!(
+                table,
+                column, "column not found, skipping it (compatibility mode)"
+            );
+        }
+        Ok(())
     }
 
-    async fn get_repository_issues(&self, repository_id: Uuid) -> Result<Vec<Issue>> {
+    /// Build the condition used to exclude the issues converted to
+    /// discussions, which are kept only to redirect to them (nothing is
+    /// excluded when the column is missing in compatibility mode).
+    fn issues_not_converted(&self, prefix: &str) -> String {
+        if self.has_column("issue", "converted_to_discussion_url") {
+            format!("and {prefix}converted_to_discussion_url is null")
+        } else {
+            String::new()
+        }
+    }
+
+    /// Build the query used to register an issue, setting the columns
+    /// provided. Registering an issue again clears its discussion conversion
+    /// flag, as it's available again.
+    fn register_issue_query(&self, names: &[&str]) -> String {
+        let n = names.len();
+        let values: Vec<String> = (1..=n).map(|i| format!("${i}")).collect();
+        let mut updates: Vec<String> = names
+            .iter()
+            .filter(|column| !ISSUE_IMMUTABLE_COLUMNS.contains(column))
+            .map(|column| format!("{column} = excluded.{column}"))
+            .collect();
+        if self.has_column("issue", "converted_to_discussion_url") {
+            updates.push("converted_to_discussion_url = null".to_string());
+        }
+        format!(
+            "
+            insert into issue ({}, tsdoc)
+            values (
+                {},
+                setweight(to_tsvector(${}), 'A') ||
+                setweight(to_tsvector(${}), 'B') ||
+                setweight(to_tsvector(${}), 'C')
+            ) on conflict (issue_id) do update
+            set {}, tsdoc = excluded.tsdoc;
+            ",
+            names.join(", "),
+            values.join(", "),
+            n + 1,
+            n + 2,
+            n + 3,
+            updates.join(", "),
+        )
+    }
+
+    /// Build the select list of the columns of the table provided, reading
+    /// the ones missing as null instead.
+    fn select_columns(&self, table: &str, prefix: &str, columns: &[&str]) -> String {
+        columns
+            .iter()
+            .map(|column| {
+                match self
+                    .missing_columns
+                    .iter()
+                    .find(|(t, c, _)| *t == table && c == column)
+                {
+                    Some((_, _, column_type)) => format!("null::{column_type} as {column}"),
+                    None => format!("{prefix}{column}"),
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    /// Build the query used to get the repositories that need to be tracked.
+    /// When a repair window is provided ($2 and $3), the repositories tracked
+    /// within it (or never tracked) are returned instead, regardless of their
+    /// staleness. Staleness is always checked against the database clock, as
+    /// it sets the tracked_at timestamps, using the threshold provided ($4).
+    /// The repair window is widened by the clock skew tolerance ($5).
+    fn repositories_to_track_query(&self) -> String {
+        format!(
+            "
+            select
+                {},
+                p.name as project_name,
+                p.foundation_id
+            from repository r
+            join project p using (project_id)
+            where (
+                r.tracked_at is null
+                or case when $2::timestamptz is null then
+                    r.tracked_at < current_timestamp - make_interval(secs => $4::double precision)
+                else
+                    r.tracked_at between
+                        $2::timestamptz - make_interval(secs => $5::double precision)
+                        and $3::timestamptz + make_interval(secs => $5::double precision)
+                end
+            )
+            and r.disabled_at is null
+            and ($1::text is null or r.\"group\" = $1::text)
+            order by r.url asc;
+            ",
+            self.select_columns("repository", "r.", REPOSITORY_COLUMNS)
+        )
+    }
+
+    /// Check that the database schema version is compatible with the one
+    /// required by the tracker.
+    pub(crate) async fn check_schema_version(&self) -> Result<()> {
         let db = self.pool.get().await?;
-        let issues_ids = db
+        let version: i32 = db
+            .query_one("select version from version_schema;", &[])
+            .await?
+            .get("version");
+        if version < MIN_SCHEMA_VERSION {
+            bail!(
+                "database schema version ({version}) is older than the one required ({MIN_SCHEMA_VERSION}), please run the database migrations"
+            );
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DB for PgDB {
+    fn has_column(&self, table: &str, column: &str) -> bool {
+        !self
+            .missing_columns
+            .iter()
+            .any(|(t, c, _)| *t == table && *c == column)
+    }
+
+    async fn disable_untracked_repositories(
+        &self,
+        max_age_days: u32,
+        group: Option<&String>,
+    ) -> Result<Vec<String>> {
+        let db = self.pool.get().await?;
+        let urls = db
             .query(
                 "
-                select
-                    issue_id,
-                    title,
-                    url,
-                    number,
-                    labels,
-                    published_at,
-                    has_linked_prs,
-                    digest,
-                    area,
-                    kind,
-                    difficulty,
-                    mentor_available,
-                    mentor,
-                    good_first_issue
-                from issue
-                where repository_id = $1;
+                update repository set disabled_at = current_timestamp
+                where disabled_at is null
+                and coalesce(tracked_at, created_at) < current_timestamp - make_interval(days => $1::int)
+                and ($2::text is null or \"group\" = $2::text)
+                returning url;
                 ",
-                &[&repository_id],
+                &[&i32::try_from(max_age_days)?, &group],
             )
             .await?
             .iter()
-            .map(|row| Issue {
-                issue_id: row.get("issue_id"),
-                title: row.get("title"),
-                url: row.get("title"),
-                number: row.get("number"),
-                labels: row.get("labels"),
-                published_at: row.get("published_at"),
-                has_linked_prs: row.get("has_linked_prs"),
-                digest: row.get("digest"),
-                area: row.get("area"),
-                kind: row.get("kind"),
-                difficulty: row.get("difficulty"),
-                mentor_available: row.get("mentor_available"),
-                mentor: row.get("mentor"),
-                good_first_issue: row.get("good_first_issue"),
+            .map(|row| row.get("url"))
+            .collect();
+        Ok(urls)
+    }
+
+    async fn get_repositories_to_track(
+        &self,
+        group: Option<&String>,
+        clock_skew_tolerance: u32,
+    ) -> Result<Vec<Repository>> {
+        let db = self.pool.get().await?;
+        let (staleness_threshold, clock_skew_tolerance) = staleness_params(clock_skew_tolerance);
+        let repositories = db
+            .query(
+                &self.repositories_to_track_query(),
+                &[
+                    &group,
+                    &None::<OffsetDateTime>,
+                    &None::<OffsetDateTime>,
+                    &staleness_threshold,
+                    &clock_skew_tolerance,
+                ],
+            )
+            .await?
+            .iter()
+            .map(repository_from_row)
+            .collect();
+        Ok(repositories)
+    }
+
+    async fn get_repositories_to_repair(
+        &self,
+        group: Option<&String>,
+        since: OffsetDateTime,
+        until: OffsetDateTime,
+        clock_skew_tolerance: u32,
+    ) -> Result<Vec<Repository>> {
+        let db = self.pool.get().await?;
+        let (staleness_threshold, clock_skew_tolerance) = staleness_params(clock_skew_tolerance);
+        let repositories = db
+            .query(
+                &self.repositories_to_track_query(),
+                &[
+                    &group,
+                    &since,
+                    &until,
+                    &staleness_threshold,
+                    &clock_skew_tolerance,
+                ],
+            )
+            .await?
+            .iter()
+            .map(repository_from_row)
+            .collect();
+        Ok(repositories)
+    }
+
+    async fn stream_repositories_to_track(
+        &self,
+        group: Option<&String>,
+        clock_skew_tolerance: u32,
+    ) -> Result<BoxStream<'static, Result<Repository>>> {
+        let db = self.pool.get().await?;
+        let (staleness_threshold, clock_skew_tolerance) = staleness_params(clock_skew_tolerance);
+        let rows = db
+            .query_raw(
+                &self.repositories_to_track_query(),
+                [
+                    &group as &(dyn ToSql + Sync),
+                    &None::<OffsetDateTime>,
+                    &None::<OffsetDateTime>,
+                    &staleness_threshold,
+                    &clock_skew_tolerance,
+                ],
+            )
+            .await?;
+
+        // The db client is moved into the stream so that it's not returned to
+        // the pool until the stream is dropped
+        let repositories = rows
+            .map(move |row| {
+                let _ = &db;
+                Ok(repository_from_row(&row?))
             })
+            .boxed();
+        Ok(repositories)
+    }
+
+    async fn get_repository_issues(&self, repository_id: Uuid) -> Result<Vec<Issue>> {
+        let db = self.pool.get().await?;
+        let query = format!(
+            "select {} from issue where repository_id = $1 {};",
+            self.select_columns("issue", "", ISSUE_COLUMNS),
+            self.issues_not_converted(""),
+        );
+        let issues_ids = db
+            .query(&query, &[&repository_id])
+            .await?
+            .iter()
+            .map(issue_from_row)
             .collect();
         Ok(issues_ids)
     }
 
+    async fn get_issues_digests(
+        &self,
+        group: Option<&String>,
+    ) -> Result<HashMap<Uuid, Vec<(i64, String)>>> {
+        let db = self.pool.get().await?;
+        let query = format!(
+            "
+            select i.repository_id, i.issue_id, i.digest
+            from issue i
+            join repository r using (repository_id)
+            where r.disabled_at is null
+            and ($1::text is null or r.\"group\" = $1::text)
+            and i.digest is not null
+            {};
+            ",
+            self.issues_not_converted("i."),
+        );
+        let mut digests: HashMap<Uuid, Vec<(i64, String)>> = HashMap::new();
+        for row in db.query(&query, &[&group]).await? {
+            digests
+                .entry(row.get("repository_id"))
+                .or_default()
+                .push((row.get("issue_id"), row.get("digest")));
+        }
+        Ok(digests)
+    }
+
     async fn register_issue(&self, repository: &Repository, issue: &Issue) -> Result<()> {
         let db = self.pool.get().await?;
         let ts_texts = issue.prepare_ts_texts(repository);
+        let reactions_by_type = issue.reactions_by_type.as_ref().map(Json);
+        let columns: Vec<(&str, &(dyn ToSql + Sync))> = [
+            ("issue_id", &issue.issue_id as &(dyn ToSql + Sync)),
+            ("title", &issue.title),
+            ("url", &issue.url),
+            ("number", &issue.number),
+            ("labels", &issue.labels),
+            ("digest", &issue.digest),
+            ("area", &issue.area),
+            ("kind", &issue.kind),
+            ("difficulty", &issue.difficulty),
+            ("mentor_available", &issue.mentor_available),
+            ("mentor", &issue.mentor),
+            ("good_first_issue", &issue.good_first_issue),
+            ("has_linked_prs", &issue.has_linked_prs),
+            ("published_at", &issue.published_at),
+            ("repository_id", &repository.repository_id),
+            ("reactions_by_type", &reactions_by_type),
+            ("linked_pr_url", &issue.linked_pr_url),
+            ("linked_pr_state", &issue.linked_pr_state),
+            ("last_activity_at", &issue.last_activity_at),
+            ("participants", &issue.participants),
+            ("is_pinned", &issue.is_pinned),
+            ("is_available", &issue.is_available),
+            ("needs_triage", &issue.needs_triage),
+            ("first_response_seconds", &issue.first_response_seconds),
+        ]
+        .into_iter()
+        .filter(|(column, _)| self.has_column("issue", column))
+        .collect();
+        let names: Vec<&str> = columns.iter().map(|(column, _)| *column).collect();
+        let query = self.register_issue_query(&names);
+        let mut params: Vec<&(dyn ToSql + Sync)> =
+            columns.iter().map(|(_, value)| *value).collect();
+        params.extend([
+            &ts_texts.weight_a as &(dyn ToSql + Sync),
+            &ts_texts.weight_b,
+            &ts_texts.weight_c,
+        ]);
+        db.execute(&query, &params).await?;
+        Ok(())
+    }
+
+    async fn record_issue_labels(
+        &self,
+        issue_id: i64,
+        labels: &[String],
+        recorded_at: OffsetDateTime,
+    ) -> Result<()> {
+        let db = self.pool.get().await?;
+        db.execute(
+            "
+            insert into issue_label_history (
+                issue_id,
+                labels,
+                recorded_at
+            ) values (
+                $1, $2, $3
+            );
+            ",
+            &[&issue_id, &labels, &recorded_at],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn update_issues_scores(&self, scores: &[(i64, f64)]) -> Result<()> {
+        let db = self.pool.get().await?;
+        let (issues_ids, scores): (Vec<i64>, Vec<f64>) = scores.iter().copied().unzip();
+        db.execute(
+            "
+            update issue set score = s.score
+            from unnest($1::bigint[], $2::double precision[]) as s(issue_id, score)
+            where issue.issue_id = s.issue_id;
+            ",
+            &[&issues_ids, &scores],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn flag_issue_converted_to_discussion(
+        &self,
+        issue_id: i64,
+        discussion_url: &str,
+    ) -> Result<()> {
+        let db = self.pool.get().await?;
         db.execute(
             "
-            insert into issue (
+            update issue set
+                converted_to_discussion_url = $2,
+                updated_at = current_timestamp
+            where issue_id = $1;
+            ",
+            &[&issue_id, &discussion_url],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn unregister_converted_issues(
+        &self,
+        max_age_days: u32,
+        group: Option<&String>,
+    ) -> Result<u64> {
+        if !self.has_column("issue", "converted_to_discussion_url") {
+            return Ok(0);
+        }
+        let db = self.pool.get().await?;
+        let unregistered = db
+            .execute(
+                "
+                delete from issue i
+                using repository r
+                where i.repository_id = r.repository_id
+                and i.converted_to_discussion_url is not null
+                and i.updated_at < current_timestamp - make_interval(days => $1::int)
+                and ($2::text is null or r.\"group\" = $2::text);
+                ",
+                &[&i32::try_from(max_age_days)?, &group],
+            )
+            .await?;
+        Ok(unregistered)
+    }
+
+    async fn unregister_issue(&self, issue_id: i64) -> Result<()> {
+        let db = self.pool.get().await?;
+        db.execute("delete from issue where issue_id = $1;", &[&issue_id])
+            .await?;
+        Ok(())
+    }
+
+    async fn unregister_issues(&self, issues_ids: &[i64]) -> Result<()> {
+        let db = self.pool.get().await?;
+        db.execute(
+            "delete from issue where issue_id = any($1::bigint[]);",
+            &[&issues_ids],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn update_recently_closed_issues(
+        &self,
+        repository_id: Uuid,
+        issues: &[ClosedIssue],
+        since: OffsetDateTime,
+    ) -> Result<()> {
+        let mut db = self.pool.get().await?;
+        let tx = db.transaction().await?;
+        let issues_ids: Vec<i64> = issues.iter().map(|i| i.issue_id).collect();
+        let titles: Vec<&String> = issues.iter().map(|i| &i.title).collect();
+        let urls: Vec<&String> = issues.iter().map(|i| &i.url).collect();
+        let numbers: Vec<i32> = issues.iter().map(|i| i.number).collect();
+        let closed_at: Vec<OffsetDateTime> = issues.iter().map(|i| i.closed_at).collect();
+        tx.execute(
+            "
+            insert into recently_closed_issue (
                 issue_id,
+                repository_id,
                 title,
                 url,
                 number,
-                labels,
-                digest,
-                area,
-                kind,
-                difficulty,
-                mentor_available,
-                mentor,
-                good_first_issue,
-                has_linked_prs,
-                published_at,
-                repository_id,
-                tsdoc
-            ) values (
-                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15,
-                setweight(to_tsvector($16), 'A') ||
-                setweight(to_tsvector($17), 'B') ||
-                setweight(to_tsvector($18), 'C')
-            ) on conflict (issue_id) do update
+                closed_at
+            )
+            select i.issue_id, $1::uuid, i.title, i.url, i.number, i.closed_at
+            from unnest($2::bigint[], $3::text[], $4::text[], $5::integer[], $6::timestamptz[])
+                as i(issue_id, title, url, number, closed_at)
+            on conflict (issue_id) do update
             set
                 title = excluded.title,
-                labels = excluded.labels,
-                digest = excluded.digest,
-                area = excluded.area,
-                kind = excluded.kind,
-                difficulty = excluded.difficulty,
-                mentor_available = excluded.mentor_available,
-                mentor = excluded.mentor,
-                good_first_issue = excluded.good_first_issue,
-                has_linked_prs = excluded.has_linked_prs,
-                tsdoc = excluded.tsdoc;
+                url = excluded.url,
+                closed_at = excluded.closed_at;
             ",
             &[
-                &issue.issue_id,
-                &issue.title,
-                &issue.url,
-                &issue.number,
-                &issue.labels,
-                &issue.digest,
-                &issue.area,
-                &issue.kind,
-                &issue.difficulty,
-                &issue.mentor_available,
-                &issue.mentor,
-                &issue.good_first_issue,
-                &issue.has_linked_prs,
-                &issue.published_at,
-                &repository.repository_id,
-                &ts_texts.weight_a,
-                &ts_texts.weight_b,
-                &ts_texts.weight_c,
+                &repository_id,
+                &issues_ids,
+                &titles,
+                &urls,
+                &numbers,
+                &closed_at,
             ],
         )
         .await?;
+        tx.execute(
+            "delete from recently_closed_issue where closed_at < $1::timestamptz;",
+            &[&since],
+        )
+        .await?;
+        tx.commit().await?;
         Ok(())
     }
 
-    async fn unregister_issue(&self, issue_id: i64) -> Result<()> {
+    async fn update_issues_references(
+        &self,
+        issues_ids: &[i64],
+        references: &[IssueReference],
+    ) -> Result<()> {
+        let mut db = self.pool.get().await?;
+        let tx = db.transaction().await?;
+        tx.execute(
+            "delete from issue_references where issue_id = any($1::bigint[]);",
+            &[&issues_ids],
+        )
+        .await?;
+        let ids: Vec<i64> = references.iter().map(|r| r.issue_id).collect();
+        let sources_ids: Vec<i64> = references.iter().map(|r| r.source_issue_id).collect();
+        let sources_urls: Vec<&String> = references.iter().map(|r| &r.source_url).collect();
+        tx.execute(
+            "
+            insert into issue_references (issue_id, source_issue_id, source_url)
+            select r.issue_id, r.source_issue_id, r.source_url
+            from unnest($1::bigint[], $2::bigint[], $3::text[])
+                as r(issue_id, source_issue_id, source_url)
+            where exists (select from issue where issue_id = r.issue_id)
+            on conflict do nothing;
+            ",
+            &[&ids, &sources_ids, &sources_urls],
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn register_repository_labels(
+        &self,
+        repository_id: Uuid,
+        labels: &[Label],
+    ) -> Result<()> {
         let db = self.pool.get().await?;
-        db.execute("delete from issue where issue_id = $1;", &[&issue_id])
-            .await?;
+        let names: Vec<&String> = labels.iter().map(|l| &l.name).collect();
+        let colors: Vec<&String> = labels.iter().map(|l| &l.color).collect();
+        let descriptions: Vec<Option<&String>> =
+            labels.iter().map(|l| l.description.as_ref()).collect();
+        db.execute(
+            "
+            insert into label (repository_id, name, color, description)
+            select $1::uuid, name, color, description
+            from unnest($2::text[], $3::text[], $4::text[]) as l(name, color, description)
+            on conflict (repository_id, name) do update
+            set
+                color = excluded.color,
+                description = excluded.description,
+                updated_at = current_timestamp
+            where (label.color, label.description)
+                is distinct from (excluded.color, excluded.description);
+            ",
+            &[&repository_id, &names, &colors, &descriptions],
+        )
+        .await?;
         Ok(())
     }
 
     async fn update_repository_gh_data(&self, repository: &Repository) -> Result<()> {
         let db = self.pool.get().await?;
-        db.execute(
+        let top_contributors = repository.top_contributors.as_ref().map(Json);
+        let badges = repository.badges.as_ref().map(Json);
+
+        // Values must be in the same order as the REPOSITORY_GH_DATA_COLUMNS
+        let values: [&(dyn ToSql + Sync); 25] = [
+            &repository.description,
+            &repository.homepage_url,
+            &repository.languages,
+            &repository.stars,
+            &repository.topics,
+            &repository.digest,
+            &repository.open_issues_total,
+            &repository.closed_issues_total,
+            &repository.default_branch_protected,
+            &repository.query_version,
+            &repository.is_active,
+            &repository.requires_cla,
+            &top_contributors,
+            &repository.primary_language,
+            &repository.primary_language_color,
+            &repository.commit_activity,
+            &repository.has_issues_enabled,
+            &repository.open_milestones,
+            &repository.is_empty,
+            &repository.latest_release_at,
+            &repository.owner_followers,
+            &repository.head_sha,
+            &repository.raw_response_digest,
+            &repository.watch_star_ratio,
+            &badges,
+        ];
+        let mut assignments = vec![];
+        let mut params: Vec<&(dyn ToSql + Sync)> = vec![&repository.repository_id];
+        for ((column, _), value) in REPOSITORY_GH_DATA_COLUMNS.iter().zip(values) {
+            if self.has_column("repository", column) {
+                params.push(value);
+                assignments.push(format!("{column} = ${}", params.len()));
+            }
+        }
+        let query = format!(
             "
             update repository set
-                description = $2,
-                homepage_url = $3,
-                languages = $4,
-                stars = $5,
-                topics = $6,
-                digest = $7,
+                {},
                 updated_at = current_timestamp
             where repository_id = $1;
             ",
+            assignments.join(", ")
+        );
+        db.execute(&query, &params).await?;
+        Ok(())
+    }
+
+    async fn batch_update_repositories(&self, repositories: &[Repository]) -> Result<()> {
+        let db = self.pool.get().await?;
+        let repositories = repositories
+            .iter()
+            .map(|r| {
+                Ok(json!({
+                    "repository_id": r.repository_id,
+                    "description": r.description,
+                    "homepage_url": r.homepage_url,
+                    "languages": r.languages,
+                    "stars": r.stars,
+                    "topics": r.topics,
+                    "digest": r.digest,
+                    "open_issues_total": r.open_issues_total,
+                    "closed_issues_total": r.closed_issues_total,
+                    "default_branch_protected": r.default_branch_protected,
+                    "query_version": r.query_version,
+                    "is_active": r.is_active,
+                    "requires_cla": r.requires_cla,
+                    "top_contributors": r.top_contributors,
+                    "primary_language": r.primary_language,
+                    "primary_language_color": r.primary_language_color,
+                    "commit_activity": r.commit_activity,
+                    "has_issues_enabled": r.has_issues_enabled,
+                    "open_milestones": r.open_milestones,
+                    "is_empty": r.is_empty,
+                    "latest_release_at": r.latest_release_at.map(|ts| ts.format(&Rfc3339)).transpose()?,
+                    "owner_followers": r.owner_followers,
+                    "head_sha": r.head_sha,
+                    "raw_response_digest": r.raw_response_digest,
+                    "watch_star_ratio": r.watch_star_ratio,
+                    "badges": r.badges,
+                }))
+            })
+            .collect::<Result<Vec<Value>>>()?;
+        let columns: Vec<&(&str, &str)> = REPOSITORY_GH_DATA_COLUMNS
+            .iter()
+            .filter(|(column, _)| self.has_column("repository", column))
+            .collect();
+        let assignments: Vec<String> = columns
+            .iter()
+            .map(|(column, _)| format!("{column} = r.{column}"))
+            .collect();
+        let definitions: Vec<String> = columns
+            .iter()
+            .map(|(column, column_type)| format!("{column} {column_type}"))
+            .collect();
+        let query = format!(
+            "
+            update repository set
+                {},
+                updated_at = current_timestamp
+            from jsonb_to_recordset($1::jsonb) as r(repository_id uuid, {})
+            where repository.repository_id = r.repository_id;
+            ",
+            assignments.join(", "),
+            definitions.join(", "),
+        );
+        db.execute(&query, &[&Json(repositories)]).await?;
+        Ok(())
+    }
+
+    async fn update_repository_stars(
+        &self,
+        repository_id: Uuid,
+        stars: Option<i32>,
+        watch_star_ratio: Option<f32>,
+    ) -> Result<()> {
+        let db = self.pool.get().await?;
+        if self.has_column("repository", "watch_star_ratio") {
+            db.execute(
+                "update repository set stars = $2, watch_star_ratio = $3 where repository_id = $1;",
+                &[&repository_id, &stars, &watch_star_ratio],
+            )
+            .await?;
+        } else {
+            db.execute(
+                "update repository set stars = $2 where repository_id = $1;",
+                &[&repository_id, &stars],
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn update_repository_last_track_ts(&self, repository_id: Uuid) -> Result<()> {
+        let db = self.pool.get().await?;
+        db.execute(
+            "update repository set tracked_at = current_timestamp where repository_id = $1;",
+            &[&repository_id],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn record_track_failure(
+        &self,
+        repository_id: Uuid,
+        category: TrackFailureCategory,
+        status: Option<i32>,
+        message: &str,
+        failed_at: OffsetDateTime,
+    ) -> Result<()> {
+        let db = self.pool.get().await?;
+        db.execute(
+            "
+            insert into track_failure (
+                repository_id,
+                category,
+                status,
+                message,
+                failed_at
+            ) values (
+                $1, $2, $3, $4, $5
+            );
+            ",
+            &[&repository_id, &category, &status, &message, &failed_at],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn update_repository_issues_cursor(
+        &self,
+        repository_id: Uuid,
+        issues_cursor: Option<&String>,
+    ) -> Result<()> {
+        let db = self.pool.get().await?;
+        db.execute(
+            "update repository set issues_cursor = $2 where repository_id = $1;",
+            &[&repository_id, &issues_cursor],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn update_repository_issue_close_rate(
+        &self,
+        repository_id: Uuid,
+        issue_close_rate: Option<f32>,
+    ) -> Result<()> {
+        if !self.has_column("repository", "issue_close_rate") {
+            return Ok(());
+        }
+        let db = self.pool.get().await?;
+        db.execute(
+            "update repository set issue_close_rate = $2 where repository_id = $1;",
+            &[&repository_id, &issue_close_rate],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn update_repository_visibility(
+        &self,
+        repository_id: Uuid,
+        is_private: bool,
+    ) -> Result<()> {
+        if !self.has_column("repository", "is_private") {
+            return Ok(());
+        }
+        let db = self.pool.get().await?;
+        db.execute(
+            "update repository set is_private = $2 where repository_id = $1;",
+            &[&repository_id, &is_private],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn update_repository_issues_page_size(
+        &self,
+        repository_id: Uuid,
+        issues_page_size: i32,
+    ) -> Result<()> {
+        if !self.has_column("repository", "issues_page_size") {
+            return Ok(());
+        }
+        let db = self.pool.get().await?;
+        db.execute(
+            "update repository set issues_page_size = $2 where repository_id = $1;",
+            &[&repository_id, &issues_page_size],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn update_repository_metadata(
+        &self,
+        repository_id: Uuid,
+        metadata: Option<&RepositoryMetadata>,
+    ) -> Result<()> {
+        let db = self.pool.get().await?;
+        db.execute(
+            "
+            update repository set
+                metadata_category = $2,
+                metadata_subcategory = $3,
+                metadata_project_name = $4
+            where repository_id = $1;
+            ",
             &[
-                &repository.repository_id,
-                &repository.description,
-                &repository.homepage_url,
-                &repository.languages,
-                &repository.stars,
-                &repository.topics,
-                &repository.digest,
+                &repository_id,
+                &metadata.map(|m| &m.category),
+                &metadata.map(|m| &m.subcategory),
+                &metadata.map(|m| &m.project_name),
             ],
         )
         .await?;
         Ok(())
     }
 
-    async fn update_repository_last_track_ts(&self, repository_id: Uuid) -> Result<()> {
+    async fn get_topics_to_track(&self) -> Result<Vec<String>> {
+        let db = self.pool.get().await?;
+        let topics = db
+            .query(
+                "
+                select name from (
+                    select distinct unnest(topics) as name
+                    from repository
+                    except
+                    select name
+                    from topic
+                    where updated_at > current_timestamp - '7 days'::interval
+                ) t
+                order by name asc;
+                ",
+                &[],
+            )
+            .await?
+            .iter()
+            .map(|row| row.get("name"))
+            .collect();
+        Ok(topics)
+    }
+
+    async fn register_topic(&self, topic: &Topic) -> Result<()> {
         let db = self.pool.get().await?;
         db.execute(
-            "update repository set tracked_at = current_timestamp where repository_id = $1;",
-            &[&repository_id],
+            "
+            insert into topic (
+                name,
+                display_name,
+                short_description,
+                description,
+                featured,
+                curated
+            ) values (
+                $1, $2, $3, $4, $5, $6
+            ) on conflict (name) do update
+            set
+                display_name = excluded.display_name,
+                short_description = excluded.short_description,
+                description = excluded.description,
+                featured = excluded.featured,
+                curated = excluded.curated,
+                updated_at = current_timestamp;
+            ",
+            &[
+                &topic.name,
+                &topic.display_name,
+                &topic.short_description,
+                &topic.description,
+                &topic.featured,
+                &topic.curated,
+            ],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn update_spotlight_issues(&self, cfg: &SpotlightCfg) -> Result<()> {
+        let mut db = self.pool.get().await?;
+        let tx = db.transaction().await?;
+        tx.execute("delete from spotlight_issues;", &[]).await?;
+        let query = format!(
+            "
+            insert into spotlight_issues (issue_id, score)
+            select issue_id, score from (
+                select
+                    i.issue_id,
+                    $2::double precision * coalesce(i.score, 0)
+                    + $3::double precision / (
+                        1 + extract(epoch from current_timestamp - i.published_at)
+                            ::double precision / 2592000
+                    )
+                    + $4::double precision * ln(1 + coalesce(r.stars, 0)) as score
+                from issue i
+                join repository r using (repository_id)
+                where r.disabled_at is null
+                {}
+            ) s
+            order by score desc
+            limit $1::bigint;
+            ",
+            self.issues_not_converted("i."),
+        );
+        tx.execute(
+            &query,
+            &[
+                &i64::from(cfg.size),
+                &cfg.score_weight,
+                &cfg.recency_weight,
+                &cfg.popularity_weight,
+            ],
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn update_beginner_leaderboard(&self, cfg: &BeginnerLeaderboardCfg) -> Result<()> {
+        let mut db = self.pool.get().await?;
+        let tx = db.transaction().await?;
+        tx.execute("delete from beginner_leaderboard;", &[]).await?;
+        let query = format!(
+            "
+            insert into beginner_leaderboard (issue_id, score)
+            select issue_id, score from (
+                select
+                    i.issue_id,
+                    $2::double precision * coalesce(i.score, 0)
+                    + $3::double precision / (
+                        1 + extract(epoch from current_timestamp - i.published_at)
+                            ::double precision / 2592000
+                    )
+                    + $4::double precision * ln(1 + coalesce(r.stars, 0)) as score
+                from issue i
+                join repository r using (repository_id)
+                where r.disabled_at is null
+                and i.good_first_issue = true
+                {}
+            ) s
+            order by score desc
+            limit $1::bigint;
+            ",
+            self.issues_not_converted("i."),
+        );
+        tx.execute(
+            &query,
+            &[
+                &i64::from(cfg.size),
+                &cfg.score_weight,
+                &cfg.recency_weight,
+                &cfg.popularity_weight,
+            ],
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn update_topic_stats(&self) -> Result<()> {
+        let mut db = self.pool.get().await?;
+        let tx = db.transaction().await?;
+        tx.execute("delete from topic_stats;", &[]).await?;
+        tx.execute(
+            "
+            insert into topic_stats (name, repositories, stars)
+            select
+                topic,
+                count(*),
+                coalesce(sum(stars), 0)
+            from (
+                select unnest(topics) as topic, stars
+                from repository
+                where tracked_at is not null
+                and disabled_at is null
+            ) t
+            group by topic;
+            ",
+            &[],
         )
         .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn update_org_stats(&self) -> Result<()> {
+        let mut db = self.pool.get().await?;
+        let tx = db.transaction().await?;
+        tx.execute("delete from org_stats;", &[]).await?;
+        let query = format!(
+            "
+            insert into org_stats (
+                name,
+                repositories,
+                stars,
+                open_issues,
+                most_active_repository_id
+            )
+            select
+                org,
+                count(*),
+                coalesce(sum(stars), 0),
+                sum(open_issues),
+                (array_agg(repository_id order by open_issues desc, stars desc nulls last))[1]
+            from (
+                select
+                    lower(split_part(r.url, '/', 4)) as org,
+                    r.repository_id,
+                    r.stars,
+                    (
+                        select count(*)
+                        from issue i
+                        where i.repository_id = r.repository_id
+                        {}
+                    ) as open_issues
+                from repository r
+                where r.tracked_at is not null
+                and r.disabled_at is null
+            ) r
+            where org <> ''
+            group by org;
+            ",
+            self.issues_not_converted("i."),
+        );
+        tx.execute(&query, &[]).await?;
+        tx.commit().await?;
         Ok(())
     }
 }
+
+/// Compute how long ago (in seconds) a repository must have been tracked to be
+/// considered stale, tolerating the clock skew provided, along with the clock
+/// skew tolerance actually applied. The tolerance is capped so that
+/// repositories are never tracked twice within half of the track interval.
+fn staleness_params(clock_skew_tolerance: u32) -> (f64, f64) {
+    let clock_skew_tolerance = clock_skew_tolerance.min(MAX_CLOCK_SKEW_TOLERANCE);
+    (
+        f64::from(REPOSITORY_TRACK_INTERVAL - clock_skew_tolerance),
+        f64::from(clock_skew_tolerance),
+    )
+}
+
+/// Create an issue instance from the db row provided.
+fn issue_from_row(row: &Row) -> Issue {
+    Issue {
+        issue_id: row.get("issue_id"),
+        title: row.get("title"),
+        url: row.get("url"),
+        number: row.get("number"),
+        labels: row.get("labels"),
+        published_at: row.get("published_at"),
+        has_linked_prs: row.get("has_linked_prs"),
+        linked_pr_url: row.get("linked_pr_url"),
+        linked_pr_state: row.get("linked_pr_state"),
+        last_activity_at: row.get("last_activity_at"),
+        reactions_by_type: row
+            .get::<_, Option<Json<Vec<(String, i32)>>>>("reactions_by_type")
+            .map(|Json(reactions_by_type)| reactions_by_type),
+        participants: row.get("participants"),
+        is_pinned: row.get("is_pinned"),
+        is_available: row.get("is_available"),
+        needs_triage: row.get("needs_triage"),
+        first_response_seconds: row.get("first_response_seconds"),
+        digest: row.get("digest"),
+        area: row.get("area"),
+        kind: row.get("kind"),
+        difficulty: row.get("difficulty"),
+        mentor_available: row.get("mentor_available"),
+        mentor: row.get("mentor"),
+        good_first_issue: row.get("good_first_issue"),
+    }
+}
+
+/// Create a repository instance from the db row provided.
+fn repository_from_row(row: &Row) -> Repository {
+    Repository {
+        repository_id: row.get("repository_id"),
+        name: row.get("name"),
+        description: row.get("description"),
+        url: row.get("url"),
+        homepage_url: row.get("homepage_url"),
+        topics: row.get("topics"),
+        languages: row.get("languages"),
+        primary_language: row.get("primary_language"),
+        primary_language_color: row.get("primary_language_color"),
+        stars: row.get("stars"),
+        digest: row.get("digest"),
+        issues_filter_label: row.get("issues_filter_label"),
+        open_issues_total: row.get("open_issues_total"),
+        closed_issues_total: row.get("closed_issues_total"),
+        default_branch_protected: row.get("default_branch_protected"),
+        query_version: row.get("query_version"),
+        is_active: row.get("is_active"),
+        issues_cursor: row.get("issues_cursor"),
+        issues_page_size: row.get("issues_page_size"),
+        requires_cla: row.get("requires_cla"),
+        top_contributors: row
+            .get::<_, Option<Json<Vec<(String, i32)>>>>("top_contributors")
+            .map(|Json(top_contributors)| top_contributors),
+        commit_activity: row.get("commit_activity"),
+        has_issues_enabled: row.get("has_issues_enabled"),
+        open_milestones: row.get("open_milestones"),
+        is_empty: row.get("is_empty"),
+        is_private: row.get("is_private"),
+        latest_release_at: row.get("latest_release_at"),
+        owner_followers: row.get("owner_followers"),
+        head_sha: row.get("head_sha"),
+        raw_response_digest: row.get("raw_response_digest"),
+        watch_star_ratio: row.get("watch_star_ratio"),
+        badges: row
+            .get::<_, Option<Json<Vec<(String, String)>>>>("badges")
+            .map(|Json(badges)| badges),
+        issue_close_rate: row.get("issue_close_rate"),
+        metadata: row
+            .get::<_, Option<String>>("metadata_category")
+            .map(|category| RepositoryMetadata {
+                category,
+                subcategory: row.get("metadata_subcategory"),
+                project_name: row.get("metadata_project_name"),
+            }),
+        project_name: row.get("project_name"),
+        foundation_id: row.get("foundation_id"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use bytes::BytesMut;
+    use postgres_types::{FromSql, Type};
+    use regex::Regex;
+    use time::{UtcOffset, macros::datetime};
+
+    use super::*;
+    use crate::tracker::TrackerCfg;
+
+    #[test]
+    fn timestamptz_round_trip_preserves_instant_in_utc() {
+        let ts = datetime!(2024-03-10 12:30:45.123456 +02:00);
+
+        let mut buf = BytesMut::new();
+        ts.to_sql(&Type::TIMESTAMPTZ, &mut buf).unwrap();
+        let ts_read = OffsetDateTime::from_sql(&Type::TIMESTAMPTZ, &buf).unwrap();
+
+        assert_eq!(ts_read, ts);
+        assert_eq!(ts_read.offset(), UtcOffset::UTC);
+        assert_eq!(ts_read.hour(), 10);
+    }
+
+    #[test]
+    fn schema_timestamps_stored_with_timezone() {
+        let schema_path =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../database/migrations/schema");
+        let timestamp = Regex::new(r"(?i)\btimestamp(\s+with\s+time\s+zone)?\b").unwrap();
+
+        for entry in fs::read_dir(schema_path).unwrap() {
+            let path = entry.unwrap().path();
+            let migration = fs::read_to_string(&path).unwrap();
+            for captures in timestamp.captures_iter(&migration) {
+                assert!(
+                    captures.get(1).is_some(),
+                    "timestamp without time zone found in {}",
+                    path.display()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn rows_mapped_to_matching_columns() {
+        let source =
+            fs::read_to_string(Path::new(env!("CARGO_MANIFEST_DIR")).join("src/db.rs")).unwrap();
+        let mapping = Regex::new(r#"(\w+): row\s*\.get[^"]*"(\w+)""#).unwrap();
+
+        for (from_row_fn, columns) in [
+            ("fn issue_from_row(", ISSUE_COLUMNS),
+            ("fn repository_from_row(", REPOSITORY_COLUMNS),
+        ] {
+            let start = source.find(from_row_fn).unwrap();
+            let end = start + source[start..].find("\n}\n").unwrap();
+            let mut mapped = vec![];
+            for captures in mapping.captures_iter(&source[start..end]) {
+                // Repository metadata fields are read from the prefixed columns
+                let (field, column) = (&captures[1], &captures[2]);
+                assert!(
+                    field == column || column.starts_with("metadata_"),
+                    "{from_row_fn} {field}: {column}"
+                );
+                mapped.push(captures[2].to_string());
+            }
+            for column in columns {
+                assert!(
+                    mapped.contains(&(*column).to_string()),
+                    "{from_row_fn} {column}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn staleness_params_tolerate_capped_clock_skew() {
+        assert_eq!(staleness_params(0), (1800.0, 0.0));
+        assert_eq!(staleness_params(60), (1740.0, 60.0));
+        assert_eq!(staleness_params(899), (901.0, 899.0));
+        assert_eq!(staleness_params(900), (900.0, 900.0));
+        assert_eq!(staleness_params(901), (900.0, 900.0));
+        assert_eq!(staleness_params(u32::MAX), (900.0, 900.0));
+    }
+
+    #[test]
+    fn staleness_threshold_unchanged_with_default_config() {
+        let cfg = TrackerCfg::default();
+        assert_eq!(
+            staleness_params(cfg.clock_skew_tolerance_seconds),
+            (f64::from(REPOSITORY_TRACK_INTERVAL), 0.0)
+        );
+    }
+
+    #[test]
+    fn repositories_to_track_query_checks_staleness_with_tolerance() {
+        let manager =
+            deadpool_postgres::Manager::new(tokio_postgres::Config::new(), tokio_postgres::NoTls);
+        let pool = Pool::builder(manager).build().unwrap();
+        let db = PgDB::new(pool);
+        let query = db.repositories_to_track_query();
+        assert!(query.contains(
+            "r.tracked_at < current_timestamp - make_interval(secs => $4::double precision)"
+        ));
+        assert!(query.contains("$2::timestamptz - make_interval(secs => $5::double precision)\n"));
+        assert!(
+            query.contains("and $3::timestamptz + make_interval(secs => $5::double precision)\n")
+        );
+    }
+
+    #[test]
+    fn register_issue_query_clears_discussion_conversion() {
+        let manager =
+            deadpool_postgres::Manager::new(tokio_postgres::Config::new(), tokio_postgres::NoTls);
+        let pool = Pool::builder(manager).build().unwrap();
+        let mut db = PgDB::new(pool);
+        let query = db.register_issue_query(&["issue_id", "title"]);
+        assert!(query.contains("insert into issue (issue_id, title, tsdoc)"));
+        assert!(query.contains(
+            "set title = excluded.title, converted_to_discussion_url = null, tsdoc = excluded.tsdoc;"
+        ));
+
+        db.missing_columns = vec![("issue", "converted_to_discussion_url", "text")];
+        let query = db.register_issue_query(&["issue_id", "title"]);
+        assert!(query.contains("set title = excluded.title, tsdoc = excluded.tsdoc;"));
+    }
+
+    #[test]
+    fn select_columns_reads_missing_columns_as_null() {
+        let manager =
+            deadpool_postgres::Manager::new(tokio_postgres::Config::new(), tokio_postgres::NoTls);
+        let pool = Pool::builder(manager).build().unwrap();
+        let mut db = PgDB::new(pool);
+        assert_eq!(
+            db.select_columns("repository", "r.", &["name", "badges", "head_sha"]),
+            "r.name, r.badges, r.head_sha"
+        );
+
+        db.missing_columns = vec![
+            ("repository", "badges", "jsonb"),
+            ("repository", "head_sha", "text"),
+        ];
+        assert_eq!(
+            db.select_columns("repository", "r.", &["name", "badges", "head_sha"]),
+            "r.name, null::jsonb as badges, null::text as head_sha"
+        );
+        assert_eq!(
+            db.select_columns("issue", "", &["badges", "head_sha"]),
+            "badges, head_sha"
+        );
+        assert!(
+            db.repositories_to_track_query()
+                .contains("null::jsonb as badges, r.issue_close_rate,")
+        );
+        assert!(!db.has_column("repository", "badges"));
+        assert!(db.has_column("issue", "badges"));
+    }
+}
@@ -1,22 +1,43 @@
-use std::sync::{Arc, LazyLock};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::{
+        Arc, LazyLock, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
 
-use anyhow::{Context, Result, bail, format_err};
+use anyhow::{Context, Error, Result, bail, format_err};
 use async_trait::async_trait;
 use graphql_client::{GraphQLQuery, Response};
 #[cfg(test)]
 use mockall::automock;
 use regex::Regex;
-use reqwest::StatusCode;
+use reqwest::{StatusCode, Url, header::HeaderMap};
+use serde::Deserialize;
 use time::{
     OffsetDateTime,
     ext::NumericalDuration,
     format_description::well_known::{Iso8601, Rfc3339},
 };
+use tokio::time::sleep;
+use tracing::warn;
+
+use crate::tracker::{
+    ClosedIssue, Issue, IssueAbsenceReason, IssueReference, IssueScoreWeights, Label, Topic,
+    TrackerCfg,
+};
+
+/// GitHub REST API base URL.
+const GITHUB_REST_API_URL: &str = "https://api.github.com";
 
-use crate::tracker::Issue;
+/// GitHub REST API topics search URL.
+const GITHUB_SEARCH_TOPICS_URL: &str = "https://api.github.com/search/topics";
 
-/// GitHub GraphQL API URL.
-const GITHUB_GRAPHQL_API_URL: &str = "https://api.github.com/graphql";
+/// Maximum number of attempts when fetching the commit activity statistics
+/// while GitHub is still computing them.
+const COMMIT_ACTIVITY_MAX_ATTEMPTS: u32 = 4;
 
 /// Label used to filter the issues we want to track.
 const DEFAULT_ISSUES_FILTER_LABEL: &str = "help wanted";
@@ -26,6 +47,17 @@ static GITHUB_REPO_URL: LazyLock<Regex> = LazyLock::new(|| {
         .expect("exprs in GITHUB_REPO_URL to be valid")
 });
 
+static CLA_KEYWORDS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\b(cla|dco|contributor license agreement|developer certificate of origin|signed-off-by)\b",
+    )
+    .expect("exprs in CLA_KEYWORDS to be valid")
+});
+
+static README_BADGE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\[!\[([^\]]*)\]\((\S+?)\)\]\(\S+?\)").expect("exprs in README_BADGE to be valid")
+});
+
 /// Type alias to represent a GH trait object.
 pub(crate) type DynGH = Arc<dyn GH + Send + Sync>;
 
@@ -36,18 +68,310 @@ type URI = String;
 /// Type alias for GraphQL DateTime scalar type.
 type DateTime = String;
 
+/// Type alias for GraphQL GitObjectID scalar type.
+type GitObjectID = String;
+
+/// Version of the repository view query. It must be bumped every time the
+/// query in `repo_view.graphql` is modified.
+pub(crate) const REPO_VIEW_QUERY_VERSION: i32 = 23;
+
 /// GitHub repository view (represents GitHub GraphQL API query).
 #[derive(Debug, Clone, GraphQLQuery)]
 #[graphql(
     schema_path = "src/graphql/github_schema.graphql",
     query_path = "src/graphql/repo_view.graphql",
-    response_derives = "Debug, PartialEq, Eq"
+    response_derives = "Debug, PartialEq, Eq, Serialize"
 )]
 pub struct RepoView;
 
+/// GitHub issues activity (represents GitHub GraphQL API query).
+#[derive(Debug, Clone, GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/github_schema.graphql",
+    query_path = "src/graphql/issues_activity.graphql",
+    response_derives = "Debug, PartialEq, Eq"
+)]
+pub struct IssuesActivity;
+
+/// GitHub issues first response (represents GitHub GraphQL API query).
+#[derive(Debug, Clone, GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/github_schema.graphql",
+    query_path = "src/graphql/issues_first_response.graphql",
+    response_derives = "Debug, PartialEq, Eq"
+)]
+pub struct IssuesFirstResponse;
+
+/// GitHub issue discussion (represents GitHub GraphQL API query).
+#[derive(Debug, Clone, GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/github_schema.graphql",
+    query_path = "src/graphql/issue_discussion.graphql",
+    response_derives = "Debug, PartialEq, Eq"
+)]
+pub struct IssueDiscussion;
+
+/// GitHub issues references (represents GitHub GraphQL API query).
+#[derive(Debug, Clone, GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/github_schema.graphql",
+    query_path = "src/graphql/issues_references.graphql",
+    response_derives = "Debug, PartialEq, Eq"
+)]
+pub struct IssuesReferences;
+
+/// GitHub recently closed issues (represents GitHub GraphQL API query).
+#[derive(Debug, Clone, GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/github_schema.graphql",
+    query_path = "src/graphql/recently_closed_issues.graphql",
+    response_derives = "Debug, PartialEq, Eq"
+)]
+pub struct RecentlyClosedIssues;
+
+/// GitHub issues close rate (represents GitHub GraphQL API query).
+#[derive(Debug, Clone, GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/github_schema.graphql",
+    query_path = "src/graphql/issues_close_rate.graphql",
+    response_derives = "Debug, PartialEq, Eq"
+)]
+pub struct IssuesCloseRate;
+
+impl repo_view::RepoViewRepositoryIssuesNodes {
+    /// Return whether the issue is available to be worked on: open (only open
+    /// issues are fetched), unassigned, not locked and not linked to an open
+    /// PR.
+    pub(crate) fn is_available(&self) -> bool {
+        let linked_to_open_pr = self
+            .closed_by_pull_requests_references
+            .as_ref()
+            .and_then(|refs| refs.nodes.as_ref())
+            .is_some_and(|prs| {
+                prs.iter()
+                    .flatten()
+                    .any(|pr| pr.state == repo_view::PullRequestState::OPEN)
+            });
+        self.assignees.total_count == 0 && !self.locked && !linked_to_open_pr
+    }
+
+    /// Return whether the issue is awaiting triage: open (only open issues are
+    /// fetched), unassigned and without labels. The label used to filter the
+    /// issues fetched is not considered, as all of them have it.
+    pub(crate) fn needs_triage(&self, issues_filter_label: &str) -> bool {
+        let has_labels = self
+            .labels
+            .as_ref()
+            .and_then(|labels| labels.nodes.as_ref())
+            .is_some_and(|nodes| {
+                nodes
+                    .iter()
+                    .flatten()
+                    .any(|label| !label.name.eq_ignore_ascii_case(issues_filter_label))
+            });
+        self.assignees.total_count == 0 && !has_labels
+    }
+}
+
 impl repo_view::RepoViewRepository {
+    /// Return the most recent issue activity (creation or update) timestamp.
+    /// The issues last activity provided, when available, is used instead of
+    /// the issue update timestamp.
+    pub(crate) fn issues_last_activity(
+        &self,
+        issues_activity: &HashMap<i64, OffsetDateTime>,
+    ) -> Option<OffsetDateTime> {
+        self.issues
+            .nodes
+            .as_ref()?
+            .iter()
+            .flatten()
+            .flat_map(|node| {
+                let published_at = node
+                    .published_at
+                    .as_ref()
+                    .and_then(|ts| OffsetDateTime::parse(ts, &Rfc3339).ok());
+                let last_activity_at = node
+                    .database_id
+                    .and_then(|issue_id| issues_activity.get(&issue_id).copied())
+                    .or_else(|| OffsetDateTime::parse(&node.updated_at, &Rfc3339).ok());
+                [published_at, last_activity_at]
+            })
+            .flatten()
+            .max()
+    }
+
+    /// Return whether the repository is dormant: nothing has been pushed to it
+    /// and there has been no issues activity since the cutoff provided. When
+    /// the last push timestamp is not available, it's not considered dormant.
+    pub(crate) fn is_dormant(
+        &self,
+        cutoff: OffsetDateTime,
+        issues_activity: &HashMap<i64, OffsetDateTime>,
+    ) -> bool {
+        let Some(pushed_at) = self
+            .pushed_at
+            .as_ref()
+            .and_then(|ts| OffsetDateTime::parse(ts, &Rfc3339).ok())
+        else {
+            return false;
+        };
+        pushed_at < cutoff
+            && self
+                .issues_last_activity(issues_activity)
+                .is_none_or(|ts| ts < cutoff)
+    }
+
+    /// Return the creation timestamp of the repository's latest release.
+    pub(crate) fn latest_release_at(&self) -> Option<OffsetDateTime> {
+        let release = self
+            .latest_release
+            .nodes
+            .as_ref()?
+            .iter()
+            .flatten()
+            .next()?;
+        OffsetDateTime::parse(&release.created_at, &Rfc3339).ok()
+    }
+
+    /// Return the score of each of the repository issues, computed using the
+    /// weights provided.
+    pub(crate) fn issues_scores(&self, weights: &IssueScoreWeights) -> Vec<(i64, f64)> {
+        let now = OffsetDateTime::now_utc();
+        self.issues
+            .nodes
+            .iter()
+            .flatten()
+            .flatten()
+            .filter_map(|node| {
+                let issue_id = node.database_id?;
+                let published_at =
+                    OffsetDateTime::parse(node.published_at.as_ref()?, &Rfc3339).ok()?;
+                let updated_at = OffsetDateTime::parse(&node.updated_at, &Rfc3339).ok()?;
+                let score = weights.score(
+                    now - published_at,
+                    now - updated_at,
+                    node.reactions.total_count,
+                    node.comments.total_count,
+                );
+                Some((issue_id, score))
+            })
+            .collect()
+    }
+
+    /// Return the node ids of the repository issues.
+    pub(crate) fn issues_ids(&self) -> Vec<String> {
+        self.issues
+            .nodes
+            .iter()
+            .flatten()
+            .flatten()
+            .map(|node| node.id.clone())
+            .collect()
+    }
+
+    /// Return the node ids of the repository's issues whose database id is
+    /// included in the collection provided.
+    pub(crate) fn issues_ids_matching(&self, issues_ids: &HashSet<i64>) -> Vec<String> {
+        self.issues
+            .nodes
+            .iter()
+            .flatten()
+            .flatten()
+            .filter(|node| node.database_id.is_some_and(|id| issues_ids.contains(&id)))
+            .map(|node| node.id.clone())
+            .collect()
+    }
+
+    /// Return whether the repository requires signing a CLA or DCO, based on
+    /// the content of its contributing guide (none if it isn't available).
+    pub(crate) fn requires_cla(&self) -> Option<bool> {
+        use repo_view::{
+            RepoViewRepositoryContributingGuide as Guide,
+            RepoViewRepositoryGithubContributingGuide as GithubGuide,
+        };
+
+        let guide = self
+            .contributing_guide
+            .as_ref()
+            .and_then(|guide| match guide {
+                Guide::Blob(blob) => blob.text.as_ref(),
+                _ => None,
+            });
+        let github_guide = self
+            .github_contributing_guide
+            .as_ref()
+            .and_then(|guide| match guide {
+                GithubGuide::Blob(blob) => blob.text.as_ref(),
+                _ => None,
+            });
+        guide
+            .or(github_guide)
+            .map(|text| CLA_KEYWORDS.is_match(text))
+    }
+
+    /// Return the badges (label and image url) found in the repository's
+    /// README file. Only the images wrapped in a link are considered badges.
+    pub(crate) fn badges(&self) -> Option<Vec<(String, String)>> {
+        let Some(repo_view::RepoViewRepositoryReadme::Blob(blob)) = &self.readme else {
+            return None;
+        };
+        let text = blob.text.as_ref()?;
+        Some(
+            README_BADGE
+                .captures_iter(text)
+                .map(|c| (c[1].trim().to_string(), c[2].to_string()))
+                .collect(),
+        )
+    }
+
+    /// Return the definitions of the labels used by the repository issues.
+    pub(crate) fn labels(&self) -> Vec<Label> {
+        let mut labels: Vec<Label> = vec![];
+        for node in self.issues.nodes.iter().flatten().flatten() {
+            let label_nodes = node
+                .labels
+                .as_ref()
+                .and_then(|labels| labels.nodes.as_ref());
+            for label in label_nodes.into_iter().flatten().flatten() {
+                if !labels.iter().any(|l| l.name == label.name) {
+                    labels.push(Label {
+                        name: label.name.clone(),
+                        color: label.color.clone(),
+                        description: label.description.clone(),
+                    });
+                }
+            }
+        }
+        labels
+    }
+
+    /// Return the database ids of the repository's pinned issues.
+    fn pinned_issues_ids(&self) -> HashSet<i64> {
+        self.pinned_issues
+            .as_ref()
+            .and_then(|pinned_issues| pinned_issues.nodes.as_ref())
+            .map(|nodes| {
+                nodes
+                    .iter()
+                    .flatten()
+                    .filter_map(|node| node.issue.database_id)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     /// Return repository issues.
-    pub(crate) fn issues(&self) -> Vec<Issue> {
+    #[allow(clippy::too_many_lines)]
+    pub(crate) fn issues(
+        &self,
+        cfg: &TrackerCfg,
+        issues_filter_label: Option<&String>,
+        issues_activity: &HashMap<i64, OffsetDateTime>,
+    ) -> Vec<Issue> {
+        let pinned_issues_ids = self.pinned_issues_ids();
+        let issues_filter_label =
+            issues_filter_label.map_or(DEFAULT_ISSUES_FILTER_LABEL, String::as_str);
         self.issues
             .nodes
             .as_ref()
@@ -62,13 +386,30 @@ impl repo_view::RepoViewRepository {
                         }
 
                         // Check if the are PRs linked to the issue
-                        let has_linked_prs = node
+                        let linked_prs = node
                             .closed_by_pull_requests_references
                             .as_ref()
-                            .and_then(|refs| refs.nodes.as_ref())
-                            .is_some_and(|prs| !prs.is_empty());
+                            .and_then(|refs| refs.nodes.as_ref());
+                        let has_linked_prs = linked_prs.is_some_and(|prs| !prs.is_empty());
 
-                        // Prepare labels
+                        // Prepare linked PR details (if enabled)
+                        let linked_pr = linked_prs
+                            .filter(|_| cfg.fetch_linked_pr_details)
+                            .and_then(|prs| prs.iter().flatten().next());
+                        let linked_pr_url = linked_pr.map(|pr| pr.url.clone());
+                        let linked_pr_state =
+                            linked_pr.map(|pr| pull_request_state_name(&pr.state));
+
+                        // Check if the issue is available to be worked on (if
+                        // enabled)
+                        let is_available = cfg.compute_availability.then(|| node.is_available());
+
+                        // Check if the issue is awaiting triage (if enabled)
+                        let needs_triage = cfg
+                            .compute_needs_triage
+                            .then(|| node.needs_triage(issues_filter_label));
+
+                        // Prepare labels (mapped to their canonical form)
                         let labels = node
                             .labels
                             .as_ref()
@@ -76,14 +417,33 @@ impl repo_view::RepoViewRepository {
                             .nodes
                             .as_ref()
                             .map(|nodes| {
-                                nodes
-                                    .iter()
-                                    .flatten()
-                                    .map(|node| node.name.clone())
-                                    .collect()
+                                let mut labels: Vec<String> = vec![];
+                                for node in nodes.iter().flatten() {
+                                    let label =
+                                        canonical_label(&node.name, cfg.label_aliases.as_ref());
+                                    if !labels.contains(&label) {
+                                        labels.push(label);
+                                    }
+                                }
+                                labels
                             })
                             .unwrap();
 
+                        // Prepare reactions breakdown (only reactions used)
+                        #[allow(clippy::cast_possible_truncation)]
+                        let reactions_by_type = node.reaction_groups.as_ref().map(|groups| {
+                            groups
+                                .iter()
+                                .filter(|group| group.reactors.total_count > 0)
+                                .map(|group| {
+                                    (
+                                        reaction_name(&group.content),
+                                        group.reactors.total_count as i32,
+                                    )
+                                })
+                                .collect()
+                        });
+
                         // Prepare published date
                         let published_at =
                             OffsetDateTime::parse(node.published_at.as_ref().unwrap(), &Rfc3339)
@@ -94,11 +454,26 @@ impl repo_view::RepoViewRepository {
                         let mut issue = Issue {
                             issue_id: node.database_id.unwrap(),
                             title: node.title.clone(),
-                            url: node.url.clone(),
+                            url: if cfg.normalize_issue_urls {
+                                normalize_issue_url(&node.url)
+                            } else {
+                                node.url.clone()
+                            },
                             number: node.number as i32,
                             labels,
                             published_at,
                             has_linked_prs,
+                            linked_pr_url,
+                            linked_pr_state,
+                            last_activity_at: issues_activity
+                                .get(&node.database_id.unwrap())
+                                .copied(),
+                            reactions_by_type,
+                            participants: node.participants.total_count as i32,
+                            is_pinned: pinned_issues_ids.contains(&node.database_id.unwrap()),
+                            is_available,
+                            needs_triage,
+                            first_response_seconds: None,
                             digest: None,
                             area: None,
                             kind: None,
@@ -107,7 +482,7 @@ impl repo_view::RepoViewRepository {
                             mentor: None,
                             good_first_issue: None,
                         };
-                        issue.populate_from_labels();
+                        issue.populate_from_labels(cfg.difficulty_labels.as_ref());
                         issue.update_digest();
 
                         Some(issue)
@@ -129,16 +504,168 @@ pub(crate) trait GH {
         token: &str,
         url: &str,
         issues_filter_label: Option<&String>,
+        issues_cursor: Option<&String>,
+        issues_page_size: u32,
+        cfg: &TrackerCfg,
     ) -> Result<repo_view::RepoViewRepository>;
+
+    /// Get the last activity timestamp of the issues provided (by node id),
+    /// based on their most recent timeline item. Issues are keyed by their
+    /// database id.
+    async fn issues_activity(
+        &self,
+        token: &str,
+        ids: &[String],
+    ) -> Result<HashMap<i64, OffsetDateTime>>;
+
+    /// Get the time (in seconds) it took the issues provided (by node id) to
+    /// get their first comment or label, based on their timeline. Issues are
+    /// keyed by their database id, and the ones without a response yet are
+    /// not included.
+    async fn issues_first_response(&self, token: &str, ids: &[String])
+    -> Result<HashMap<i64, i64>>;
+
+    /// Get the issues that cross-reference the issues provided (by node id),
+    /// based on their timeline.
+    async fn issues_references(&self, token: &str, ids: &[String]) -> Result<Vec<IssueReference>>;
+
+    /// Get the issues of the repository provided closed since the timestamp
+    /// given (only the ones closed as completed if requested).
+    async fn recently_closed_issues(
+        &self,
+        token: &str,
+        url: &str,
+        since: OffsetDateTime,
+        completed_only: bool,
+    ) -> Result<Vec<ClosedIssue>>;
+
+    /// Get the number of issues of the repository provided opened and closed
+    /// since the timestamp given.
+    async fn issues_opened_and_closed(
+        &self,
+        token: &str,
+        url: &str,
+        since: OffsetDateTime,
+    ) -> Result<(i64, i64)>;
+
+    /// Get topic metadata from GitHub.
+    async fn topic(&self, token: &str, name: &str) -> Result<Option<Topic>>;
+
+    /// Get the top contributors of the repository provided (login and number
+    /// of contributions).
+    async fn contributors(&self, token: &str, url: &str, limit: u32) -> Result<Vec<(String, i32)>>;
+
+    /// Get the weekly commit counts of the repository provided over the last
+    /// year. None is returned when GitHub is still computing them.
+    async fn commit_activity(&self, token: &str, url: &str) -> Result<Option<Vec<i32>>>;
+
+    /// Get the number of followers of the owner (organization or user) of the
+    /// repository provided.
+    async fn owner_followers(&self, token: &str, url: &str) -> Result<i32>;
+
+    /// Find out why the issue provided is no longer returned when fetching
+    /// the repository data.
+    async fn issue_absence_reason(
+        &self,
+        token: &str,
+        url: &str,
+        number: i32,
+    ) -> Result<IssueAbsenceReason>;
+
+    /// Get the url of the discussion the issue provided was converted into
+    /// (if any), based on its timeline.
+    async fn issue_discussion_url(
+        &self,
+        token: &str,
+        url: &str,
+        number: i32,
+    ) -> Result<Option<String>>;
+
+    /// Get the GraphQL API budget (remaining points) of the token provided,
+    /// as reported in the last response received using it (if any).
+    fn remaining_budget(&self, token: &str) -> Option<u32>;
+
+    /// Get the cost of the last repository view query done with the token
+    /// provided, when it was requested.
+    fn last_query_cost(&self, token: &str) -> Option<u32>;
 }
 
 /// GH implementation backed by the GitHub GraphQL API.
-pub(crate) struct GHGraphQL {}
+pub(crate) struct GHGraphQL {
+    graphql_url: String,
+    reported_forbidden_fields: Mutex<HashSet<String>>,
+    report_deprecations: bool,
+    reported_deprecation: AtomicBool,
+    remaining_budgets: Mutex<HashMap<String, u32>>,
+    query_costs: Mutex<HashMap<String, u32>>,
+}
 
 impl GHGraphQL {
-    /// Create a new GHGraphQL instance.
-    pub(crate) fn new() -> Self {
-        Self {}
+    /// Create a new GHGraphQL instance. The GraphQL path provided is appended
+    /// to the GitHub API base URL to build the GraphQL endpoint.
+    pub(crate) fn new(graphql_path: &str, report_deprecations: bool) -> Self {
+        Self {
+            graphql_url: format!(
+                "{GITHUB_REST_API_URL}/{}",
+                graphql_path.trim_start_matches('/')
+            ),
+            reported_forbidden_fields: Mutex::new(HashSet::new()),
+            report_deprecations,
+            reported_deprecation: AtomicBool::new(false),
+            remaining_budgets: Mutex::new(HashMap::new()),
+            query_costs: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Log a warning if the response provided includes a deprecation notice,
+    /// only the first time one is found.
+    fn report_deprecation(&self, resp: &reqwest::Response) {
+        if !self.report_deprecations {
+            return;
+        }
+        let Some(notice) = DeprecationNotice::from_headers(resp.headers()) else {
+            return;
+        };
+        if !self.reported_deprecation.swap(true, Ordering::Relaxed) {
+            warn!(
+                url = %resp.url(),
+                deprecation = notice.deprecation,
+                sunset = notice.sunset,
+                link = notice.link,
+                "github api deprecation notice received, the queries used may break soon"
+            );
+        }
+    }
+
+    /// Record the GraphQL API budget remaining for the token provided, as
+    /// reported in the response given.
+    fn record_remaining_budget(&self, token: &str, resp: &reqwest::Response) {
+        let Some(remaining) = resp
+            .headers()
+            .get("x-ratelimit-remaining")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok())
+        else {
+            return;
+        };
+        self.remaining_budgets
+            .lock()
+            .expect("lock not poisoned")
+            .insert(token.to_string(), remaining);
+    }
+
+    /// Log the forbidden fields provided, only the first time each of them is
+    /// found.
+    fn report_forbidden_fields(&self, forbidden_fields: Vec<String>) {
+        let mut reported_forbidden_fields = self
+            .reported_forbidden_fields
+            .lock()
+            .expect("lock not poisoned");
+        for field in forbidden_fields {
+            if reported_forbidden_fields.insert(field.clone()) {
+                warn!(field, "field forbidden for the token used, ignoring it");
+            }
+        }
     }
 }
 
@@ -149,6 +676,9 @@ impl GH for GHGraphQL {
         token: &str,
         url: &str,
         issues_filter_label: Option<&String>,
+        issues_cursor: Option<&String>,
+        issues_page_size: u32,
+        cfg: &TrackerCfg,
     ) -> Result<repo_view::RepoViewRepository> {
         // Do request to GraphQL API
         let http_client = setup_http_client(token)?;
@@ -164,14 +694,84 @@ impl GH for GHGraphQL {
             owner,
             issues_label,
             issues_since,
+            issues_cursor: issues_cursor.cloned(),
+            issues_page_size: i64::from(issues_page_size),
+            fetch_branch_protection: cfg.fetch_branch_protection,
+            fetch_reaction_breakdown: cfg.fetch_reaction_breakdown,
+            detect_cla: cfg.detect_cla,
+            fetch_head_sha: cfg.fetch_head_sha,
+            fetch_pushed_at: cfg.dormant_repo_cutoff_days.is_some(),
+            parse_badges: cfg.parse_badges,
+            fetch_query_cost: cfg.adaptive_issues_page_size.is_some(),
         };
         let req_body = &RepoView::build_query(vars);
         let resp = http_client
-            .post(GITHUB_GRAPHQL_API_URL)
+            .post(&self.graphql_url)
+            .json(req_body)
+            .send()
+            .await
+            .context("error querying graphql api")?;
+        self.report_deprecation(&resp);
+        self.record_remaining_budget(token, &resp);
+        let status = resp.status();
+        if status != StatusCode::OK {
+            let headers = resp.headers().clone();
+            let resp_body = resp.text().await?;
+            let mut err = Error::new(UnexpectedStatus(status));
+            if is_rate_limited(status, &headers, &resp_body) {
+                err = err.context(RateLimited);
+            } else if matches!(status, StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN) {
+                err = err.context(AuthError);
+            }
+            return Err(err.context(format!(
+                "unexpected status code querying graphql api: {status} - {resp_body}"
+            )));
+        }
+
+        // Parse response body and extract repository data
+        let resp_body = resp.text().await?;
+        let (repository, forbidden_fields, cost) = parse_repo_view_response(&resp_body)?;
+        if let Some(cost) = cost {
+            self.query_costs
+                .lock()
+                .expect("lock not poisoned")
+                .insert(token.to_string(), cost);
+        }
+        if !forbidden_fields.is_empty() {
+            if cfg.fail_on_forbidden_fields {
+                bail!(
+                    "forbidden fields in query response: {}",
+                    forbidden_fields.join(", ")
+                );
+            }
+            self.report_forbidden_fields(forbidden_fields);
+        }
+
+        Ok(repository)
+    }
+
+    async fn issues_activity(
+        &self,
+        token: &str,
+        ids: &[String],
+    ) -> Result<HashMap<i64, OffsetDateTime>> {
+        use issues_activity::{
+            IssuesActivityNodes as Node,
+            IssuesActivityNodesOnIssueTimelineItemsNodes as TimelineItem,
+        };
+
+        // Do request to GraphQL API
+        let http_client = setup_http_client(token)?;
+        let vars = issues_activity::Variables { ids: ids.to_vec() };
+        let req_body = &IssuesActivity::build_query(vars);
+        let resp = http_client
+            .post(&self.graphql_url)
             .json(req_body)
             .send()
             .await
             .context("error querying graphql api")?;
+        self.report_deprecation(&resp);
+        self.record_remaining_budget(token, &resp);
         if resp.status() != StatusCode::OK {
             bail!(
                 "unexpected status code querying graphql api: {} - {}",
@@ -180,17 +780,691 @@ impl GH for GHGraphQL {
             );
         }
 
-        // Parse response body and extract repository data
+        // Parse response body and extract issues last activity
+        let resp_body = resp.text().await?;
+        let nodes = serde_json::from_str::<Response<issues_activity::ResponseData>>(&resp_body)
+            .context(format!("error deserializing query response: {resp_body}"))?
+            .data
+            .ok_or_else(|| format_err!("data field not found: {resp_body}"))?
+            .nodes;
+        let mut issues_activity = HashMap::new();
+        for node in nodes.into_iter().flatten() {
+            let Node::Issue(issue) = node else {
+                continue;
+            };
+            let Some(issue_id) = issue.database_id else {
+                continue;
+            };
+            let last_item = issue
+                .timeline_items
+                .nodes
+                .into_iter()
+                .flatten()
+                .flatten()
+                .last();
+            let ts = match last_item {
+                Some(TimelineItem::CrossReferencedEvent(item)) => item.created_at,
+                Some(TimelineItem::IssueComment(item)) => item.updated_at,
+                Some(TimelineItem::LabeledEvent(item)) => item.created_at,
+                Some(TimelineItem::UnlabeledEvent(item)) => item.created_at,
+                _ => continue,
+            };
+            if let Ok(ts) = OffsetDateTime::parse(&ts, &Rfc3339) {
+                issues_activity.insert(issue_id, ts);
+            }
+        }
+
+        Ok(issues_activity)
+    }
+
+    async fn issues_first_response(
+        &self,
+        token: &str,
+        ids: &[String],
+    ) -> Result<HashMap<i64, i64>> {
+        use issues_first_response::{
+            IssuesFirstResponseNodes as Node,
+            IssuesFirstResponseNodesOnIssueTimelineItemsNodes as TimelineItem,
+        };
+
+        // Do request to GraphQL API
+        let http_client = setup_http_client(token)?;
+        let vars = issues_first_response::Variables { ids: ids.to_vec() };
+        let req_body = &IssuesFirstResponse::build_query(vars);
+        let resp = http_client
+            .post(&self.graphql_url)
+            .json(req_body)
+            .send()
+            .await
+            .context("error querying graphql api")?;
+        self.report_deprecation(&resp);
+        self.record_remaining_budget(token, &resp);
+        if resp.status() != StatusCode::OK {
+            bail!(
+                "unexpected status code querying graphql api: {} - {}",
+                resp.status(),
+                resp.text().await?,
+            );
+        }
+
+        // Parse response body and extract issues first response time
         let resp_body = resp.text().await?;
-        let repo = serde_json::from_str::<Response<repo_view::ResponseData>>(&resp_body)
+        let nodes =
+            serde_json::from_str::<Response<issues_first_response::ResponseData>>(&resp_body)
+                .context(format!("error deserializing query response: {resp_body}"))?
+                .data
+                .ok_or_else(|| format_err!("data field not found: {resp_body}"))?
+                .nodes;
+        let mut issues_first_response = HashMap::new();
+        for node in nodes.into_iter().flatten() {
+            let Node::Issue(issue) = node else {
+                continue;
+            };
+            let Some(issue_id) = issue.database_id else {
+                continue;
+            };
+            let first_item = issue
+                .timeline_items
+                .nodes
+                .into_iter()
+                .flatten()
+                .flatten()
+                .next();
+            let ts = match first_item {
+                Some(TimelineItem::IssueComment(item)) => item.created_at,
+                Some(TimelineItem::LabeledEvent(item)) => item.created_at,
+                _ => continue,
+            };
+            if let (Ok(created_at), Ok(ts)) = (
+                OffsetDateTime::parse(&issue.created_at, &Rfc3339),
+                OffsetDateTime::parse(&ts, &Rfc3339),
+            ) {
+                let seconds = (ts - created_at).whole_seconds().max(0);
+                issues_first_response.insert(issue_id, seconds);
+            }
+        }
+
+        Ok(issues_first_response)
+    }
+
+    async fn issues_references(&self, token: &str, ids: &[String]) -> Result<Vec<IssueReference>> {
+        use issues_references::{
+            IssuesReferencesNodes as Node,
+            IssuesReferencesNodesOnIssueTimelineItemsNodes as TimelineItem,
+            IssuesReferencesNodesOnIssueTimelineItemsNodesOnCrossReferencedEventSource as Source,
+        };
+
+        // Do request to GraphQL API
+        let http_client = setup_http_client(token)?;
+        let vars = issues_references::Variables { ids: ids.to_vec() };
+        let req_body = &IssuesReferences::build_query(vars);
+        let resp = http_client
+            .post(&self.graphql_url)
+            .json(req_body)
+            .send()
+            .await
+            .context("error querying graphql api")?;
+        self.report_deprecation(&resp);
+        self.record_remaining_budget(token, &resp);
+        if resp.status() != StatusCode::OK {
+            bail!(
+                "unexpected status code querying graphql api: {} - {}",
+                resp.status(),
+                resp.text().await?,
+            );
+        }
+
+        // Parse response body and extract the issues cross-referencing others
+        let resp_body = resp.text().await?;
+        let nodes = serde_json::from_str::<Response<issues_references::ResponseData>>(&resp_body)
             .context(format!("error deserializing query response: {resp_body}"))?
             .data
             .ok_or_else(|| format_err!("data field not found: {resp_body}"))?
-            .repository
-            .ok_or_else(|| format_err!("repository field not found: {resp_body}"))?;
+            .nodes;
+        let mut issues_references = vec![];
+        for node in nodes.into_iter().flatten() {
+            let Node::Issue(issue) = node else {
+                continue;
+            };
+            let Some(issue_id) = issue.database_id else {
+                continue;
+            };
+            for item in issue.timeline_items.nodes.into_iter().flatten().flatten() {
+                let TimelineItem::CrossReferencedEvent(event) = item else {
+                    continue;
+                };
+                let Source::Issue(source) = event.source else {
+                    continue;
+                };
+                let Some(source_issue_id) = source.database_id else {
+                    continue;
+                };
+                if source_issue_id == issue_id
+                    || issues_references.iter().any(|r: &IssueReference| {
+                        r.issue_id == issue_id && r.source_issue_id == source_issue_id
+                    })
+                {
+                    continue;
+                }
+                issues_references.push(IssueReference {
+                    issue_id,
+                    source_issue_id,
+                    source_url: source.url,
+                });
+            }
+        }
 
-        Ok(repo)
+        Ok(issues_references)
     }
+
+    async fn recently_closed_issues(
+        &self,
+        token: &str,
+        url: &str,
+        since: OffsetDateTime,
+        completed_only: bool,
+    ) -> Result<Vec<ClosedIssue>> {
+        // Do request to GraphQL API
+        let http_client = setup_http_client(token)?;
+        let (owner, repo) = get_owner_and_repo(url)?;
+        let vars = recently_closed_issues::Variables {
+            repo,
+            owner,
+            closed_since: since.format(&Iso8601::DEFAULT)?,
+        };
+        let req_body = &RecentlyClosedIssues::build_query(vars);
+        let resp = http_client
+            .post(&self.graphql_url)
+            .json(req_body)
+            .send()
+            .await
+            .context("error querying graphql api")?;
+        self.report_deprecation(&resp);
+        self.record_remaining_budget(token, &resp);
+        if resp.status() != StatusCode::OK {
+            bail!(
+                "unexpected status code querying graphql api: {} - {}",
+                resp.status(),
+                resp.text().await?,
+            );
+        }
+
+        // Parse response body and extract the issues closed (as completed if
+        // requested) within the window (issues are filtered by their update
+        // timestamp)
+        let resp_body = resp.text().await?;
+        let nodes =
+            serde_json::from_str::<Response<recently_closed_issues::ResponseData>>(&resp_body)
+                .context(format!("error deserializing query response: {resp_body}"))?
+                .data
+                .and_then(|data| data.repository)
+                .ok_or_else(|| format_err!("repository field not found: {resp_body}"))?
+                .issues
+                .nodes;
+        let issues = nodes
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|node| {
+                !completed_only
+                    || node.state_reason
+                        == Some(recently_closed_issues::IssueStateReason::COMPLETED)
+            })
+            .filter_map(|node| {
+                let closed_at = OffsetDateTime::parse(node.closed_at.as_ref()?, &Rfc3339).ok()?;
+                #[allow(clippy::cast_possible_truncation)]
+                Some(ClosedIssue {
+                    issue_id: node.database_id?,
+                    title: node.title,
+                    url: node.url,
+                    number: node.number as i32,
+                    closed_at,
+                })
+            })
+            .filter(|issue| issue.closed_at >= since)
+            .collect();
+
+        Ok(issues)
+    }
+
+    async fn issues_opened_and_closed(
+        &self,
+        token: &str,
+        url: &str,
+        since: OffsetDateTime,
+    ) -> Result<(i64, i64)> {
+        // Do request to GraphQL API
+        let http_client = setup_http_client(token)?;
+        let (owner, repo) = get_owner_and_repo(url)?;
+        let since = since.date();
+        let vars = issues_close_rate::Variables {
+            opened_query: format!("repo:{owner}/{repo} is:issue created:>={since}"),
+            closed_query: format!("repo:{owner}/{repo} is:issue closed:>={since}"),
+        };
+        let req_body = &IssuesCloseRate::build_query(vars);
+        let resp = http_client
+            .post(&self.graphql_url)
+            .json(req_body)
+            .send()
+            .await
+            .context("error querying graphql api")?;
+        self.report_deprecation(&resp);
+        self.record_remaining_budget(token, &resp);
+        if resp.status() != StatusCode::OK {
+            bail!(
+                "unexpected status code querying graphql api: {} - {}",
+                resp.status(),
+                resp.text().await?,
+            );
+        }
+
+        // Parse response body and extract the issues counts
+        let resp_body = resp.text().await?;
+        let data = serde_json::from_str::<Response<issues_close_rate::ResponseData>>(&resp_body)
+            .context(format!("error deserializing query response: {resp_body}"))?
+            .data
+            .ok_or_else(|| format_err!("data field not found: {resp_body}"))?;
+
+        Ok((data.opened.issue_count, data.closed.issue_count))
+    }
+
+    async fn topic(&self, token: &str, name: &str) -> Result<Option<Topic>> {
+        // Search topic using the REST API (metadata like the topic description
+        // is not available in the GraphQL API)
+        let http_client = setup_http_client(token)?;
+        let resp = http_client
+            .get(GITHUB_SEARCH_TOPICS_URL)
+            .query(&[("q", name)])
+            .send()
+            .await
+            .context("error searching topic")?;
+        self.report_deprecation(&resp);
+        if resp.status() != StatusCode::OK {
+            bail!(
+                "unexpected status code searching topic: {} - {}",
+                resp.status(),
+                resp.text().await?,
+            );
+        }
+
+        // Pick the topic that matches exactly the name provided
+        let topic = resp
+            .json::<TopicsSearchResults>()
+            .await?
+            .items
+            .into_iter()
+            .find(|topic| topic.name == name);
+
+        Ok(topic)
+    }
+
+    async fn contributors(&self, token: &str, url: &str, limit: u32) -> Result<Vec<(String, i32)>> {
+        // Get contributors using the REST API (contributors are not available
+        // in the GraphQL API)
+        let http_client = setup_http_client(token)?;
+        let (owner, repo) = get_owner_and_repo(url)?;
+        let resp = http_client
+            .get(format!(
+                "{GITHUB_REST_API_URL}/repos/{owner}/{repo}/contributors"
+            ))
+            .query(&[("per_page", limit)])
+            .send()
+            .await
+            .context("error getting contributors")?;
+        self.report_deprecation(&resp);
+        match resp.status() {
+            StatusCode::OK => {}
+            StatusCode::NO_CONTENT => return Ok(vec![]),
+            status => bail!(
+                "unexpected status code getting contributors: {} - {}",
+                status,
+                resp.text().await?,
+            ),
+        }
+
+        let contributors = resp
+            .json::<Vec<Contributor>>()
+            .await?
+            .into_iter()
+            .map(|c| (c.login, c.contributions))
+            .collect();
+
+        Ok(contributors)
+    }
+
+    async fn commit_activity(&self, token: &str, url: &str) -> Result<Option<Vec<i32>>> {
+        // Get commit activity statistics using the REST API. GitHub computes
+        // them in the background, responding with a 202 until they are ready.
+        let http_client = setup_http_client(token)?;
+        let (owner, repo) = get_owner_and_repo(url)?;
+        let mut attempt = 1;
+        let resp = loop {
+            let resp = http_client
+                .get(format!(
+                    "{GITHUB_REST_API_URL}/repos/{owner}/{repo}/stats/commit_activity"
+                ))
+                .send()
+                .await
+                .context("error getting commit activity")?;
+            self.report_deprecation(&resp);
+            match resp.status() {
+                StatusCode::OK => break resp,
+                StatusCode::ACCEPTED if attempt < COMMIT_ACTIVITY_MAX_ATTEMPTS => {
+                    sleep(Duration::from_secs(2_u64.pow(attempt))).await;
+                    attempt += 1;
+                }
+                StatusCode::ACCEPTED => return Ok(None),
+                StatusCode::NO_CONTENT => return Ok(Some(vec![])),
+                status => bail!(
+                    "unexpected status code getting commit activity: {} - {}",
+                    status,
+                    resp.text().await?,
+                ),
+            }
+        };
+
+        let commit_activity = resp
+            .json::<Vec<WeeklyCommitActivity>>()
+            .await?
+            .into_iter()
+            .map(|w| w.total)
+            .collect();
+
+        Ok(Some(commit_activity))
+    }
+
+    async fn owner_followers(&self, token: &str, url: &str) -> Result<i32> {
+        // Get owner details using the REST API (organizations followers are
+        // not available in the GraphQL API)
+        let http_client = setup_http_client(token)?;
+        let (owner, _) = get_owner_and_repo(url)?;
+        let resp = http_client
+            .get(format!("{GITHUB_REST_API_URL}/users/{owner}"))
+            .send()
+            .await
+            .context("error getting owner")?;
+        self.report_deprecation(&resp);
+        if resp.status() != StatusCode::OK {
+            bail!(
+                "unexpected status code getting owner: {} - {}",
+                resp.status(),
+                resp.text().await?,
+            );
+        }
+
+        Ok(resp.json::<Owner>().await?.followers)
+    }
+
+    async fn issue_absence_reason(
+        &self,
+        token: &str,
+        url: &str,
+        number: i32,
+    ) -> Result<IssueAbsenceReason> {
+        // Get issue details using the REST API (transferred issues are
+        // redirected to their new location)
+        let http_client = setup_http_client(token)?;
+        let (owner, repo) = get_owner_and_repo(url)?;
+        let repo_api_url = format!("{GITHUB_REST_API_URL}/repos/{owner}/{repo}");
+        let resp = http_client
+            .get(format!("{repo_api_url}/issues/{number}"))
+            .send()
+            .await
+            .context("error getting issue")?;
+        self.report_deprecation(&resp);
+        match resp.status() {
+            StatusCode::OK => {}
+            StatusCode::NOT_FOUND | StatusCode::GONE => return Ok(IssueAbsenceReason::Deleted),
+            status => bail!(
+                "unexpected status code getting issue: {} - {}",
+                status,
+                resp.text().await?,
+            ),
+        }
+
+        // Classify the issue absence
+        let issue = resp.json::<IssueDetails>().await?;
+        let reason = if !issue.repository_url.eq_ignore_ascii_case(&repo_api_url) {
+            IssueAbsenceReason::Transferred
+        } else if issue.state == "closed" {
+            IssueAbsenceReason::Closed
+        } else {
+            IssueAbsenceReason::Open
+        };
+
+        Ok(reason)
+    }
+
+    async fn issue_discussion_url(
+        &self,
+        token: &str,
+        url: &str,
+        number: i32,
+    ) -> Result<Option<String>> {
+        use issue_discussion::IssueDiscussionRepositoryIssueTimelineItemsNodes as TimelineItem;
+
+        // Do request to GraphQL API
+        let http_client = setup_http_client(token)?;
+        let (owner, repo) = get_owner_and_repo(url)?;
+        let vars = issue_discussion::Variables {
+            repo,
+            owner,
+            number: i64::from(number),
+        };
+        let req_body = &IssueDiscussion::build_query(vars);
+        let resp = http_client
+            .post(&self.graphql_url)
+            .json(req_body)
+            .send()
+            .await
+            .context("error querying graphql api")?;
+        self.report_deprecation(&resp);
+        self.record_remaining_budget(token, &resp);
+        if resp.status() != StatusCode::OK {
+            bail!(
+                "unexpected status code querying graphql api: {} - {}",
+                resp.status(),
+                resp.text().await?,
+            );
+        }
+
+        // Parse response body and extract the discussion url from the
+        // conversion event (missing issues were not converted)
+        let resp_body = resp.text().await?;
+        let discussion_url =
+            serde_json::from_str::<Response<issue_discussion::ResponseData>>(&resp_body)
+                .context(format!("error deserializing query response: {resp_body}"))?
+                .data
+                .and_then(|data| data.repository)
+                .and_then(|repository| repository.issue)
+                .and_then(|issue| issue.timeline_items.nodes)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .find_map(|node| match node {
+                    TimelineItem::ConvertedToDiscussionEvent(event) => {
+                        event.discussion.map(|discussion| discussion.url)
+                    }
+                    _ => None,
+                });
+
+        Ok(discussion_url)
+    }
+
+    fn remaining_budget(&self, token: &str) -> Option<u32> {
+        self.remaining_budgets
+            .lock()
+            .expect("lock not poisoned")
+            .get(token)
+            .copied()
+    }
+
+    fn last_query_cost(&self, token: &str) -> Option<u32> {
+        self.query_costs
+            .lock()
+            .expect("lock not poisoned")
+            .get(token)
+            .copied()
+    }
+}
+
+/// Repository contributor (represents GitHub REST API response).
+#[derive(Debug, Deserialize)]
+struct Contributor {
+    login: String,
+    contributions: i32,
+}
+
+/// Repository weekly commit activity (represents GitHub REST API response).
+#[derive(Debug, Deserialize)]
+struct WeeklyCommitActivity {
+    total: i32,
+}
+
+/// Repository owner details (represents GitHub REST API response).
+#[derive(Debug, Deserialize)]
+struct Owner {
+    followers: i32,
+}
+
+/// Issue details (represents GitHub REST API response).
+#[derive(Debug, Deserialize)]
+struct IssueDetails {
+    state: String,
+    repository_url: String,
+}
+
+/// Error returned when GitHub responds successfully to a repository view query
+/// but no repository data is returned (i.e. the repository does not exist or
+/// it is not accessible with the token used).
+#[derive(Debug)]
+pub(crate) struct RepositoryNotFound;
+
+impl fmt::Display for RepositoryNotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "repository not found or inaccessible")
+    }
+}
+
+impl std::error::Error for RepositoryNotFound {}
+
+/// Error returned when the token used is not authorized to access the
+/// repository (i.e. it's been revoked or its SSO session has expired).
+#[derive(Debug)]
+pub(crate) struct AuthError;
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "token not authorized to access the repository")
+    }
+}
+
+impl std::error::Error for AuthError {}
+
+/// Error returned when GitHub rejects a request because a primary or
+/// secondary rate limit has been exceeded.
+#[derive(Debug)]
+pub(crate) struct RateLimited;
+
+impl fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "github rate limit exceeded")
+    }
+}
+
+impl std::error::Error for RateLimited {}
+
+/// Error returned when GitHub responds to a request with an unexpected status
+/// code.
+#[derive(Debug)]
+pub(crate) struct UnexpectedStatus(pub StatusCode);
+
+impl fmt::Display for UnexpectedStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unexpected status code: {}", self.0)
+    }
+}
+
+impl std::error::Error for UnexpectedStatus {}
+
+/// Repository view query response. GitHub includes the type of the errors,
+/// which is not part of the GraphQL spec, so errors are parsed separately.
+#[derive(Debug, Deserialize)]
+struct RepoViewResponse {
+    data: Option<repo_view::ResponseData>,
+    errors: Option<Vec<GraphQLError>>,
+}
+
+/// GraphQL error (represents GitHub GraphQL API response).
+#[derive(Debug, Deserialize)]
+struct GraphQLError {
+    message: String,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    path: Option<Vec<serde_json::Value>>,
+}
+
+/// Check if the unsuccessful response provided (status, headers and body) was
+/// caused by a rate limit. Secondary rate limits are returned with a 403
+/// status, like authorization errors.
+fn is_rate_limited(status: StatusCode, headers: &HeaderMap, body: &str) -> bool {
+    if !matches!(
+        status,
+        StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS
+    ) {
+        return false;
+    }
+    status == StatusCode::TOO_MANY_REQUESTS
+        || headers.contains_key("retry-after")
+        || headers
+            .get("x-ratelimit-remaining")
+            .is_some_and(|value| value.as_bytes() == b"0")
+        || body.to_lowercase().contains("rate limit")
+}
+
+/// Extract the repository data from the repository view query response body,
+/// along with the fields that the token used was not allowed to access (which
+/// are returned as null) and the cost of the query (when requested).
+fn parse_repo_view_response(
+    resp_body: &str,
+) -> Result<(repo_view::RepoViewRepository, Vec<String>, Option<u32>)> {
+    let resp = serde_json::from_str::<RepoViewResponse>(resp_body)
+        .context(format!("error deserializing query response: {resp_body}"))?;
+    let errors = resp.errors.unwrap_or_default();
+    let sso_enforced = errors
+        .iter()
+        .any(|err| err.message.contains("SAML enforcement"));
+    match resp.data {
+        Some(repo_view::ResponseData {
+            repository: Some(repository),
+            rate_limit,
+        }) => {
+            let forbidden_fields = errors
+                .iter()
+                .filter(|err| err.kind.as_deref() == Some("FORBIDDEN"))
+                .filter_map(|err| {
+                    let path: Vec<&str> = err
+                        .path
+                        .as_ref()?
+                        .iter()
+                        .filter_map(|p| p.as_str())
+                        .collect();
+                    Some(path.join("."))
+                })
+                .collect();
+            let cost = rate_limit.and_then(|rate_limit| u32::try_from(rate_limit.cost).ok());
+            Ok((repository, forbidden_fields, cost))
+        }
+        _ if sso_enforced => Err(Error::new(AuthError).context(resp_body.to_string())),
+        Some(_) => Err(Error::new(RepositoryNotFound).context(resp_body.to_string())),
+        None => Err(format_err!("data field not found: {resp_body}")),
+    }
+}
+
+/// Topics search results (represents GitHub REST API response).
+#[derive(Debug, Deserialize)]
+struct TopicsSearchResults {
+    items: Vec<Topic>,
 }
 
 // Setup a new authenticated http client to interact with the GitHub API.
@@ -209,9 +1483,293 @@ pub(crate) fn setup_http_client(github_token: &str) -> Result<reqwest::Client, r
 }
 
 /// Extract the owner and repository from the repository url provided.
-fn get_owner_and_repo(repo_url: &str) -> Result<(String, String)> {
+pub(crate) fn get_owner_and_repo(repo_url: &str) -> Result<(String, String)> {
     let c = GITHUB_REPO_URL
         .captures(repo_url)
         .ok_or_else(|| format_err!("invalid repository url"))?;
     Ok((c["owner"].to_string(), c["repo"].to_string()))
 }
+
+/// Deprecation notice sent by GitHub in the response headers when a feature
+/// that will be removed (or has been deprecated) is used.
+#[derive(Debug, Clone, PartialEq)]
+struct DeprecationNotice {
+    deprecation: Option<String>,
+    sunset: Option<String>,
+    link: Option<String>,
+}
+
+impl DeprecationNotice {
+    /// Extract the deprecation notice from the headers provided, if any.
+    fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(ToString::to_string)
+        };
+        let notice = Self {
+            deprecation: header("deprecation"),
+            sunset: header("sunset"),
+            link: header("link").filter(|link| link.contains("rel=\"deprecation\"")),
+        };
+        if notice.deprecation.is_none() && notice.sunset.is_none() && notice.link.is_none() {
+            return None;
+        }
+        Some(notice)
+    }
+}
+
+/// Return the canonical form of the label provided, using the aliases table
+/// provided (aliases are matched case-insensitively). Labels without an alias
+/// are returned as they are.
+fn canonical_label(label: &str, aliases: Option<&HashMap<String, String>>) -> String {
+    aliases
+        .and_then(|aliases| {
+            aliases
+                .iter()
+                .find(|(alias, _)| alias.eq_ignore_ascii_case(label))
+        })
+        .map_or_else(|| label.to_string(), |(_, canonical)| canonical.clone())
+}
+
+/// Normalize the issue url provided, so that it always uses the canonical
+/// (lowercase) host and doesn't include any query parameters or fragment. The
+/// url is returned as is if it can't be parsed.
+fn normalize_issue_url(url: &str) -> String {
+    let Ok(mut url) = Url::parse(url) else {
+        return url.to_string();
+    };
+    url.set_query(None);
+    url.set_fragment(None);
+    url.as_str().trim_end_matches('/').to_string()
+}
+
+/// Return the name of the reaction content provided.
+fn reaction_name(content: &repo_view::ReactionContent) -> String {
+    match content {
+        repo_view::ReactionContent::CONFUSED => "confused".to_string(),
+        repo_view::ReactionContent::EYES => "eyes".to_string(),
+        repo_view::ReactionContent::HEART => "heart".to_string(),
+        repo_view::ReactionContent::HOORAY => "hooray".to_string(),
+        repo_view::ReactionContent::LAUGH => "laugh".to_string(),
+        repo_view::ReactionContent::ROCKET => "rocket".to_string(),
+        repo_view::ReactionContent::THUMBS_DOWN => "thumbs_down".to_string(),
+        repo_view::ReactionContent::THUMBS_UP => "thumbs_up".to_string(),
+        repo_view::ReactionContent::Other(other) => other.to_lowercase(),
+    }
+}
+
+/// Return the name of the pull request state provided.
+fn pull_request_state_name(state: &repo_view::PullRequestState) -> String {
+    match state {
+        repo_view::PullRequestState::CLOSED => "closed".to_string(),
+        repo_view::PullRequestState::MERGED => "merged".to_string(),
+        repo_view::PullRequestState::OPEN => "open".to_string(),
+        repo_view::PullRequestState::Other(other) => other.to_lowercase(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonical_label_uses_aliases() {
+        let aliases = HashMap::from([
+            (
+                "good first issue".to_string(),
+                "good-first-issue".to_string(),
+            ),
+            (
+                "beginner-friendly".to_string(),
+                "good-first-issue".to_string(),
+            ),
+        ]);
+
+        assert_eq!(
+            canonical_label("Good First Issue", Some(&aliases)),
+            "good-first-issue"
+        );
+        assert_eq!(
+            canonical_label("beginner-friendly", Some(&aliases)),
+            "good-first-issue"
+        );
+        assert_eq!(canonical_label("bug", Some(&aliases)), "bug");
+        assert_eq!(
+            canonical_label("good first issue", None),
+            "good first issue"
+        );
+    }
+
+    #[test]
+    fn normalize_issue_url_uses_canonical_form() {
+        assert_eq!(
+            normalize_issue_url("https://GitHub.com/org1/repo1/issues/1/?utm_source=x#top"),
+            "https://github.com/org1/repo1/issues/1"
+        );
+        assert_eq!(normalize_issue_url("issue1_url"), "issue1_url");
+    }
+
+    #[test]
+    fn deprecation_notice_from_headers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(DeprecationNotice::from_headers(&headers), None);
+
+        headers.insert(
+            "link",
+            "<https://api.github.com/page2>; rel=\"next\""
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(DeprecationNotice::from_headers(&headers), None);
+
+        headers.insert("sunset", "Wed, 11 Nov 2026 23:59:59 GMT".parse().unwrap());
+        assert_eq!(
+            DeprecationNotice::from_headers(&headers),
+            Some(DeprecationNotice {
+                deprecation: None,
+                sunset: Some("Wed, 11 Nov 2026 23:59:59 GMT".to_string()),
+                link: None,
+            })
+        );
+    }
+
+    #[test]
+    fn gh_graphql_url_uses_graphql_path() {
+        assert_eq!(
+            GHGraphQL::new("/graphql", true).graphql_url,
+            "https://api.github.com/graphql"
+        );
+        assert_eq!(
+            GHGraphQL::new("proxy/graphql", true).graphql_url,
+            "https://api.github.com/proxy/graphql"
+        );
+    }
+
+    #[test]
+    fn is_rate_limited_detects_secondary_rate_limits() {
+        let no_headers = HeaderMap::new();
+        assert!(!is_rate_limited(
+            StatusCode::FORBIDDEN,
+            &no_headers,
+            "forbidden"
+        ));
+        assert!(is_rate_limited(
+            StatusCode::FORBIDDEN,
+            &no_headers,
+            "You have exceeded a secondary rate limit"
+        ));
+        assert!(is_rate_limited(
+            StatusCode::TOO_MANY_REQUESTS,
+            &no_headers,
+            ""
+        ));
+
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after", "60".parse().unwrap());
+        assert!(is_rate_limited(StatusCode::FORBIDDEN, &headers, ""));
+        assert!(!is_rate_limited(StatusCode::BAD_GATEWAY, &headers, ""));
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", "0".parse().unwrap());
+        assert!(is_rate_limited(StatusCode::FORBIDDEN, &headers, ""));
+        headers.insert("x-ratelimit-remaining", "10".parse().unwrap());
+        assert!(!is_rate_limited(StatusCode::FORBIDDEN, &headers, ""));
+    }
+
+    #[test]
+    fn parse_repo_view_response_null_repository() {
+        let err = parse_repo_view_response(r#"{"data": {"repository": null}}"#).unwrap_err();
+        assert!(err.is::<RepositoryNotFound>());
+    }
+
+    #[test]
+    fn parse_repo_view_response_sso_enforced() {
+        let err = parse_repo_view_response(
+            r#"{
+                "data": {"repository": null},
+                "errors": [{"message": "Resource protected by organization SAML enforcement."}]
+            }"#,
+        )
+        .unwrap_err();
+        assert!(err.is::<AuthError>());
+    }
+
+    #[test]
+    fn parse_repo_view_response_forbidden_fields() {
+        let (_, forbidden_fields, _) = parse_repo_view_response(
+            r#"{
+                "data": {"repository": {
+                    "closedIssues": {"totalCount": 0},
+                    "createdAt": "2020-01-01T00:00:00Z",
+                    "defaultBranchRef": {"branchProtectionRule": null},
+                    "description": null,
+                    "hasIssuesEnabled": true,
+                    "homepageUrl": null,
+                    "isEmpty": false,
+                    "isPrivate": false,
+                    "issues": {"nodes": [], "pageInfo": {"endCursor": null, "hasNextPage": false}},
+                    "latestRelease": {"nodes": []},
+                    "openIssues": {"totalCount": 0},
+                    "openMilestones": null,
+                    "pinnedIssues": null,
+                    "primaryLanguage": null,
+                    "languages": null,
+                    "repositoryTopics": {"nodes": []},
+                    "stargazerCount": 0,
+                    "watchers": {"totalCount": 0}
+                }},
+                "errors": [{
+                    "type": "FORBIDDEN",
+                    "path": ["repository", "defaultBranchRef", "branchProtectionRule"],
+                    "message": "Resource not accessible by integration"
+                }]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            forbidden_fields,
+            vec!["repository.defaultBranchRef.branchProtectionRule"]
+        );
+    }
+
+    #[test]
+    fn parse_repo_view_response_query_cost() {
+        let (_, _, cost) = parse_repo_view_response(
+            r#"{
+                "data": {
+                    "rateLimit": {"cost": 2},
+                    "repository": {
+                        "closedIssues": {"totalCount": 0},
+                        "createdAt": "2020-01-01T00:00:00Z",
+                        "defaultBranchRef": null,
+                        "description": null,
+                        "hasIssuesEnabled": true,
+                        "homepageUrl": null,
+                        "isEmpty": false,
+                        "isPrivate": false,
+                        "issues": {"nodes": [], "pageInfo": {"endCursor": null, "hasNextPage": false}},
+                        "latestRelease": {"nodes": []},
+                        "openIssues": {"totalCount": 0},
+                        "openMilestones": null,
+                        "pinnedIssues": null,
+                        "primaryLanguage": null,
+                        "languages": null,
+                        "repositoryTopics": {"nodes": []},
+                        "stargazerCount": 0,
+                        "watchers": {"totalCount": 0}
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(cost, Some(2));
+    }
+
+    #[test]
+    fn parse_repo_view_response_missing_data() {
+        let err = parse_repo_view_response(r#"{"errors": [{"message": "error"}]}"#).unwrap_err();
+        assert!(!err.is::<RepositoryNotFound>());
+    }
+}
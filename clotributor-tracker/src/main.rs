@@ -7,9 +7,11 @@ use anyhow::{Context, Result};
 use clap::Parser;
 use config::{Config, File};
 use deadpool_postgres::{Config as DbConfig, Runtime};
+use futures::future;
 use openssl::ssl::{SslConnector, SslMethod, SslVerifyMode};
 use postgres_openssl::MakeTlsConnector;
-use tracing::debug;
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+use tracing::{debug, warn};
 use tracing_subscriber::EnvFilter;
 
 use crate::{db::PgDB, github::GHGraphQL};
@@ -24,6 +26,20 @@ struct Args {
     /// Config file path
     #[clap(short, long)]
     config: PathBuf,
+
+    /// Repair the repositories tracked since this timestamp (RFC3339)
+    #[clap(long, value_parser = parse_timestamp)]
+    repair_since: Option<OffsetDateTime>,
+
+    /// Repair the repositories tracked until this timestamp (RFC3339,
+    /// defaults to now)
+    #[clap(long, value_parser = parse_timestamp, requires = "repair_since")]
+    repair_until: Option<OffsetDateTime>,
+}
+
+/// Parse the RFC3339 timestamp provided.
+fn parse_timestamp(ts: &str) -> Result<OffsetDateTime, time::error::Parse> {
+    OffsetDateTime::parse(ts, &Rfc3339)
 }
 
 #[tokio::main]
@@ -32,6 +48,16 @@ async fn main() -> Result<()> {
 
     // Setup configuration
     let cfg = Config::builder()
+        .set_default("creds.githubOrgTokens", None::<String>)?
+        .set_default("creds.githubTokenLabels", None::<String>)?
+        .set_default("db.checkSchemaVersion", true)?
+        .set_default("db.compatibilityMode", false)?
+        .set_default("db.warmupConnections", 0)?
+        .set_default("github.finalRateLimitCheck", true)?
+        .set_default("github.graphqlPath", "/graphql")?
+        .set_default("github.maxQps", None::<f64>)?
+        .set_default("github.reportDeprecations", true)?
+        .set_default("metrics.pushgatewayUrl", None::<String>)?
         .set_default("tracker.concurrency", 10)?
         .add_source(File::from(args.config))
         .build()
@@ -56,11 +82,45 @@ async fn main() -> Result<()> {
     let connector = MakeTlsConnector::new(builder.build());
     let db_cfg: DbConfig = cfg.get("db")?;
     let pool = db_cfg.create_pool(Some(Runtime::Tokio1), connector)?;
-    let db = Arc::new(PgDB::new(pool));
+
+    // Warm up database connections pool (if enabled)
+    let warmup_connections = cfg
+        .get::<usize>("db.warmupConnections")?
+        .min(pool.status().max_size);
+    if warmup_connections > 0 {
+        debug!(warmup_connections, "warming up database connections pool");
+        future::try_join_all((0..warmup_connections).map(|_| pool.get())).await?;
+    }
+    let mut db = PgDB::new(pool);
+
+    // Skip the optional columns not found in the database (if enabled)
+    let compatibility_mode = cfg.get::<bool>("db.compatibilityMode")?;
+    if compatibility_mode {
+        db.enable_compatibility_mode().await?;
+    }
+
+    // Check database schema version is compatible (if enabled). In
+    // compatibility mode, an incompatible version is only reported
+    if cfg.get::<bool>("db.checkSchemaVersion")? {
+        match db.check_schema_version().await {
+            Err(err) if compatibility_mode => warn!(?err, "database schema check failed"),
+            result => result?,
+        }
+    }
+    let db = Arc::new(db);
 
     // Setup GitHub client
-    let gh = Arc::new(GHGraphQL::new());
+    let gh = Arc::new(GHGraphQL::new(
+        &cfg.get_string("github.graphqlPath")?,
+        cfg.get_bool("github.reportDeprecations")?,
+    ));
 
-    // Run tracker
-    tracker::run(&cfg, db, gh).await
+    // Run tracker (in repair mode if requested)
+    match args.repair_since {
+        Some(since) => {
+            let until = args.repair_until.unwrap_or_else(OffsetDateTime::now_utc);
+            tracker::repair(&cfg, db, gh, since, until).await
+        }
+        None => tracker::run(&cfg, db, gh).await,
+    }
 }
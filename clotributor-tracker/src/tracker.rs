@@ -1,99 +1,446 @@
-use std::time::{Duration, Instant};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    convert::Infallible,
+    fmt::Write,
+    hash::{DefaultHasher, Hash, Hasher},
+    sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
 
 use anyhow::{Context, Error, Result, bail, format_err};
 use config::Config;
 use deadpool::unmanaged::{Object, Pool};
-use futures::stream::{self, StreamExt};
+use futures::{
+    future,
+    stream::{self, StreamExt, TryStreamExt},
+};
 use postgres_types::{FromSql, ToSql};
+use regex::RegexSet;
+use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 #[cfg(not(test))]
 use serde_json::Value;
 use sha2::{Digest, Sha256};
-use time::OffsetDateTime;
-use tokio::time::timeout;
-use tracing::{debug, info, instrument};
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+use tokio::{
+    sync::{Mutex, Semaphore, SemaphorePermit},
+    time::{Interval, MissedTickBehavior, error::Elapsed, interval, sleep, timeout},
+};
+use tracing::{Span, debug, error, info, instrument, warn};
 use uuid::Uuid;
 
-#[cfg(not(test))]
-use crate::github;
 use crate::{
     db::DynDB,
-    github::{DynGH, repo_view},
+    github::{
+        self, AuthError, DynGH, REPO_VIEW_QUERY_VERSION, RateLimited, RepositoryNotFound,
+        UnexpectedStatus, repo_view,
+    },
 };
 
 /// Maximum time that can take tracking a single repository.
 const REPOSITORY_TRACK_TIMEOUT: u64 = 300;
 
+/// Number of days considered when refreshing the issues of a repository that
+/// couldn't be fetched.
+const CLOSED_ISSUES_REFRESH_DAYS: i64 = 30;
+
+/// Number of issues fetched per page when the page size is not adapted.
+const DEFAULT_ISSUES_PAGE_SIZE: u32 = 50;
+
+/// Job name used when pushing metrics to the Prometheus Pushgateway.
+const PUSHGATEWAY_JOB: &str = "clotributor_tracker";
+
 /// Track repositories that need to be tracked.
 #[instrument(skip_all, err)]
 pub(crate) async fn run(cfg: &Config, db: DynDB, gh: DynGH) -> Result<()> {
+    track_repositories(cfg, db, gh, None).await
+}
+
+/// Force track the repositories tracked within the window provided (or never
+/// tracked), regardless of their staleness. This is used to recover from
+/// incidents where some writes to the database were lost.
+#[instrument(skip_all, err)]
+pub(crate) async fn repair(
+    cfg: &Config,
+    db: DynDB,
+    gh: DynGH,
+    since: OffsetDateTime,
+    until: OffsetDateTime,
+) -> Result<()> {
+    if since > until {
+        bail!("invalid repair window: since must be before until");
+    }
+    track_repositories(cfg, db, gh, Some((since, until))).await
+}
+
+/// Track the repositories selected, which are the ones that need to be
+/// tracked or, when a repair window is provided, the ones to repair.
+#[allow(clippy::too_many_lines)]
+async fn track_repositories(
+    cfg: &Config,
+    db: DynDB,
+    gh: DynGH,
+    repair_window: Option<(OffsetDateTime, OffsetDateTime)>,
+) -> Result<()> {
     // Setup GitHub tokens pool
     let gh_tokens = cfg.get::<Vec<String>>("creds.githubTokens")?;
     if gh_tokens.is_empty() {
         bail!("GitHub tokens not found in config file (creds.githubTokens)");
     }
-    let gh_tokens_pool = Pool::from(gh_tokens.clone());
+    let gh_org_tokens = cfg
+        .get::<Option<HashMap<String, Vec<String>>>>("creds.githubOrgTokens")?
+        .unwrap_or_default();
+    let gh_tokens_pools = TokensPools::new(gh_tokens.clone(), gh_org_tokens.clone());
+    let gh_token_labels = TokenLabels::new(
+        gh_tokens.iter().chain(gh_org_tokens.values().flatten()),
+        cfg.get::<Option<HashMap<String, String>>>("creds.githubTokenLabels")?
+            .unwrap_or_default(),
+    )?;
 
-    // Get repositories to track
-    debug!("getting repositories to track");
-    let repositories_to_track = db.get_repositories_to_track().await?;
-    if repositories_to_track.is_empty() {
-        info!("no repositories to track, finished");
-        return Ok(());
+    // Setup tracker configuration
+    let mut tracker_cfg: TrackerCfg = cfg.get("tracker")?;
+    tracker_cfg.validate()?;
+    if repair_window.is_some() {
+        tracker_cfg.force_refresh = true;
+    }
+
+    // Setup GitHub queries rate limiter (if enabled)
+    let gh_rate_limiter = match cfg.get::<Option<f64>>("github.maxQps")? {
+        Some(max_qps) => Some(QueriesRateLimiter::new(max_qps)?),
+        None => None,
+    };
+
+    // Setup run-global retry budget (if enabled)
+    let retry_budget = tracker_cfg.max_total_retries.map(RetryBudget::new);
+
+    // Load the issues digests cache (if enabled)
+    let digest_cache = if tracker_cfg.digest_cache {
+        let start = Instant::now();
+        let digest_cache =
+            DigestCache::new(db.get_issues_digests(tracker_cfg.group.as_ref()).await?);
+        info!(
+            repositories = digest_cache.fingerprints.len(),
+            size_bytes = digest_cache.size_bytes(),
+            duration_ms = start.elapsed().as_millis(),
+            "issues digests cache loaded"
+        );
+        Some(digest_cache)
+    } else {
+        None
+    };
+
+    // Setup repositories urls filter
+    let url_filter = UrlFilter::new(
+        tracker_cfg.url_allow_patterns.as_ref(),
+        tracker_cfg.url_block_patterns.as_ref(),
+    )?;
+
+    // Setup issues sync semaphore (if enabled)
+    let sync_semaphore = tracker_cfg.max_concurrent_sync_txns.map(Semaphore::new);
+
+    // Setup deferred issues unregistrations (if enabled)
+    let deferred_unregistrations = tracker_cfg
+        .defer_unregistrations
+        .then(|| Mutex::new(Vec::new()));
+
+    // Setup changed repositories batch (if enabled)
+    let repositories_batch = tracker_cfg
+        .repositories_batch_size
+        .map(RepositoriesBatch::new);
+
+    // Load repositories metadata from the landscape file (if enabled)
+    let repositories_metadata = match &tracker_cfg.metadata_file {
+        Some(location) => Some(load_repositories_metadata(location).await?),
+        None => None,
+    };
+
+    // Disable repositories not tracked successfully for too long (if enabled
+    // and not repairing)
+    if let Some(days) = tracker_cfg.auto_disable_after_days
+        && repair_window.is_none()
+    {
+        let disabled = db
+            .disable_untracked_repositories(days, tracker_cfg.group.as_ref())
+            .await?;
+        for url in disabled {
+            warn!(url, "repository not tracked in {days} days, disabled");
+        }
+    }
+
+    // Unregister issues converted to discussions for too long (if enabled
+    // and not repairing)
+    if let Some(days) = tracker_cfg.converted_issues_max_age_days
+        && repair_window.is_none()
+    {
+        let unregistered = db
+            .unregister_converted_issues(days, tracker_cfg.group.as_ref())
+            .await?;
+        if unregistered > 0 {
+            info!(
+                unregistered,
+                days, "issues converted to discussions unregistered"
+            );
+        }
     }
 
-    // Track repositories
+    // Get repositories to track (streamed from the database if enabled)
+    debug!("getting repositories to track");
+    let mut total = None;
+    let repositories_to_track = if tracker_cfg.stream_repositories && repair_window.is_none() {
+        db.stream_repositories_to_track(
+            tracker_cfg.group.as_ref(),
+            tracker_cfg.clock_skew_tolerance_seconds,
+        )
+        .await?
+        .try_filter(|repository| future::ready(url_filter.allows(&repository.url)))
+        .boxed()
+    } else {
+        let mut repositories_to_track = match repair_window {
+            Some((since, until)) => {
+                db.get_repositories_to_repair(
+                    tracker_cfg.group.as_ref(),
+                    since,
+                    until,
+                    tracker_cfg.clock_skew_tolerance_seconds,
+                )
+                .await?
+            }
+            None => {
+                db.get_repositories_to_track(
+                    tracker_cfg.group.as_ref(),
+                    tracker_cfg.clock_skew_tolerance_seconds,
+                )
+                .await?
+            }
+        };
+        repositories_to_track.retain(|repository| url_filter.allows(&repository.url));
+        if repositories_to_track.is_empty() {
+            info!("no repositories to track, finished");
+            return Ok(());
+        }
+        if tracker_cfg.dedupe_repositories {
+            let duplicates = remove_duplicated_repositories(&mut repositories_to_track);
+            if duplicates > 0 {
+                info!(duplicates, "duplicated repositories to track collapsed");
+            }
+        }
+        if tracker_cfg.interleave_owners {
+            repositories_to_track = interleave_by_owner(repositories_to_track);
+        }
+        total = Some(repositories_to_track.len());
+        stream::iter(repositories_to_track).map(Ok).boxed()
+    };
+
+    // Track repositories (logging the run progress periodically if enabled)
     info!("tracking repositories");
-    #[allow(clippy::manual_try_fold)]
-    let result = stream::iter(repositories_to_track)
+    let progress = RunProgress::new(total);
+    let tracking = repositories_to_track
         .map(|repository| async {
+            let repository = repository.context("error getting repository to track")?;
             let db = db.clone();
             let gh = gh.clone();
-            let gh_token = gh_tokens_pool.get().await.expect("token -when available-");
             let repo_url = repository.url.clone();
+            let repository_id = repository.repository_id;
+
+            // Pick a token authorized for the repository's organization
+            let Some(gh_tokens_pool) = gh_tokens_pools.for_repository(&repo_url) else {
+                let org = github::get_owner_and_repo(&repo_url)?.0;
+                warn!(
+                    url = repo_url,
+                    "no SSO-authorized token for org {org}, skipping repository"
+                );
+                return Ok(());
+            };
+            let gh_token = if tracker_cfg.prefer_tokens_with_budget {
+                get_token_with_most_budget(gh_tokens_pool, &gh).await
+            } else {
+                gh_tokens_pool.get().await.expect("token -when available-")
+            };
 
-            match timeout(
+            let result = match timeout(
                 Duration::from_secs(REPOSITORY_TRACK_TIMEOUT),
-                track_repository(db, gh, gh_token, repository),
+                track_repository(
+                    &tracker_cfg,
+                    db.clone(),
+                    gh,
+                    gh_rate_limiter.as_ref(),
+                    retry_budget.as_ref(),
+                    sync_semaphore.as_ref(),
+                    repositories_batch.as_ref(),
+                    deferred_unregistrations.as_ref(),
+                    digest_cache.as_ref(),
+                    gh_tokens_pool,
+                    gh_token,
+                    &gh_token_labels,
+                    repositories_metadata.as_ref(),
+                    repository,
+                ),
             )
             .await
             {
                 Ok(result) => result,
-                Err(err) => Err(format_err!("{}", err)),
+                Err(err) => Err(Error::new(err)),
+            };
+
+            // Record failure in the database (if enabled). Errors are logged
+            // and don't affect the repository's track result
+            if tracker_cfg.record_failures
+                && let Err(err) = &result
+            {
+                let (category, status) = TrackFailureCategory::from_error(err);
+                if let Err(db_err) = db
+                    .record_track_failure(
+                        repository_id,
+                        category,
+                        status,
+                        &format!("{err:#}"),
+                        OffsetDateTime::now_utc(),
+                    )
+                    .await
+                {
+                    error!(?db_err, url = repo_url, "error recording track failure");
+                }
             }
-            .context(format!("error tracking repository {repo_url}"))
+
+            result.context(format!("error tracking repository {repo_url}"))
         })
         .buffer_unordered(cfg.get("tracker.concurrency")?)
-        .collect::<Vec<Result<()>>>()
-        .await
-        .into_iter()
-        .fold(
-            Ok::<(), Error>(()),
-            |final_result, task_result| match task_result {
-                Ok(()) => final_result,
-                Err(task_err) => match final_result {
-                    Ok(()) => Err(Into::into(task_err)),
-                    Err(final_err) => Err(format_err!("{:#}\n{:#}", final_err, task_err)),
+        .inspect(|task_result| progress.record(task_result))
+        .collect::<Vec<Result<()>>>();
+    let tasks_results = match tracker_cfg
+        .progress_log_interval
+        .filter(|period| *period > 0)
+    {
+        Some(period) => tokio::select! {
+            tasks_results = tracking => tasks_results,
+            never = progress.log_periodically(Duration::from_secs(period)) => match never {},
+        },
+        None => tracking.await,
+    };
+    #[allow(clippy::manual_try_fold)]
+    let mut result =
+        tasks_results
+            .into_iter()
+            .fold(
+                Ok::<(), Error>(()),
+                |final_result, task_result| match task_result {
+                    Ok(()) => final_result,
+                    Err(task_err) => match final_result {
+                        Ok(()) => Err(Into::into(task_err)),
+                        Err(final_err) => Err(format_err!("{:#}\n{:#}", final_err, task_err)),
+                    },
                 },
-            },
-        );
+            );
 
-    // Check Github API rate limit status for each token
+    // Write the changed repositories still pending in the batch (if enabled)
+    if let Some(repositories_batch) = &repositories_batch
+        && let Err(err) = repositories_batch.flush(&db).await
+    {
+        result = match result {
+            Ok(()) => Err(err),
+            Err(final_err) => Err(format_err!("{:#}\n{:#}", final_err, err)),
+        };
+    }
+
+    // Unregister the issues whose unregistration was deferred (if enabled)
+    if let Some(deferred_unregistrations) = deferred_unregistrations {
+        let issues_ids = deferred_unregistrations.into_inner();
+        if !issues_ids.is_empty() {
+            match db.unregister_issues(&issues_ids).await {
+                Ok(()) => debug!(count = issues_ids.len(), "deferred issues unregistered"),
+                Err(err) => {
+                    let err = err.context("error unregistering deferred issues");
+                    result = match result {
+                        Ok(()) => Err(err),
+                        Err(final_err) => Err(format_err!("{:#}\n{:#}", final_err, err)),
+                    };
+                }
+            }
+        }
+    }
+
+    // Track topics metadata (if enabled)
+    if tracker_cfg.fetch_topic_metadata {
+        let gh_token = gh_tokens_pools
+            .default
+            .get()
+            .await
+            .expect("token -when available-");
+
+        // Errors are logged and don't affect the run's result
+        _ = track_topics(db.clone(), gh.clone(), &gh_token).await;
+    }
+
+    // Update spotlight issues from the data stored (if enabled). Errors are
+    // logged and don't affect the run's result
+    if let Some(spotlight_cfg) = &tracker_cfg.spotlight {
+        match db.update_spotlight_issues(spotlight_cfg).await {
+            Ok(()) => debug!("spotlight issues updated"),
+            Err(err) => error!(?err, "error updating spotlight issues"),
+        }
+    }
+
+    // Update beginner leaderboard from the data stored (if enabled). Errors
+    // are logged and don't affect the run's result
+    if let Some(leaderboard_cfg) = &tracker_cfg.beginner_leaderboard {
+        match db.update_beginner_leaderboard(leaderboard_cfg).await {
+            Ok(()) => debug!("beginner leaderboard updated"),
+            Err(err) => error!(?err, "error updating beginner leaderboard"),
+        }
+    }
+
+    // Update topics statistics from the data stored (if enabled). Errors are
+    // logged and don't affect the run's result
+    if tracker_cfg.compute_topic_stats {
+        match db.update_topic_stats().await {
+            Ok(()) => debug!("topics statistics updated"),
+            Err(err) => error!(?err, "error updating topics statistics"),
+        }
+    }
+
+    // Update organizations statistics from the data stored (if enabled).
+    // Errors are logged and don't affect the run's result
+    if tracker_cfg.compute_org_stats {
+        match db.update_org_stats().await {
+            Ok(()) => debug!("organizations statistics updated"),
+            Err(err) => error!(?err, "error updating organizations statistics"),
+        }
+    }
+
+    // Push the run metrics to the Prometheus Pushgateway (if enabled). Errors
+    // are logged and don't affect the run's result
+    if let Some(pushgateway_url) = cfg.get::<Option<String>>("metrics.pushgatewayUrl")? {
+        let mut metrics = progress.metrics() + &gh_token_labels.metrics(&gh);
+        if let Some(digest_cache) = &digest_cache {
+            metrics += &digest_cache.metrics();
+        }
+        match push_metrics(&pushgateway_url, metrics).await {
+            Ok(()) => debug!("metrics pushed"),
+            Err(err) => error!(?err, "error pushing metrics"),
+        }
+    }
+
+    // Check Github API rate limit status for each token (if enabled)
     #[cfg(not(test))]
-    for (i, gh_token) in gh_tokens.into_iter().enumerate() {
-        let gh_client = github::setup_http_client(&gh_token)?;
-        let response: Value = gh_client
-            .get("https://api.github.com/rate_limit")
-            .send()
-            .await?
-            .json()
-            .await?;
-        debug!(
-            token = i,
-            rate = %response["rate"],
-            graphql = %response["resources"]["graphql"],
-            "token github rate limit info"
-        );
+    if cfg.get::<bool>("github.finalRateLimitCheck")? {
+        for gh_token in gh_tokens
+            .into_iter()
+            .chain(gh_org_tokens.into_values().flatten())
+        {
+            let gh_client = github::setup_http_client(&gh_token)?;
+            let response: Value = gh_client
+                .get("https://api.github.com/rate_limit")
+                .send()
+                .await?
+                .json()
+                .await?;
+            debug!(
+                token = gh_token_labels.get(&gh_token),
+                rate = %response["rate"],
+                graphql = %response["resources"]["graphql"],
+                "token github rate limit info"
+            );
+        }
     }
 
     info!("finished");
@@ -101,583 +448,7924 @@ pub(crate) async fn run(cfg: &Config, db: DynDB, gh: DynGH) -> Result<()> {
 }
 
 /// Track repository provided.
-#[instrument(fields(url = %repo.url), skip_all, err)]
+#[instrument(fields(url = %repo.url, token), skip_all, err)]
+#[allow(clippy::too_many_arguments)]
 async fn track_repository(
+    cfg: &TrackerCfg,
     db: DynDB,
     gh: DynGH,
-    gh_token: Object<String>,
+    gh_rate_limiter: Option<&QueriesRateLimiter>,
+    retry_budget: Option<&RetryBudget>,
+    sync_semaphore: Option<&Semaphore>,
+    repositories_batch: Option<&RepositoriesBatch>,
+    deferred_unregistrations: Option<&Mutex<Vec<i64>>>,
+    digest_cache: Option<&DigestCache>,
+    gh_tokens_pool: &Pool<String>,
+    mut gh_token: Object<String>,
+    gh_token_labels: &TokenLabels,
+    repositories_metadata: Option<&HashMap<String, RepositoryMetadata>>,
     mut repo: Repository,
 ) -> Result<()> {
     let start = Instant::now();
+    let mut timings = TrackTimings::default();
+    Span::current().record("token", gh_token_labels.get(&gh_token));
     debug!("started");
 
-    // Fetch repository data from GitHub
-    let gh_repo = gh
-        .repository(&gh_token, &repo.url, repo.issues_filter_label.as_ref())
-        .await?;
+    // Update repository's metadata in db if needed (if enabled)
+    if let Some(repositories_metadata) = repositories_metadata {
+        let metadata = repositories_metadata
+            .get(&normalize_repository_url(&repo.url))
+            .cloned();
+        if metadata != repo.metadata {
+            timings
+                .db_write(db.update_repository_metadata(repo.repository_id, metadata.as_ref()))
+                .await?;
+            repo.metadata = metadata;
+            debug!("metadata updated in database");
+        }
+    }
 
-    // Update repository's GitHub data in db if needed
-    let changed = repo.update_gh_data(&gh_repo)?;
-    if changed {
-        db.update_repository_gh_data(&repo).await?;
-        debug!("github data updated in database");
+    // Fetch repository data from GitHub (retrying on failure if enabled)
+    let issues_cursor = if cfg.persist_issues_cursor {
+        repo.issues_cursor.clone()
+    } else {
+        None
+    };
+    let issues_page_size = cfg
+        .adaptive_issues_page_size
+        .as_ref()
+        .map_or(DEFAULT_ISSUES_PAGE_SIZE, |page_size_cfg| {
+            page_size_cfg.page_size(repo.issues_page_size)
+        });
+    let mut attempt = 1;
+    let gh_repo = loop {
+        if let Some(gh_rate_limiter) = gh_rate_limiter {
+            gh_rate_limiter.wait().await;
+        }
+        match timings
+            .gh(gh.repository(
+                &gh_token,
+                &repo.url,
+                repo.issues_filter_label.as_ref(),
+                issues_cursor.as_ref(),
+                issues_page_size,
+                cfg,
+            ))
+            .await
+        {
+            Ok(gh_repo) => break gh_repo,
+            Err(err) if cfg.skip_not_found_repositories && err.is::<RepositoryNotFound>() => {
+                warn!("repository not found or inaccessible, skipping");
+                timings
+                    .db_write(db.update_repository_last_track_ts(repo.repository_id))
+                    .await?;
+                return Ok(());
+            }
+            Err(err)
+                if cfg.retries.switch_token_on_auth_error
+                    && err.is::<AuthError>()
+                    && attempt < cfg.retries.max_attempts
+                    && retry_budget.is_none_or(RetryBudget::acquire) =>
+            {
+                // The current token is held while waiting, so the one we get
+                // is a different one. If none becomes available in time, the
+                // same token is used again.
+                let delay = cfg.retries.backoff_delay(attempt);
+                if let Ok(Ok(other_gh_token)) = timeout(delay, gh_tokens_pool.get()).await {
+                    gh_token = other_gh_token;
+                    Span::current().record("token", gh_token_labels.get(&gh_token));
+                    debug!(
+                        ?err,
+                        attempt, "auth error fetching repository, switching token"
+                    );
+                } else {
+                    debug!(?err, attempt, "auth error fetching repository, retrying");
+                }
+                attempt += 1;
+            }
+            Err(err)
+                if attempt < cfg.retries.max_attempts
+                    && !err.is::<RepositoryNotFound>()
+                    && retry_budget.is_none_or(RetryBudget::acquire) =>
+            {
+                let delay = cfg.retries.backoff_delay(attempt);
+                debug!(
+                    ?err,
+                    attempt,
+                    delay_ms = delay.as_millis(),
+                    "error fetching repository, retrying"
+                );
+                sleep(delay).await;
+                attempt += 1;
+            }
+            Err(err) if cfg.resilient_issue_sync && is_transient_error(&err) => {
+                // The repository's metadata is left untouched, but the issues
+                // closed in the meantime are still unregistered (if possible)
+                warn!(
+                    ?err,
+                    "error fetching repository, refreshing its issues only"
+                );
+                if let Err(refresh_err) =
+                    unregister_closed_issues(db, gh, &gh_token, &repo, &mut timings).await
+                {
+                    error!(?refresh_err, "error refreshing issues");
+                }
+                return Err(err);
+            }
+            Err(err) => return Err(err),
+        }
+    };
+
+    // Adapt repository's issues page size to the cost of the query (if
+    // enabled), remembering it for the next runs
+    if let Some(page_size_cfg) = &cfg.adaptive_issues_page_size
+        && let Some(cost) = gh.last_query_cost(&gh_token)
+    {
+        let issues = gh_repo.issues.nodes.as_ref().map_or(0, Vec::len);
+        let next_page_size = page_size_cfg.next_page_size(issues_page_size, cost, issues);
+        let next_page_size = i32::try_from(next_page_size)?;
+        if repo.issues_page_size != Some(next_page_size) {
+            timings
+                .db_write(db.update_repository_issues_page_size(repo.repository_id, next_page_size))
+                .await?;
+            repo.issues_page_size = Some(next_page_size);
+            debug!(cost, next_page_size, "issues page size updated in database");
+        }
     }
 
-    // Sync issues in GitHub with database
-    let mut issues_in_gh = gh_repo.issues();
-    let issues_in_db = db.get_repository_issues(repo.repository_id).await?;
+    // Fetch repository's top contributors (if enabled). On error, the ones
+    // previously fetched are kept.
+    if cfg.fetch_contributors {
+        match timings
+            .gh(gh.contributors(&gh_token, &repo.url, cfg.top_contributors_count))
+            .await
+        {
+            Ok(contributors) => repo.top_contributors = Some(contributors),
+            Err(err) => error!(?err, "error fetching contributors"),
+        }
+    } else {
+        repo.top_contributors = None;
+    }
 
-    // Register/update new or outdated issues
-    for issue in &mut issues_in_gh {
-        let digest_in_db = find_issue(issue.issue_id, &issues_in_db);
-        if issue.digest != digest_in_db {
-            db.register_issue(&repo, issue).await?;
-            debug!(issue.number, "registering issue");
+    // Fetch repository's commit activity (if enabled). On error, or when it's
+    // still being computed by GitHub, the one previously fetched is kept.
+    if cfg.fetch_commit_activity {
+        match timings.gh(gh.commit_activity(&gh_token, &repo.url)).await {
+            Ok(Some(commit_activity)) => repo.commit_activity = Some(commit_activity),
+            Ok(None) => debug!("commit activity not available yet"),
+            Err(err) => error!(?err, "error fetching commit activity"),
         }
+    } else {
+        repo.commit_activity = None;
     }
 
-    // Unregister issues no longer available in GitHub
-    for issue in &issues_in_db {
-        if find_issue(issue.issue_id, &issues_in_gh).is_none() {
-            db.unregister_issue(issue.issue_id).await?;
-            debug!(issue.number, "unregistering issue");
+    // Fetch repository's owner followers (if enabled). On error, the number
+    // previously fetched is kept.
+    if cfg.fetch_owner_followers {
+        match timings.gh(gh.owner_followers(&gh_token, &repo.url)).await {
+            Ok(owner_followers) => repo.owner_followers = Some(owner_followers),
+            Err(err) => error!(?err, "error fetching owner followers"),
         }
+    } else {
+        repo.owner_followers = None;
     }
 
-    // Update repository's last track timestamp in db
-    db.update_repository_last_track_ts(repo.repository_id)
-        .await?;
+    // Check if the repository became public since the last track (if
+    // enabled and supported by the database schema). Repositories whose
+    // visibility is unknown yet are considered as such too, and all of them
+    // are fully tracked in this cycle.
+    let detect_public_transitions =
+        cfg.detect_public_transitions && db.has_column("repository", "is_private");
+    let became_public =
+        detect_public_transitions && !gh_repo.is_private && repo.is_private != Some(false);
+    if detect_public_transitions && repo.is_private != Some(gh_repo.is_private) {
+        timings
+            .db_write(db.update_repository_visibility(repo.repository_id, gh_repo.is_private))
+            .await?;
+        repo.is_private = Some(gh_repo.is_private);
+        debug!(
+            is_private = gh_repo.is_private,
+            "visibility updated in database"
+        );
+    }
+    let force_refresh = cfg.force_refresh || became_public;
+    if became_public {
+        info!("repository became public, forcing a full track");
+    }
 
-    debug!(duration_ms = start.elapsed().as_millis(), "completed");
-    Ok(())
-}
+    // Check if the repository is dormant (if enabled). The issues update
+    // timestamps are used, as their timeline isn't fetched for dormant ones
+    let is_dormant = !became_public
+        && cfg.dormant_repo_cutoff_days.is_some_and(|days| {
+            let cutoff =
+                OffsetDateTime::now_utc().saturating_sub(time::Duration::days(days.into()));
+            gh_repo.is_dormant(cutoff, &HashMap::new())
+        });
 
-/// Find an issue in the provided collection, returning its digest if found.
-fn find_issue(issue_id: i64, issues: &[Issue]) -> Option<String> {
-    issues
-        .iter()
-        .find(|i| i.issue_id == issue_id)
-        .map(|i| i.digest.clone().expect("to be present"))
-}
+    // Fetch issues last activity from their timeline (if enabled)
+    let issues_activity = if cfg.precise_activity && !is_dormant && gh_repo.issues.nodes.is_some() {
+        if let Some(gh_rate_limiter) = gh_rate_limiter {
+            gh_rate_limiter.wait().await;
+        }
+        timings
+            .gh(gh.issues_activity(&gh_token, &gh_repo.issues_ids()))
+            .await?
+    } else {
+        HashMap::new()
+    };
 
-/// Repository information.
-#[derive(Debug, Clone, PartialEq, Default)]
-#[allow(clippy::struct_field_names)]
-pub(crate) struct Repository {
-    pub repository_id: Uuid,
-    pub name: String,
-    pub description: Option<String>,
-    pub url: String,
-    pub homepage_url: Option<String>,
-    pub topics: Option<Vec<String>>,
-    pub languages: Option<Vec<String>>,
-    pub stars: Option<i32>,
-    pub digest: Option<String>,
-    pub issues_filter_label: Option<String>,
-    pub project_name: String,
-    pub foundation_id: String,
-}
+    // Update repository's GitHub data in db if needed. When stars aren't part
+    // of the digest, their changes (and the watch to star ratio ones) are
+    // written on their own.
+    let prev_stars = (repo.stars, repo.watch_star_ratio);
+    if force_refresh {
+        repo.digest = None;
+    }
+    let changed = repo.update_gh_data(cfg, &gh_repo, &issues_activity)?;
+    if changed {
+        if let Some(repositories_batch) = repositories_batch {
+            timings
+                .db_write(repositories_batch.add(&db, repo.clone()))
+                .await?;
+            debug!("github data added to repositories batch");
+        } else {
+            timings
+                .db_write(db.update_repository_gh_data(&repo))
+                .await?;
+            debug!("github data updated in database");
+        }
+    } else if (repo.stars, repo.watch_star_ratio) != prev_stars {
+        timings
+            .db_write(db.update_repository_stars(
+                repo.repository_id,
+                repo.stars,
+                repo.watch_star_ratio,
+            ))
+            .await?;
+        debug!("stars updated in database");
+    }
 
-impl Repository {
-    /// Update repository's GitHub data.
-    #[allow(clippy::cast_possible_truncation)]
-    fn update_gh_data(&mut self, gh_repo: &repo_view::RepoViewRepository) -> Result<bool> {
-        // Description
-        self.description.clone_from(&gh_repo.description);
+    // Register repository's labels definitions in db (if enabled)
+    if cfg.track_labels {
+        timings
+            .db_write(db.register_repository_labels(repo.repository_id, &gh_repo.labels()))
+            .await?;
+        debug!("labels registered in database");
+    }
 
-        // Homepage url
-        self.homepage_url.clone_from(&gh_repo.homepage_url);
+    // Skip issues sync when the repository has issues disabled, as no issues
+    // being returned doesn't mean they have been removed
+    if !gh_repo.has_issues_enabled {
+        timings
+            .db_write(db.update_repository_last_track_ts(repo.repository_id))
+            .await?;
+        debug!(
+            duration_ms = start.elapsed().as_millis(),
+            "completed (issues disabled)"
+        );
+        return Ok(());
+    }
 
-        // Languages
-        self.languages = gh_repo.languages.as_ref().and_then(|languages| {
-            languages.nodes.as_ref().map(|nodes| {
-                nodes
-                    .iter()
-                    .flatten()
-                    .map(|node| node.name.clone())
-                    .collect()
-            })
-        });
+    // Skip issues sync when the repository is dormant. Its existing issues
+    // are left untouched to preserve them
+    if is_dormant {
+        timings
+            .db_write(db.update_repository_last_track_ts(repo.repository_id))
+            .await?;
+        debug!(
+            duration_ms = start.elapsed().as_millis(),
+            "completed (repository dormant)"
+        );
+        return Ok(());
+    }
 
-        // Stars
-        self.stars = Some(gh_repo.stargazer_count as i32);
+    // Sync issues in GitHub with database
+    let mut issues_in_gh = gh_repo.issues(cfg, repo.issues_filter_label.as_ref(), &issues_activity);
+    let issues_page = if cfg.persist_issues_cursor {
+        IssuesPage::new(&issues_in_gh, &gh_repo, issues_cursor.is_none())
+    } else {
+        IssuesPage::default()
+    };
+    let repo_created_at = OffsetDateTime::parse(&gh_repo.created_at, &Rfc3339).ok();
+    issues_in_gh.retain_mut(
+        |issue| match issue.validate(&cfg.issue_validation, repo_created_at) {
+            Ok(()) => true,
+            Err(err) => {
+                warn!(issue.number, %err, "invalid issue, skipping");
+                false
+            }
+        },
+    );
+    if cfg.dedupe_issue_titles {
+        remove_duplicated_issues(&mut issues_in_gh);
+    }
+    if cfg.sort_issues_by_id {
+        issues_in_gh.sort_by_key(|issue| issue.issue_id);
+    }
 
-        // Topics
-        self.topics = gh_repo.repository_topics.nodes.as_ref().map(|nodes| {
-            nodes
+    // Skip the issues sync when none of them changed since the run started,
+    // according to the digests cache (if enabled). The issues in the db are
+    // not read in that case, as there is nothing to register or unregister.
+    let issues_unchanged = !force_refresh
+        && !cfg.compute_first_response
+        && digest_cache.is_some_and(|cache| cache.matches(repo.repository_id, &issues_in_gh));
+    let issues_in_db = if issues_unchanged {
+        debug!("issues unchanged according to the digests cache");
+        Vec::new()
+    } else {
+        timings
+            .db_read(db.get_repository_issues(repo.repository_id))
+            .await?
+    };
+
+    // Set the issues first response time (if enabled). It's only fetched for
+    // the issues that didn't have a response the last time they were tracked
+    if cfg.compute_first_response {
+        let mut pending_issues_ids = HashSet::new();
+        for issue in &mut issues_in_gh {
+            let first_response_seconds = issues_in_db
                 .iter()
-                .flatten()
-                .map(|node| node.topic.name.clone())
-                .collect()
-        });
+                .find(|i| i.issue_id == issue.issue_id)
+                .and_then(|i| i.first_response_seconds);
+            if first_response_seconds.is_some() {
+                issue.first_response_seconds = first_response_seconds;
+                issue.update_digest();
+            } else {
+                pending_issues_ids.insert(issue.issue_id);
+            }
+        }
+        if !pending_issues_ids.is_empty() {
+            if let Some(gh_rate_limiter) = gh_rate_limiter {
+                gh_rate_limiter.wait().await;
+            }
+            let issues_first_response = timings
+                .gh(gh.issues_first_response(
+                    &gh_token,
+                    &gh_repo.issues_ids_matching(&pending_issues_ids),
+                ))
+                .await?;
+            for issue in &mut issues_in_gh {
+                if let Some(seconds) = issues_first_response.get(&issue.issue_id) {
+                    issue.first_response_seconds = Some(*seconds);
+                    issue.update_digest();
+                }
+            }
+        }
+    }
 
-        // Digest
-        let prev_digest = self.digest.clone();
-        self.update_digest()?;
-        Ok(self.digest != prev_digest)
+    // Register/update new or outdated issues
+    let mut issues_errors = 0;
+    let sync_permit = acquire_sync_permit(sync_semaphore, !issues_unchanged).await?;
+    for issue in issues_in_gh.iter_mut().filter(|_| !issues_unchanged) {
+        let digest_in_db = if force_refresh {
+            None
+        } else {
+            find_issue(issue.issue_id, &issues_in_db)
+        };
+        if issue.digest != digest_in_db {
+            match timings.db_write(db.register_issue(&repo, issue)).await {
+                Ok(()) => {
+                    debug!(issue.number, "registering issue");
+
+                    // Record issue's labels in its history if they changed
+                    // (if enabled). Errors are logged and don't affect the
+                    // issue's registration
+                    if cfg.track_label_history
+                        && labels_changed(issue, &issues_in_db)
+                        && let Err(err) = timings
+                            .db_write(db.record_issue_labels(
+                                issue.issue_id,
+                                &issue.labels,
+                                OffsetDateTime::now_utc(),
+                            ))
+                            .await
+                    {
+                        error!(?err, issue.number, "error recording issue labels");
+                    }
+                }
+                Err(err) if cfg.isolate_issue_errors => {
+                    error!(?err, issue.number, "error registering issue");
+                    issues_errors += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        }
     }
+    drop(sync_permit);
 
-    /// Update repository's digest.
-    fn update_digest(&mut self) -> Result<()> {
-        let data = bincode::serde::encode_to_vec(
-            (
-                &self.description,
-                &self.homepage_url,
-                &self.languages,
-                &self.topics,
-                &self.stars,
-            ),
-            bincode::config::legacy(),
-        )?;
-        let digest = hex::encode(Sha256::digest(data));
-        self.digest = Some(digest);
-        Ok(())
+    // Refresh the issues references from their timeline (if enabled)
+    if cfg.fetch_references && !issues_in_gh.is_empty() {
+        if let Some(gh_rate_limiter) = gh_rate_limiter {
+            gh_rate_limiter.wait().await;
+        }
+        let references = timings
+            .gh(gh.issues_references(&gh_token, &gh_repo.issues_ids()))
+            .await?;
+        let issues_ids: Vec<i64> = issues_in_gh.iter().map(|issue| issue.issue_id).collect();
+        timings
+            .db_write(db.update_issues_references(&issues_ids, &references))
+            .await?;
+        debug!("issues references updated in database");
     }
-}
 
-/// Issue area.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSql, FromSql)]
-#[serde(rename_all = "kebab-case")]
-#[postgres(name = "area")]
-pub enum IssueArea {
-    #[postgres(name = "docs")]
-    Docs,
-}
+    // Refresh the repository's recently closed issues feed (if enabled)
+    if let Some(days) = cfg.recently_closed_days {
+        if let Some(gh_rate_limiter) = gh_rate_limiter {
+            gh_rate_limiter.wait().await;
+        }
+        let since = OffsetDateTime::now_utc().saturating_sub(time::Duration::days(days.into()));
+        let closed_issues = timings
+            .gh(gh.recently_closed_issues(&gh_token, &repo.url, since, true))
+            .await?;
+        timings
+            .db_write(db.update_recently_closed_issues(repo.repository_id, &closed_issues, since))
+            .await?;
+        debug!("recently closed issues updated in database");
+    }
 
-/// Issue kind.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSql, FromSql)]
-#[serde(rename_all = "kebab-case")]
-#[postgres(name = "kind")]
-pub enum IssueKind {
-    #[postgres(name = "bug")]
-    Bug,
-    #[postgres(name = "feature")]
-    Feature,
-    #[postgres(name = "enhancement")]
-    Enhancement,
-}
-
-/// Issue difficulty.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSql, FromSql)]
-#[serde(rename_all = "kebab-case")]
-#[postgres(name = "difficulty")]
-pub enum IssueDifficulty {
-    #[postgres(name = "easy")]
-    Easy,
-    #[postgres(name = "medium")]
-    Medium,
-    #[postgres(name = "hard")]
-    Hard,
-}
+    // Update issues scores in db (if enabled)
+    if let Some(weights) = &cfg.issue_score_weights {
+        timings
+            .db_write(db.update_issues_scores(&gh_repo.issues_scores(weights)))
+            .await?;
+        debug!("issues scores updated in database");
+    }
 
-/// Issue information.
-#[derive(Debug, Clone, PartialEq)]
-#[allow(clippy::struct_field_names)]
-pub(crate) struct Issue {
-    pub issue_id: i64,
-    pub title: String,
-    pub url: String,
-    pub number: i32,
-    pub labels: Vec<String>,
-    pub published_at: OffsetDateTime,
-    pub has_linked_prs: bool,
-    pub digest: Option<String>,
-    pub area: Option<IssueArea>,
-    pub kind: Option<IssueKind>,
-    pub difficulty: Option<IssueDifficulty>,
-    pub mentor_available: Option<bool>,
-    pub mentor: Option<String>,
-    pub good_first_issue: Option<bool>,
-}
+    // Unregister issues no longer available in GitHub (only the ones that
+    // should have been returned in the page of issues fetched are considered).
+    // The GitHub checks are done first, so that the database writes are done
+    // together afterwards.
+    let track_discussion_conversions =
+        cfg.track_discussion_conversions && db.has_column("issue", "converted_to_discussion_url");
+    let mut issues_to_flag = vec![];
+    let mut issues_to_unregister = vec![];
+    for issue in &issues_in_db {
+        if issues_page.covers(issue) && find_issue(issue.issue_id, &issues_in_gh).is_none() {
+            // Flag issues converted to discussions instead of unregistering
+            // them (if enabled and supported by the database schema), so that
+            // contributors can be redirected
+            if track_discussion_conversions {
+                match timings
+                    .gh(gh.issue_discussion_url(&gh_token, &repo.url, issue.number))
+                    .await
+                {
+                    Ok(Some(discussion_url)) => {
+                        issues_to_flag.push((issue, discussion_url));
+                        continue;
+                    }
+                    Ok(None) => {}
+                    Err(err) => {
+                        error!(
+                            ?err,
+                            issue.number, "error checking issue discussion conversion"
+                        );
+                        continue;
+                    }
+                }
+            }
 
-impl Issue {
-    /// Update issue's digest.
-    pub(crate) fn update_digest(&mut self) {
-        let Ok(data) = bincode::serde::encode_to_vec(
-            (&self.title, &self.labels, &self.has_linked_prs),
-            bincode::config::legacy(),
-        ) else {
-            return;
-        };
-        let digest = hex::encode(Sha256::digest(data));
-        self.digest = Some(digest);
+            // Check why the issue is no longer available (if required)
+            if let Some(unregister_reasons) = &cfg.unregister_reasons {
+                match timings
+                    .gh(gh.issue_absence_reason(&gh_token, &repo.url, issue.number))
+                    .await
+                {
+                    Ok(reason) if unregister_reasons.contains(&reason) => {}
+                    Ok(reason) => {
+                        debug!(issue.number, ?reason, "keeping issue no longer available");
+                        continue;
+                    }
+                    Err(err) => {
+                        error!(?err, issue.number, "error checking issue absence reason");
+                        continue;
+                    }
+                }
+            }
+            if let Some(deferred_unregistrations) = deferred_unregistrations {
+                deferred_unregistrations.lock().await.push(issue.issue_id);
+                debug!(issue.number, "deferring issue unregistration");
+                continue;
+            }
+            issues_to_unregister.push(issue);
+        }
+    }
+    let sync_permit = acquire_sync_permit(
+        sync_semaphore,
+        !issues_to_flag.is_empty() || !issues_to_unregister.is_empty(),
+    )
+    .await?;
+    for (issue, discussion_url) in issues_to_flag {
+        timings
+            .db_write(db.flag_issue_converted_to_discussion(issue.issue_id, &discussion_url))
+            .await?;
+        debug!(
+            issue.number,
+            discussion_url, "issue converted to discussion"
+        );
+    }
+    for issue in issues_to_unregister {
+        match timings.db_write(db.unregister_issue(issue.issue_id)).await {
+            Ok(()) => debug!(issue.number, "unregistering issue"),
+            Err(err) if cfg.isolate_issue_errors => {
+                error!(?err, issue.number, "error unregistering issue");
+                issues_errors += 1;
+            }
+            Err(err) => return Err(err),
+        }
     }
+    drop(sync_permit);
 
-    /// Prepare texts for text search document.
-    pub(crate) fn prepare_ts_texts(&self, repo: &Repository) -> IssueTsTexts {
-        // Weight A
-        let weight_a = repo.project_name.clone();
+    // Update repository's issues pagination cursor in db if needed
+    if cfg.persist_issues_cursor && issues_page.next_cursor != repo.issues_cursor {
+        timings
+            .db_write(db.update_repository_issues_cursor(
+                repo.repository_id,
+                issues_page.next_cursor.as_ref(),
+            ))
+            .await?;
+        debug!(
+            has_next_page = issues_page.next_cursor.is_some(),
+            "issues cursor updated in database"
+        );
+    }
 
-        // Weight B
-        let weight_b = format!(
-            "{} {} {} {} {}",
-            &repo.foundation_id,
-            &repo.name,
-            &repo.description.clone().unwrap_or_default(),
-            &repo
-                .topics
-                .as_ref()
-                .map(|topics| topics.join(" "))
-                .unwrap_or_default(),
-            &repo
-                .languages
-                .as_ref()
-                .map(|languages| languages.join(" "))
-                .unwrap_or_default()
-        )
-        .trim()
-        .to_owned();
+    // Update repository's issues close rate over the window configured (if
+    // enabled). Errors are logged and don't affect the repository's track
+    if let Some(days) = cfg.issue_close_rate_window_days {
+        if let Some(gh_rate_limiter) = gh_rate_limiter {
+            gh_rate_limiter.wait().await;
+        }
+        let since = OffsetDateTime::now_utc().saturating_sub(time::Duration::days(days.into()));
+        match timings
+            .gh(gh.issues_opened_and_closed(&gh_token, &repo.url, since))
+            .await
+        {
+            Ok((opened, closed)) => {
+                let issue_close_rate = issue_close_rate(opened, closed);
+                if issue_close_rate != repo.issue_close_rate {
+                    timings
+                        .db_write(db.update_repository_issue_close_rate(
+                            repo.repository_id,
+                            issue_close_rate,
+                        ))
+                        .await?;
+                    debug!(opened, closed, "issues close rate updated in database");
+                }
+            }
+            Err(err) => error!(?err, "error fetching issues opened and closed"),
+        }
+    }
 
-        // Weight C
-        let weight_c = format!("{} {}", self.title, self.labels.join(" "));
+    // Update repository's last track timestamp in db. Issues that could not be
+    // synced will be retried in the next track, as their digests won't match.
+    timings
+        .db_write(db.update_repository_last_track_ts(repo.repository_id))
+        .await?;
 
-        IssueTsTexts {
-            weight_a,
-            weight_b,
-            weight_c,
-        }
+    if cfg.log_latency_breakdown {
+        debug!(
+            gh_ms = timings.gh.as_millis(),
+            db_read_ms = timings.db_read.as_millis(),
+            db_write_ms = timings.db_write.as_millis(),
+            duration_ms = start.elapsed().as_millis(),
+            "latency breakdown"
+        );
+    }
+    if issues_errors > 0 {
+        debug!(
+            duration_ms = start.elapsed().as_millis(),
+            issues_errors, "completed partially"
+        );
+    } else {
+        debug!(duration_ms = start.elapsed().as_millis(), "completed");
     }
+    Ok(())
+}
 
-    /// Populate the issue with information extracted from the labels, like the
-    /// issue kind, its difficulty, etc.
-    pub(crate) fn populate_from_labels(&mut self) {
-        for label in &self.labels {
-            // Area
-            if label.contains("docs") || label.contains("documentation") {
-                self.area = Some(IssueArea::Docs);
-                continue;
-            }
+/// Time spent in each phase of a repository track.
+#[derive(Debug, Default)]
+struct TrackTimings {
+    gh: Duration,
+    db_read: Duration,
+    db_write: Duration,
+}
 
-            // Kind
-            if let Some(kind) = {
-                if label.contains("enhancement") || label.contains("improvement") {
-                    Some(IssueKind::Enhancement)
-                } else if label.contains("feature") {
-                    Some(IssueKind::Feature)
-                } else if label.contains("bug") {
-                    Some(IssueKind::Bug)
-                } else {
-                    None
-                }
-            } {
-                self.kind = Some(kind);
-                continue;
-            }
+impl TrackTimings {
+    /// Await the GitHub request provided, measuring the time it takes.
+    async fn gh<T>(&mut self, request: impl Future<Output = T>) -> T {
+        Self::measure(&mut self.gh, request).await
+    }
 
-            // Difficulty
-            let labels_easy = ["difficulty/easy", "level/easy"];
-            let labels_medium = ["difficulty/medium", "level/medium"];
-            let labels_hard = ["difficulty/hard", "level/hard"];
-            if let Some(difficulty) = {
-                if labels_easy.contains(&label.as_str()) {
-                    Some(IssueDifficulty::Easy)
-                } else if labels_medium.contains(&label.as_str()) {
-                    Some(IssueDifficulty::Medium)
-                } else if labels_hard.contains(&label.as_str()) {
-                    Some(IssueDifficulty::Hard)
-                } else {
-                    None
-                }
-            } {
-                self.difficulty = Some(difficulty);
-                continue;
-            }
+    /// Await the db read provided, measuring the time it takes.
+    async fn db_read<T>(&mut self, read: impl Future<Output = T>) -> T {
+        Self::measure(&mut self.db_read, read).await
+    }
 
-            // Mentor available
-            if label == "mentor available" || label == "mentorship" {
-                self.mentor_available = Some(true);
-                continue;
-            }
+    /// Await the db write provided, measuring the time it takes.
+    async fn db_write<T>(&mut self, write: impl Future<Output = T>) -> T {
+        Self::measure(&mut self.db_write, write).await
+    }
 
-            // Good first issue
-            if label == "good first issue" {
-                self.good_first_issue = Some(true);
+    /// Await the future provided, adding the time it takes to the phase
+    /// duration.
+    async fn measure<T>(phase_duration: &mut Duration, fut: impl Future<Output = T>) -> T {
+        let start = Instant::now();
+        let output = fut.await;
+        *phase_duration += start.elapsed();
+        output
+    }
+}
+
+/// Track the metadata of the topics used by the repositories that haven't been
+/// tracked recently.
+#[instrument(skip_all, err)]
+async fn track_topics(db: DynDB, gh: DynGH, gh_token: &str) -> Result<()> {
+    debug!("tracking topics");
+    for name in db.get_topics_to_track().await? {
+        match gh.topic(gh_token, &name).await {
+            Ok(topic) => {
+                // Topics without metadata are registered as well, so that we
+                // don't try to fetch them again until they are outdated
+                let topic = topic.unwrap_or(Topic {
+                    name,
+                    ..Default::default()
+                });
+                db.register_topic(&topic).await?;
             }
+            Err(err) => error!(?err, topic = name, "error fetching topic metadata"),
         }
     }
+    Ok(())
 }
 
-/// Texts used to build the issue's text search document.
-#[derive(Debug, Clone, PartialEq)]
-#[allow(clippy::struct_field_names)]
-pub(crate) struct IssueTsTexts {
-    pub weight_a: String,
-    pub weight_b: String,
-    pub weight_c: String,
+/// Check if the error provided is a transient one (timeouts, rate limits and
+/// GitHub server errors).
+fn is_transient_error(err: &Error) -> bool {
+    let (category, status) = TrackFailureCategory::from_error(err);
+    matches!(
+        category,
+        TrackFailureCategory::Timeout | TrackFailureCategory::RateLimited
+    ) || status.is_some_and(|status| (500..600).contains(&status))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::{
-        db::MockDB,
-        github::{MockGH, repo_view::*},
-    };
-    use futures::future;
-    use mockall::predicate::eq;
-    use std::sync::{Arc, LazyLock};
-    use time::format_description::well_known::Rfc3339;
+/// Unregister the repository's issues stored in the database that have been
+/// closed recently, regardless of the reason. This is a lightweight
+/// alternative used to keep the issues fresh when the repository's data can't
+/// be fetched.
+async fn unregister_closed_issues(
+    db: DynDB,
+    gh: DynGH,
+    gh_token: &str,
+    repo: &Repository,
+    timings: &mut TrackTimings,
+) -> Result<()> {
+    let issues_in_db = timings
+        .db_read(db.get_repository_issues(repo.repository_id))
+        .await?;
+    if issues_in_db.is_empty() {
+        return Ok(());
+    }
+    let since =
+        OffsetDateTime::now_utc().saturating_sub(time::Duration::days(CLOSED_ISSUES_REFRESH_DAYS));
+    let closed_issues = timings
+        .gh(gh.recently_closed_issues(gh_token, &repo.url, since, false))
+        .await?;
+    for issue in &issues_in_db {
+        if closed_issues.iter().any(|i| i.issue_id == issue.issue_id) {
+            timings
+                .db_write(db.unregister_issue(issue.issue_id))
+                .await?;
+            debug!(issue.number, "unregistering closed issue");
+        }
+    }
+    Ok(())
+}
 
-    const TOKEN1: &str = "0001";
-    const REPOSITORY_URL: &str = "https://repo1.url";
-    const FAKE_ERROR: &str = "fake error";
+/// Compact fingerprints of the issues digests stored in the database, loaded
+/// once at the beginning of the run. They are used to detect the repositories
+/// whose issues haven't changed without reading them from the database. The
+/// repositories digests are not cached, as they are already loaded along with
+/// the repositories to track.
+struct DigestCache {
+    fingerprints: HashMap<Uuid, Vec<(i64, u64)>>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
 
-    static REPOSITORY_ID: LazyLock<Uuid> =
-        LazyLock::new(|| Uuid::parse_str("00000000-0001-0000-0000-000000000000").unwrap());
+impl DigestCache {
+    /// Create a new DigestCache instance from the issues digests provided.
+    fn new(digests: HashMap<Uuid, Vec<(i64, String)>>) -> Self {
+        let fingerprints = digests
+            .into_iter()
+            .map(|(repository_id, issues)| {
+                let mut fingerprints: Vec<(i64, u64)> = issues
+                    .iter()
+                    .map(|(issue_id, digest)| (*issue_id, fingerprint(digest)))
+                    .collect();
+                fingerprints.sort_unstable();
+                (repository_id, fingerprints)
+            })
+            .collect();
+        Self {
+            fingerprints,
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }
+    }
 
-    #[test]
-    fn repository_update_gh_data_no_changes() {
-        let mut repo = Repository {
-            repository_id: *REPOSITORY_ID,
-            url: REPOSITORY_URL.to_string(),
-            stars: Some(0),
-            digest: Some(
-                "ac07f8e8751d1696c492c15d0b812feeb05c783b7584385986770192a058a85b".to_string(),
-            ),
-            ..Default::default()
-        };
-        let gh_repo = RepoViewRepository {
-            description: None,
-            homepage_url: Some(REPOSITORY_URL.to_string()),
-            issues: RepoViewRepositoryIssues { nodes: None },
-            languages: None,
-            repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
-            stargazer_count: 0,
+    /// Check if the issues provided match the ones of the repository given
+    /// stored in the database (same issues with the same digests), recording
+    /// the result as a cache hit or miss.
+    fn matches(&self, repository_id: Uuid, issues: &[Issue]) -> bool {
+        let cached = self
+            .fingerprints
+            .get(&repository_id)
+            .map_or(&[][..], Vec::as_slice);
+        let matches = cached.len() == issues.len() && {
+            let mut fingerprints: Vec<(i64, u64)> = issues
+                .iter()
+                .filter_map(|issue| Some((issue.issue_id, fingerprint(issue.digest.as_ref()?))))
+                .collect();
+            fingerprints.sort_unstable();
+            fingerprints == cached
         };
-
-        assert!(!repo.update_gh_data(&gh_repo).unwrap());
+        if matches {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+        matches
     }
 
-    #[test]
-    fn repository_update_gh_data_description_changed() {
-        let mut repo = Repository {
-            repository_id: *REPOSITORY_ID,
-            url: REPOSITORY_URL.to_string(),
-            stars: Some(0),
-            digest: Some(
-                "ac07f8e8751d1696c492c15d0b812feeb05c783b7584385986770192a058a85b".to_string(),
+    /// Render the cache metrics in the Prometheus text exposition format.
+    fn metrics(&self) -> String {
+        let metrics = [
+            (
+                "clotributor_tracker_digest_cache_repositories",
+                "Number of repositories in the issues digests cache in the last run.",
+                self.fingerprints.len(),
             ),
-            ..Default::default()
-        };
-        let gh_repo = RepoViewRepository {
-            description: Some("description".to_string()),
-            homepage_url: Some(REPOSITORY_URL.to_string()),
-            issues: RepoViewRepositoryIssues { nodes: None },
-            languages: None,
-            repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
-            stargazer_count: 0,
-        };
+            (
+                "clotributor_tracker_digest_cache_size_bytes",
+                "Approximate memory used by the issues digests cache in the last run.",
+                self.size_bytes(),
+            ),
+            (
+                "clotributor_tracker_digest_cache_hits",
+                "Number of repositories whose issues sync was skipped by the issues digests cache in the last run.",
+                self.hits.load(Ordering::Relaxed),
+            ),
+            (
+                "clotributor_tracker_digest_cache_misses",
+                "Number of repositories whose issues didn't match the issues digests cache in the last run.",
+                self.misses.load(Ordering::Relaxed),
+            ),
+        ];
+        let mut output = String::new();
+        for (name, help, value) in metrics {
+            _ = writeln!(output, "# HELP {name} {help}");
+            _ = writeln!(output, "# TYPE {name} gauge");
+            _ = writeln!(output, "{name} {value}");
+        }
+        output
+    }
 
-        assert!(repo.update_gh_data(&gh_repo).unwrap());
-        assert_eq!(
-            repo.digest,
-            Some("5a31ae9579be1f97427de912fdd1943a9b0ef07cefe07b23a614b8d02384b7ea".to_string())
-        );
+    /// Approximate memory used by the fingerprints stored.
+    fn size_bytes(&self) -> usize {
+        self.fingerprints
+            .values()
+            .map(|fingerprints| {
+                size_of::<Uuid>() + fingerprints.capacity() * size_of::<(i64, u64)>()
+            })
+            .sum()
     }
+}
 
-    #[test]
-    fn repository_update_digest() {
-        let mut repo = Repository {
-            repository_id: *REPOSITORY_ID,
-            url: REPOSITORY_URL.to_string(),
-            stars: Some(0),
-            ..Default::default()
+/// Compute the fingerprint of the digest provided.
+fn fingerprint(digest: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    digest.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Find an issue in the provided collection, returning its digest if found.
+fn find_issue(issue_id: i64, issues: &[Issue]) -> Option<String> {
+    issues
+        .iter()
+        .find(|i| i.issue_id == issue_id)
+        .map(|i| i.digest.clone().expect("to be present"))
+}
+
+/// Check if the labels of the issue provided differ from the ones of the same
+/// issue in the collection given. New issues are considered to have changed.
+fn labels_changed(issue: &Issue, issues: &[Issue]) -> bool {
+    let Some(prev_issue) = issues.iter().find(|i| i.issue_id == issue.issue_id) else {
+        return true;
+    };
+    let mut labels: Vec<&String> = issue.labels.iter().collect();
+    let mut prev_labels: Vec<&String> = prev_issue.labels.iter().collect();
+    labels.sort();
+    prev_labels.sort();
+    labels != prev_labels
+}
+
+/// Reorder the repositories provided so that they are picked from each owner
+/// in turn (round-robin), preserving their relative order within each owner.
+fn interleave_by_owner(repositories: Vec<Repository>) -> Vec<Repository> {
+    let total = repositories.len();
+    let mut owners: Vec<(String, VecDeque<Repository>)> = vec![];
+    for repository in repositories {
+        let owner = github::get_owner_and_repo(&repository.url)
+            .map(|(owner, _)| owner.to_lowercase())
+            .unwrap_or_default();
+        match owners.iter_mut().find(|(o, _)| *o == owner) {
+            Some((_, owner_repositories)) => owner_repositories.push_back(repository),
+            None => owners.push((owner, VecDeque::from([repository]))),
+        }
+    }
+    let mut interleaved = Vec::with_capacity(total);
+    while interleaved.len() < total {
+        for (_, owner_repositories) in &mut owners {
+            if let Some(repository) = owner_repositories.pop_front() {
+                interleaved.push(repository);
+            }
+        }
+    }
+    interleaved
+}
+
+/// Remove the repositories whose normalized url is the same as the one of a
+/// previous repository in the provided collection, returning how many of them
+/// were removed.
+fn remove_duplicated_repositories(repositories: &mut Vec<Repository>) -> usize {
+    let total = repositories.len();
+    let mut seen = HashSet::new();
+    repositories.retain(|repository| seen.insert(normalize_repository_url(&repository.url)));
+    total - repositories.len()
+}
+
+/// Acquire a permit from the issues sync semaphore provided (if any) when
+/// there are issues writes to do.
+async fn acquire_sync_permit(
+    sync_semaphore: Option<&Semaphore>,
+    has_writes: bool,
+) -> Result<Option<SemaphorePermit<'_>>> {
+    match sync_semaphore {
+        Some(sync_semaphore) if has_writes => Ok(Some(sync_semaphore.acquire().await?)),
+        _ => Ok(None),
+    }
+}
+
+/// Remove the issues whose title is the same as the one of an older issue in
+/// the provided collection, keeping only the oldest one. The issues removed
+/// are dropped, so they'll be unregistered if they were registered already.
+fn remove_duplicated_issues(issues: &mut Vec<Issue>) {
+    let mut oldest: HashMap<&str, (OffsetDateTime, i64)> = HashMap::new();
+    for issue in issues.iter() {
+        let candidate = (issue.published_at, issue.issue_id);
+        oldest
+            .entry(&issue.title)
+            .and_modify(|current| *current = (*current).min(candidate))
+            .or_insert(candidate);
+    }
+    let keep: Vec<i64> = oldest.into_values().map(|(_, issue_id)| issue_id).collect();
+    issues.retain(|issue| {
+        let keep = keep.contains(&issue.issue_id);
+        if !keep {
+            debug!(issue.number, "skipping duplicated issue");
+        }
+        keep
+    });
+}
+
+/// Compute the ratio of issues closed to issues opened. None is returned when
+/// no issues were opened, as the ratio is undefined.
+#[allow(clippy::cast_precision_loss)]
+fn issue_close_rate(opened: i64, closed: i64) -> Option<f32> {
+    (opened > 0).then(|| closed as f32 / opened as f32)
+}
+
+/// Page of issues fetched from GitHub. Issues are sorted by creation date in
+/// descending order, so each page covers a range of publication dates.
+#[derive(Debug, Clone, PartialEq, Default)]
+struct IssuesPage {
+    /// Publication date of the newest issue covered (unbounded if unset).
+    newest: Option<OffsetDateTime>,
+    /// Publication date of the oldest issue covered (unbounded if unset).
+    oldest: Option<OffsetDateTime>,
+    /// Whether the page covers no issues at all.
+    empty: bool,
+    /// Cursor to use to fetch the next page (none when this is the last one).
+    next_cursor: Option<String>,
+}
+
+impl IssuesPage {
+    /// Create a new IssuesPage instance from the issues fetched from GitHub.
+    fn new(issues: &[Issue], gh_repo: &repo_view::RepoViewRepository, first: bool) -> Self {
+        let page_info = &gh_repo.issues.page_info;
+        let last = !page_info.has_next_page;
+        let newest = issues.iter().map(|issue| issue.published_at).max();
+        let oldest = issues.iter().map(|issue| issue.published_at).min();
+        Self {
+            newest: if first { None } else { newest },
+            oldest: if last { None } else { oldest },
+            empty: newest.is_none() && !(first && last),
+            next_cursor: if last {
+                None
+            } else {
+                page_info.end_cursor.clone()
+            },
+        }
+    }
+
+    /// Check if the issue provided should have been returned in this page.
+    fn covers(&self, issue: &Issue) -> bool {
+        !self.empty
+            && self
+                .newest
+                .is_none_or(|newest| issue.published_at <= newest)
+            && self
+                .oldest
+                .is_none_or(|oldest| issue.published_at >= oldest)
+    }
+}
+
+/// Progress of a tracker run, updated as repositories are tracked.
+struct RunProgress {
+    start: Instant,
+    total: Option<usize>,
+    tracked: AtomicUsize,
+    failed: AtomicUsize,
+}
+
+impl RunProgress {
+    /// Create a new RunProgress instance.
+    fn new(total: Option<usize>) -> Self {
+        Self {
+            start: Instant::now(),
+            total,
+            tracked: AtomicUsize::new(0),
+            failed: AtomicUsize::new(0),
+        }
+    }
+
+    /// Record the result of tracking a repository.
+    fn record(&self, result: &Result<()>) {
+        self.tracked.fetch_add(1, Ordering::Relaxed);
+        if result.is_err() {
+            self.failed.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Log the run progress periodically (it never returns). The period
+    /// provided must be greater than zero.
+    async fn log_periodically(&self, period: Duration) -> Infallible {
+        let mut interval = interval(period);
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            info!(
+                tracked = self.tracked.load(Ordering::Relaxed),
+                total = self.total,
+                failed = self.failed.load(Ordering::Relaxed),
+                elapsed_secs = self.start.elapsed().as_secs(),
+                "tracking progress"
+            );
+        }
+    }
+
+    /// Render the run metrics in the Prometheus text exposition format.
+    fn metrics(&self) -> String {
+        let metrics = [
+            (
+                "clotributor_tracker_repositories_tracked",
+                "Number of repositories tracked in the last run.",
+                self.tracked.load(Ordering::Relaxed).to_string(),
+            ),
+            (
+                "clotributor_tracker_repositories_failed",
+                "Number of repositories that failed to be tracked in the last run.",
+                self.failed.load(Ordering::Relaxed).to_string(),
+            ),
+            (
+                "clotributor_tracker_run_duration_seconds",
+                "Duration of the last run in seconds.",
+                self.start.elapsed().as_secs_f64().to_string(),
+            ),
+            (
+                "clotributor_tracker_last_run_timestamp_seconds",
+                "Unix timestamp of the last run completion.",
+                OffsetDateTime::now_utc().unix_timestamp().to_string(),
+            ),
+        ];
+        let mut output = String::new();
+        for (name, help, value) in metrics {
+            _ = writeln!(output, "# HELP {name} {help}");
+            _ = writeln!(output, "# TYPE {name} gauge");
+            _ = writeln!(output, "{name} {value}");
+        }
+        output
+    }
+}
+
+/// Push the metrics provided to the Prometheus Pushgateway, replacing the ones
+/// previously pushed by the tracker.
+async fn push_metrics(pushgateway_url: &str, metrics: String) -> Result<()> {
+    let url = format!(
+        "{}/metrics/job/{PUSHGATEWAY_JOB}",
+        pushgateway_url.trim_end_matches('/')
+    );
+    let resp = reqwest::Client::new().put(url).body(metrics).send().await?;
+    if resp.status() != StatusCode::OK {
+        bail!("unexpected status code pushing metrics: {}", resp.status());
+    }
+    Ok(())
+}
+
+/// Pools of GitHub tokens used to track repositories. Organizations that
+/// enforce SAML SSO get their own pool, holding only the tokens that have been
+/// authorized for them.
+struct TokensPools {
+    default: Pool<String>,
+    orgs: HashMap<String, Option<Pool<String>>>,
+}
+
+impl TokensPools {
+    /// Create a new TokensPools instance.
+    fn new(default: Vec<String>, orgs: HashMap<String, Vec<String>>) -> Self {
+        Self {
+            default: Pool::from(default),
+            orgs: orgs
+                .into_iter()
+                .map(|(org, tokens)| {
+                    let pool = (!tokens.is_empty()).then(|| Pool::from(tokens));
+                    (org.to_lowercase(), pool)
+                })
+                .collect(),
+        }
+    }
+
+    /// Return the pool of tokens to use for the repository provided. None is
+    /// returned when the repository's organization enforces SSO and there are
+    /// no tokens authorized for it.
+    fn for_repository(&self, repo_url: &str) -> Option<&Pool<String>> {
+        let Ok((org, _)) = github::get_owner_and_repo(repo_url) else {
+            return Some(&self.default);
+        };
+        match self.orgs.get(&org.to_lowercase()) {
+            Some(pool) => pool.as_ref(),
+            None => Some(&self.default),
+        }
+    }
+}
+
+/// Human-readable labels used to identify the GitHub tokens in logs and
+/// metrics without exposing them. Tokens without a label configured are
+/// identified by their position in the configuration.
+struct TokenLabels(HashMap<String, String>);
+
+impl TokenLabels {
+    /// Create a new TokenLabels instance.
+    fn new<'a>(
+        tokens: impl Iterator<Item = &'a String>,
+        mut labels: HashMap<String, String>,
+    ) -> Result<Self> {
+        let mut tokens_labels = HashMap::new();
+        for (i, token) in tokens.enumerate() {
+            let label = labels.remove(token).unwrap_or_else(|| i.to_string());
+            if label.contains(token.as_str()) {
+                bail!("label of token {i} contains the token (creds.githubTokenLabels)");
+            }
+            tokens_labels.entry(token.clone()).or_insert(label);
+        }
+        Ok(Self(tokens_labels))
+    }
+
+    /// Get the label of the token provided.
+    fn get(&self, token: &str) -> &str {
+        self.0.get(token).map_or("unknown", String::as_str)
+    }
+
+    /// Render the tokens metrics in the Prometheus text exposition format.
+    fn metrics(&self, gh: &DynGH) -> String {
+        let name = "clotributor_tracker_token_remaining_budget";
+        let mut budgets: Vec<_> = self
+            .0
+            .iter()
+            .filter_map(|(token, label)| Some((label, gh.remaining_budget(token)?)))
+            .collect();
+        if budgets.is_empty() {
+            return String::new();
+        }
+        budgets.sort();
+        let mut output = String::new();
+        _ = writeln!(
+            output,
+            "# HELP {name} GraphQL API budget remaining of each token at the end of the last run."
+        );
+        _ = writeln!(output, "# TYPE {name} gauge");
+        for (label, budget) in budgets {
+            let label = label
+                .replace('\\', r"\\")
+                .replace('"', r#"\""#)
+                .replace('\n', r"\n");
+            _ = writeln!(output, "{name}{{token=\"{label}\"}} {budget}");
+        }
+        output
+    }
+}
+
+/// Get a token from the pool provided, picking among the ones available the
+/// token with the most GraphQL API budget remaining. When the budgets are not
+/// known yet, the token is picked in the pool's order.
+async fn get_token_with_most_budget(pool: &Pool<String>, gh: &DynGH) -> Object<String> {
+    let mut candidates = vec![pool.get().await.expect("token -when available-")];
+    while let Ok(gh_token) = pool.try_get() {
+        candidates.push(gh_token);
+    }
+    let mut best = 0;
+    for (i, gh_token) in candidates.iter().enumerate() {
+        if gh.remaining_budget(gh_token) > gh.remaining_budget(&candidates[best]) {
+            best = i;
+        }
+    }
+    candidates.swap_remove(best)
+}
+
+/// Rate limiter used to cap the number of queries per second sent to the
+/// GitHub GraphQL API, regardless of the token used.
+struct QueriesRateLimiter {
+    interval: Mutex<Interval>,
+}
+
+impl QueriesRateLimiter {
+    /// Create a new QueriesRateLimiter instance.
+    fn new(max_qps: f64) -> Result<Self> {
+        if !max_qps.is_finite() || max_qps <= 0.0 {
+            bail!("invalid GitHub max queries per second value (github.maxQps): {max_qps}");
+        }
+        let mut interval = interval(Duration::from_secs_f64(1.0 / max_qps));
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        Ok(Self {
+            interval: Mutex::new(interval),
+        })
+    }
+
+    /// Wait until the next query is allowed.
+    async fn wait(&self) {
+        self.interval.lock().await.tick().await;
+    }
+}
+
+/// Tracker configuration.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+#[allow(clippy::struct_excessive_bools)]
+pub(crate) struct TrackerCfg {
+    /// Whether to fetch the repository's default branch protection status.
+    pub fetch_branch_protection: bool,
+    /// Whether to drop the issues whose title duplicates an older one's. No
+    /// duplicate flag is recorded: duplicated issues aren't registered, and
+    /// the ones already registered are unregistered.
+    pub dedupe_issue_titles: bool,
+    /// Whether to fetch the metadata of the topics used by the repositories.
+    pub fetch_topic_metadata: bool,
+    /// Whether to keep tracking a repository when some of its issues fail to
+    /// be synced.
+    pub isolate_issue_errors: bool,
+    /// Retries configuration used when fetching data from GitHub.
+    pub retries: RetriesCfg,
+    /// Group of repositories to track (all repositories are tracked if unset).
+    pub group: Option<String>,
+    /// Number of days considered when checking if a repository is active.
+    pub active_window_days: u32,
+    /// Whether to persist the issues pagination cursor, so that the next
+    /// track resumes fetching issues from where the previous one stopped.
+    pub persist_issues_cursor: bool,
+    /// Configuration used to adapt the issues page size of each repository to
+    /// the cost of the queries (a fixed page size is used if unset).
+    pub adaptive_issues_page_size: Option<AdaptivePageSizeCfg>,
+    /// Whether to fetch the issues' reactions breakdown by type.
+    pub fetch_reaction_breakdown: bool,
+    /// Whether to stream the repositories to track from the database instead
+    /// of loading them all in memory before starting tracking them.
+    pub stream_repositories: bool,
+    /// Whether to interleave the repositories to track by owner, so that
+    /// consecutive repositories belong to different organizations (not
+    /// applied when the repositories are streamed).
+    pub interleave_owners: bool,
+    /// Whether to remove the duplicated repositories to track (by normalized
+    /// url) before tracking them (not applied when the repositories are
+    /// streamed).
+    pub dedupe_repositories: bool,
+    /// Whether to record the issues labels every time they change, keeping a
+    /// history of them.
+    pub track_label_history: bool,
+    /// Whether to pick, among the tokens available, the one with the most
+    /// GraphQL API budget remaining when starting to track a repository.
+    pub prefer_tokens_with_budget: bool,
+    /// Whether to parse the badges (label and image url) included in the
+    /// repository's README file.
+    pub parse_badges: bool,
+    /// Whether to compute the time it took the issues to get their first
+    /// comment or label. It requires fetching the issues timeline.
+    pub compute_first_response: bool,
+    /// Whether to detect if the repository requires signing a CLA or DCO.
+    pub detect_cla: bool,
+    /// Whether to track the definitions of the labels used by the issues.
+    pub track_labels: bool,
+    /// Weights used to compute the issues score (disabled if unset).
+    pub issue_score_weights: Option<IssueScoreWeights>,
+    /// Whether to skip the repositories that GitHub reports as not found or
+    /// inaccessible instead of failing.
+    pub skip_not_found_repositories: bool,
+    /// Reasons for which issues no longer available are unregistered (all
+    /// issues no longer available are unregistered if unset).
+    pub unregister_reasons: Option<Vec<IssueAbsenceReason>>,
+    /// Whether to flag the issues no longer available that were converted to
+    /// discussions with the discussion url, instead of unregistering them.
+    pub track_discussion_conversions: bool,
+    /// Number of days after which the issues converted to discussions are
+    /// unregistered (kept indefinitely if unset).
+    pub converted_issues_max_age_days: Option<u32>,
+    /// Whether to fetch the repository's top contributors.
+    pub fetch_contributors: bool,
+    /// Number of top contributors to fetch.
+    pub top_contributors_count: u32,
+    /// Whether to fetch the details (url and state) of the PRs linked to the
+    /// issues.
+    pub fetch_linked_pr_details: bool,
+    /// Interval (in seconds) at which the run progress is logged (disabled if
+    /// unset or zero).
+    pub progress_log_interval: Option<u64>,
+    /// Whether to compute the issues last activity from their timeline (an
+    /// extra query per repository is needed).
+    pub precise_activity: bool,
+    /// Whether to fetch the issues cross-referencing the ones tracked from
+    /// their timeline (an extra query per repository is needed).
+    pub fetch_references: bool,
+    /// Number of days the issues closed as completed are kept in the recently
+    /// closed feed (disabled if unset).
+    pub recently_closed_days: Option<u32>,
+    /// Whether to register the issues in ascending id order, so that db writes
+    /// follow a deterministic order.
+    pub sort_issues_by_id: bool,
+    /// Location (local path or url) of a landscape file used to enrich the
+    /// repositories with their projects metadata (disabled if unset).
+    pub metadata_file: Option<String>,
+    /// Maximum number of retries allowed across all repositories during a
+    /// run (unlimited if unset). Once exhausted, further retries are disabled
+    /// for the rest of the run.
+    pub max_total_retries: Option<u32>,
+    /// Whether to fetch the repository's weekly commit activity over the last
+    /// year.
+    pub fetch_commit_activity: bool,
+    /// Validation rules checked on each issue before it's registered.
+    pub issue_validation: IssueValidationCfg,
+    /// Number of days after which repositories that haven't been tracked
+    /// successfully are disabled (disabled if unset).
+    pub auto_disable_after_days: Option<u32>,
+    /// Whether to include the stars in the repository's digest. When
+    /// disabled, stars changes are written separately and don't trigger a
+    /// full GitHub data update.
+    pub stars_in_digest: bool,
+    /// Maximum number of repositories writing their issues to the database
+    /// concurrently (unlimited if unset). The limit only applies to the
+    /// issues registrations, flags and unregistrations, not to the GitHub
+    /// requests made while syncing them. It must be greater than zero.
+    pub max_concurrent_sync_txns: Option<usize>,
+    /// Number of changed repositories buffered before writing their GitHub
+    /// data to the database in a single batched update (disabled if unset).
+    pub repositories_batch_size: Option<usize>,
+    /// Whether to defer the issues unregistrations to a single batched cleanup
+    /// phase run after all repositories have been tracked.
+    pub defer_unregistrations: bool,
+    /// Whether to still unregister the repository's issues closed recently
+    /// when its data can't be fetched due to a transient failure (timeouts,
+    /// rate limits and GitHub server errors).
+    pub resilient_issue_sync: bool,
+    /// Number of days without pushes nor issues activity after which a
+    /// repository is considered dormant, skipping its issues sync (disabled
+    /// if unset).
+    pub dormant_repo_cutoff_days: Option<u32>,
+    /// Whether to normalize the issues urls before storing them. The url
+    /// isn't part of the issues digest, so the issues already registered are
+    /// only updated when they change (or on a run with force_refresh).
+    pub normalize_issue_urls: bool,
+    /// Whether to write all the repositories and issues data fetched to the
+    /// database, even if their digests haven't changed.
+    pub force_refresh: bool,
+    /// Whether to detect the repositories that became public since the last
+    /// track, forcing a full track of them.
+    pub detect_public_transitions: bool,
+    /// Whether to load the issues digests once at the beginning of the run,
+    /// to skip the issues sync of the repositories whose issues haven't
+    /// changed without reading them from the database. The repositories
+    /// digests don't need to be cached, as they are loaded along with the
+    /// repositories to track.
+    pub digest_cache: bool,
+    /// Clock skew (in seconds) tolerated when checking if the repositories
+    /// need to be tracked again. Repositories are tracked again when they were
+    /// last tracked more than the track interval (30 minutes) minus this
+    /// tolerance ago, which is capped at 15 minutes. It also widens the repair
+    /// window by the same amount.
+    pub clock_skew_tolerance_seconds: u32,
+    /// Whether to log the breakdown of the time spent in each phase (GitHub
+    /// requests, db reads and db writes) when tracking a repository.
+    pub log_latency_breakdown: bool,
+    /// Regular expressions the repositories urls must match to be tracked.
+    pub url_allow_patterns: Option<Vec<String>>,
+    /// Regular expressions matching the urls of the repositories to skip.
+    pub url_block_patterns: Option<Vec<String>>,
+    /// Configuration of the spotlight issues computed at the end of each run
+    /// (disabled if unset).
+    pub spotlight: Option<SpotlightCfg>,
+    /// Configuration of the beginner leaderboard computed at the end of each
+    /// run (disabled if unset).
+    pub beginner_leaderboard: Option<BeginnerLeaderboardCfg>,
+    /// Whether to fetch the number of followers of the repository's owner (an
+    /// extra request per repository is needed).
+    pub fetch_owner_followers: bool,
+    /// Whether to record the repositories tracking failures in the database,
+    /// categorized by their cause.
+    pub record_failures: bool,
+    /// Whether to compute the topics statistics (number of repositories and
+    /// total stars) at the end of each run.
+    pub compute_topic_stats: bool,
+    /// Whether to compute the organizations statistics (number of
+    /// repositories, total stars, open issues and most active repository) at
+    /// the end of each run.
+    pub compute_org_stats: bool,
+    /// Whether to fail when the token used is not allowed to access some of
+    /// the repository fields, instead of ignoring them.
+    pub fail_on_forbidden_fields: bool,
+    /// Aliases table used to map the issues labels to their canonical form
+    /// (alias -> canonical label).
+    pub label_aliases: Option<HashMap<String, String>>,
+    /// Labels used to extract the issues difficulty (label -> difficulty),
+    /// checked before the default ones.
+    pub difficulty_labels: Option<HashMap<String, IssueDifficulty>>,
+    /// Whether to compute if the issues are available to be worked on (not
+    /// assigned, not locked and not linked to an open PR).
+    pub compute_availability: bool,
+    /// Whether to fetch the SHA of the latest commit in the repositories
+    /// default branch (new commits will change the repositories digest).
+    pub fetch_head_sha: bool,
+    /// Whether to compute if the issues are awaiting triage (not assigned and
+    /// without labels other than the one used to filter them).
+    pub compute_needs_triage: bool,
+    /// Whether to track the digest of the whole repository data received from
+    /// GitHub, to detect changes in fields not used in the digest.
+    pub track_raw_digest: bool,
+    /// Whether to compute the ratio of watchers to stars of the repositories.
+    pub compute_watch_star_ratio: bool,
+    /// Number of days considered when computing the ratio of issues closed to
+    /// issues opened of the repositories (disabled if unset).
+    pub issue_close_rate_window_days: Option<u32>,
+}
+
+impl Default for TrackerCfg {
+    fn default() -> Self {
+        Self {
+            fetch_branch_protection: false,
+            dedupe_issue_titles: false,
+            fetch_topic_metadata: false,
+            isolate_issue_errors: false,
+            retries: RetriesCfg::default(),
+            group: None,
+            active_window_days: 30,
+            persist_issues_cursor: false,
+            adaptive_issues_page_size: None,
+            fetch_reaction_breakdown: false,
+            stream_repositories: false,
+            interleave_owners: false,
+            dedupe_repositories: false,
+            track_label_history: false,
+            prefer_tokens_with_budget: false,
+            parse_badges: false,
+            compute_first_response: false,
+            detect_cla: false,
+            track_labels: false,
+            issue_score_weights: None,
+            skip_not_found_repositories: false,
+            unregister_reasons: None,
+            track_discussion_conversions: false,
+            converted_issues_max_age_days: None,
+            fetch_contributors: false,
+            top_contributors_count: 10,
+            fetch_linked_pr_details: false,
+            progress_log_interval: None,
+            precise_activity: false,
+            fetch_references: false,
+            recently_closed_days: None,
+            sort_issues_by_id: false,
+            metadata_file: None,
+            max_total_retries: None,
+            fetch_commit_activity: false,
+            issue_validation: IssueValidationCfg::default(),
+            auto_disable_after_days: None,
+            stars_in_digest: true,
+            max_concurrent_sync_txns: None,
+            repositories_batch_size: None,
+            defer_unregistrations: false,
+            resilient_issue_sync: false,
+            dormant_repo_cutoff_days: None,
+            normalize_issue_urls: false,
+            force_refresh: false,
+            detect_public_transitions: false,
+            digest_cache: false,
+            clock_skew_tolerance_seconds: 0,
+            log_latency_breakdown: false,
+            url_allow_patterns: None,
+            url_block_patterns: None,
+            spotlight: None,
+            beginner_leaderboard: None,
+            fetch_owner_followers: false,
+            record_failures: false,
+            compute_topic_stats: false,
+            compute_org_stats: false,
+            fail_on_forbidden_fields: false,
+            label_aliases: None,
+            difficulty_labels: None,
+            compute_availability: false,
+            fetch_head_sha: false,
+            compute_needs_triage: false,
+            track_raw_digest: false,
+            compute_watch_star_ratio: false,
+            issue_close_rate_window_days: None,
+        }
+    }
+}
+
+impl TrackerCfg {
+    /// Check that the configuration is valid.
+    fn validate(&self) -> Result<()> {
+        if self.max_concurrent_sync_txns == Some(0) {
+            bail!("invalid tracker configuration: maxConcurrentSyncTxns must be greater than 0");
+        }
+        if let Some(page_size_cfg) = &self.adaptive_issues_page_size {
+            let AdaptivePageSizeCfg {
+                initial, min, max, ..
+            } = page_size_cfg;
+            if *min < 1 || min > initial || initial > max || *max > 100 {
+                bail!(
+                    "invalid tracker configuration: adaptiveIssuesPageSize must satisfy 1 <= min <= initial <= max <= 100"
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Adaptive issues page size configuration. The page size of each repository
+/// is increased while the queries are cheap, to reduce the number of requests
+/// needed, and decreased when they become expensive.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct AdaptivePageSizeCfg {
+    /// Page size used for repositories without one chosen yet.
+    pub initial: u32,
+    /// Minimum page size.
+    pub min: u32,
+    /// Maximum page size (GitHub doesn't allow more than 100). The page sizes
+    /// must satisfy 1 <= min <= initial <= max <= 100.
+    pub max: u32,
+    /// Query cost at or below which the page size is increased.
+    pub low_cost: u32,
+    /// Query cost above which the page size is decreased.
+    pub high_cost: u32,
+}
+
+impl Default for AdaptivePageSizeCfg {
+    fn default() -> Self {
+        Self {
+            initial: DEFAULT_ISSUES_PAGE_SIZE,
+            min: 10,
+            max: 100,
+            low_cost: 1,
+            high_cost: 3,
+        }
+    }
+}
+
+impl AdaptivePageSizeCfg {
+    /// Get the page size to use for a repository, given the one chosen for it
+    /// in previous runs (if any).
+    fn page_size(&self, chosen: Option<i32>) -> u32 {
+        chosen
+            .and_then(|size| u32::try_from(size).ok())
+            .unwrap_or(self.initial)
+            .clamp(self.min, self.max)
+    }
+
+    /// Get the page size to use next, based on the cost of the last query and
+    /// the number of issues it returned. Growing the page is only worth it if
+    /// it was full, as otherwise no requests would be saved.
+    fn next_page_size(&self, size: u32, cost: u32, issues: usize) -> u32 {
+        let next = if cost > self.high_cost {
+            size / 2
+        } else if cost <= self.low_cost && issues >= size as usize {
+            size.saturating_mul(2)
+        } else {
+            size
+        };
+        next.clamp(self.min, self.max)
+    }
+}
+
+/// Beginner leaderboard configuration. The leaderboard is a ranking of the
+/// best good first issues across all repositories, computed the same way as
+/// the spotlight.
+pub(crate) type BeginnerLeaderboardCfg = SpotlightCfg;
+
+/// Spotlight issues configuration. The spotlight is a small set of the most
+/// compelling issues across all repositories, ranked by combining the issue
+/// score, how recently the issue was published and the repository stars.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct SpotlightCfg {
+    /// Number of issues in the spotlight.
+    pub size: u32,
+    /// Weight of the issue score.
+    pub score_weight: f64,
+    /// Weight of the issue publication freshness.
+    pub recency_weight: f64,
+    /// Weight of the repository popularity (stars, scaled logarithmically).
+    pub popularity_weight: f64,
+}
+
+impl Default for SpotlightCfg {
+    fn default() -> Self {
+        Self {
+            size: 10,
+            score_weight: 1.0,
+            recency_weight: 1.0,
+            popularity_weight: 0.25,
+        }
+    }
+}
+
+/// Weights used to compute the issues score. The score combines how recently
+/// the issue was published and updated with the number of reactions and
+/// comments it has received, and it's meant to be used to rank issues.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct IssueScoreWeights {
+    /// Weight of the issue publication freshness.
+    pub published: f64,
+    /// Weight of the issue last update freshness.
+    pub updated: f64,
+    /// Weight of the number of reactions.
+    pub reactions: f64,
+    /// Weight of the number of comments.
+    pub comments: f64,
+}
+
+impl Default for IssueScoreWeights {
+    fn default() -> Self {
+        Self {
+            published: 1.0,
+            updated: 1.0,
+            reactions: 0.5,
+            comments: 0.25,
+        }
+    }
+}
+
+impl IssueScoreWeights {
+    /// Compute the score of an issue from its age, the time elapsed since it
+    /// was last updated and its number of reactions and comments. Freshness
+    /// values decay from 1 (now) to 0.5 after 30 days, and counts are scaled
+    /// logarithmically so that a few very popular issues don't dominate.
+    #[allow(clippy::cast_precision_loss)]
+    pub(crate) fn score(
+        &self,
+        age: time::Duration,
+        since_update: time::Duration,
+        reactions: i64,
+        comments: i64,
+    ) -> f64 {
+        let freshness =
+            |elapsed: time::Duration| 1.0 / (1.0 + elapsed.as_seconds_f64().max(0.0) / 2_592_000.0);
+        let scale = |count: i64| (count.max(0) as f64).ln_1p();
+
+        self.published * freshness(age)
+            + self.updated * freshness(since_update)
+            + self.reactions * scale(reactions)
+            + self.comments * scale(comments)
+    }
+}
+
+/// Reason why an issue is no longer returned when fetching the repository.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum IssueAbsenceReason {
+    /// The issue has been closed.
+    Closed,
+    /// The issue has been deleted.
+    Deleted,
+    /// The issue has been transferred to another repository.
+    Transferred,
+    /// The issue is still open (it may not match the filters anymore or it
+    /// may not have been fetched).
+    Open,
+}
+
+/// Category of a repository tracking failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ToSql)]
+#[postgres(name = "track_failure_category")]
+pub(crate) enum TrackFailureCategory {
+    /// The token used is not authorized to access the repository.
+    #[postgres(name = "auth")]
+    Auth,
+    /// The repository was not found or it's inaccessible.
+    #[postgres(name = "not-found")]
+    NotFound,
+    /// The GitHub API rate limit was exceeded.
+    #[postgres(name = "rate-limited")]
+    RateLimited,
+    /// Tracking the repository took too long.
+    #[postgres(name = "timeout")]
+    Timeout,
+    /// Any other failure.
+    #[postgres(name = "other")]
+    Other,
+}
+
+impl TrackFailureCategory {
+    /// Categorize the repository tracking error provided, returning also the
+    /// HTTP status code of the GitHub response that caused it (if any).
+    fn from_error(err: &Error) -> (Self, Option<i32>) {
+        let status = err
+            .downcast_ref::<UnexpectedStatus>()
+            .map(|UnexpectedStatus(status)| status.as_u16());
+        let category = if err.is::<Elapsed>() {
+            Self::Timeout
+        } else if err.is::<RepositoryNotFound>() {
+            Self::NotFound
+        } else if err.is::<RateLimited>()
+            || status == Some(StatusCode::TOO_MANY_REQUESTS.as_u16())
+            || format!("{err:#}").to_lowercase().contains("rate limit")
+        {
+            Self::RateLimited
+        } else if err.is::<AuthError>() {
+            Self::Auth
+        } else {
+            Self::Other
+        };
+        (category, status.map(i32::from))
+    }
+}
+
+/// Retries configuration.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct RetriesCfg {
+    /// Maximum number of attempts (1 means no retries).
+    pub max_attempts: u32,
+    /// Delay used as the base for the exponential backoff.
+    pub base_delay_ms: u64,
+    /// Maximum delay between attempts, regardless of the attempt number.
+    pub max_delay_ms: u64,
+    /// Whether to retry with a different token when the repository can't be
+    /// fetched due to an authorization error.
+    pub switch_token_on_auth_error: bool,
+}
+
+impl Default for RetriesCfg {
+    fn default() -> Self {
+        Self {
+            max_attempts: 1,
+            base_delay_ms: 1_000,
+            max_delay_ms: 30_000,
+            switch_token_on_auth_error: false,
+        }
+    }
+}
+
+impl RetriesCfg {
+    /// Compute the delay before the next attempt after the failed attempt
+    /// provided, using exponential backoff with jitter. The delay returned is
+    /// never greater than the maximum delay configured.
+    fn backoff_delay(&self, attempt: u32) -> Duration {
+        let exp_delay_ms = 2_u64
+            .checked_pow(attempt.saturating_sub(1))
+            .map_or(u64::MAX, |factor| self.base_delay_ms.saturating_mul(factor))
+            .min(self.max_delay_ms);
+        let jitter_ms = rand::random_range(0..=exp_delay_ms / 2);
+        Duration::from_millis((exp_delay_ms / 2 + jitter_ms).min(self.max_delay_ms))
+    }
+}
+
+/// Changed repositories whose GitHub data is pending to be written to the
+/// database. Repositories are written in batches of the configured size.
+struct RepositoriesBatch {
+    size: usize,
+    pending: Mutex<Vec<Repository>>,
+}
+
+impl RepositoriesBatch {
+    /// Create a new RepositoriesBatch instance.
+    fn new(size: usize) -> Self {
+        Self {
+            size: size.max(1),
+            pending: Mutex::new(Vec::with_capacity(size)),
+        }
+    }
+
+    /// Add the repository provided to the batch, writing the pending ones to
+    /// the database when the batch is full.
+    async fn add(&self, db: &DynDB, repository: Repository) -> Result<()> {
+        let batch = {
+            let mut pending = self.pending.lock().await;
+            pending.push(repository);
+            if pending.len() < self.size {
+                return Ok(());
+            }
+            std::mem::take(&mut *pending)
+        };
+        db.batch_update_repositories(&batch)
+            .await
+            .context("error writing repositories batch")
+    }
+
+    /// Write the pending repositories to the database.
+    async fn flush(&self, db: &DynDB) -> Result<()> {
+        let batch = std::mem::take(&mut *self.pending.lock().await);
+        if batch.is_empty() {
+            return Ok(());
+        }
+        db.batch_update_repositories(&batch)
+            .await
+            .context("error writing repositories batch")
+    }
+}
+
+/// Budget of retries shared by all the repositories tracked during a run.
+struct RetryBudget {
+    remaining: AtomicU32,
+    exhausted: AtomicBool,
+}
+
+impl RetryBudget {
+    /// Create a new RetryBudget instance.
+    fn new(max_total_retries: u32) -> Self {
+        Self {
+            remaining: AtomicU32::new(max_total_retries),
+            exhausted: AtomicBool::new(false),
+        }
+    }
+
+    /// Take a retry from the budget, returning false if it's been exhausted.
+    fn acquire(&self) -> bool {
+        let acquired = self
+            .remaining
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |remaining| {
+                remaining.checked_sub(1)
+            })
+            .is_ok();
+        if !acquired && !self.exhausted.swap(true, Ordering::Relaxed) {
+            warn!("retry budget exhausted, retries disabled for the rest of the run");
+        }
+        acquired
+    }
+}
+
+/// Filter used to select the repositories to track by their url.
+struct UrlFilter {
+    allow: Option<RegexSet>,
+    block: Option<RegexSet>,
+}
+
+impl UrlFilter {
+    /// Create a new UrlFilter instance, compiling the patterns provided.
+    fn new(
+        allow_patterns: Option<&Vec<String>>,
+        block_patterns: Option<&Vec<String>>,
+    ) -> Result<Self> {
+        let compile = |patterns: Option<&Vec<String>>| {
+            patterns
+                .filter(|patterns| !patterns.is_empty())
+                .map(RegexSet::new)
+                .transpose()
         };
+        Ok(Self {
+            allow: compile(allow_patterns).context("invalid url allow patterns")?,
+            block: compile(block_patterns).context("invalid url block patterns")?,
+        })
+    }
+
+    /// Check if the repository url provided is allowed by the filter.
+    fn allows(&self, url: &str) -> bool {
+        if self.block.as_ref().is_some_and(|block| block.is_match(url)) {
+            return false;
+        }
+        self.allow.as_ref().is_none_or(|allow| allow.is_match(url))
+    }
+}
+
+/// Issues validation configuration. By default, issues that can be
+/// sanitized are fixed, and only the ones that can't are rejected.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct IssueValidationCfg {
+    /// Whether to reject issues breaking any rule instead of sanitizing them.
+    pub strict: bool,
+    /// Maximum length (in characters) of the issues titles.
+    pub max_title_length: usize,
+    /// Action taken on issues published in the future (beyond the clock skew
+    /// tolerance) or before the repository was created.
+    pub timestamp_anomalies: TimestampAnomalyAction,
+    /// Clock skew tolerance (in seconds) used when checking if an issue was
+    /// published in the future.
+    pub clock_skew_tolerance_secs: i64,
+}
+
+impl Default for IssueValidationCfg {
+    fn default() -> Self {
+        Self {
+            strict: false,
+            max_title_length: 256,
+            timestamp_anomalies: TimestampAnomalyAction::default(),
+            clock_skew_tolerance_secs: 300,
+        }
+    }
+}
+
+/// Action taken on issues with anomalous timestamps. Issues are always
+/// rejected when the strict validation is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum TimestampAnomalyAction {
+    /// Log the anomaly and keep the timestamp.
+    #[default]
+    Keep,
+    /// Log the anomaly and clamp the timestamp to the valid range.
+    Clamp,
+    /// Reject the issue.
+    Reject,
+}
+
+/// Repository information.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[allow(clippy::struct_field_names)]
+pub(crate) struct Repository {
+    pub repository_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub url: String,
+    pub homepage_url: Option<String>,
+    pub topics: Option<Vec<String>>,
+    pub languages: Option<Vec<String>>,
+    pub primary_language: Option<String>,
+    pub primary_language_color: Option<String>,
+    pub stars: Option<i32>,
+    pub digest: Option<String>,
+    pub issues_filter_label: Option<String>,
+    pub open_issues_total: Option<i32>,
+    pub closed_issues_total: Option<i32>,
+    pub default_branch_protected: Option<bool>,
+    pub query_version: Option<i32>,
+    pub is_active: Option<bool>,
+    pub issues_cursor: Option<String>,
+    pub issues_page_size: Option<i32>,
+    pub requires_cla: Option<bool>,
+    pub top_contributors: Option<Vec<(String, i32)>>,
+    pub commit_activity: Option<Vec<i32>>,
+    pub has_issues_enabled: Option<bool>,
+    pub open_milestones: Option<i32>,
+    pub is_empty: Option<bool>,
+    pub is_private: Option<bool>,
+    pub latest_release_at: Option<OffsetDateTime>,
+    pub owner_followers: Option<i32>,
+    pub head_sha: Option<String>,
+    pub raw_response_digest: Option<String>,
+    pub watch_star_ratio: Option<f32>,
+    pub badges: Option<Vec<(String, String)>>,
+    pub issue_close_rate: Option<f32>,
+    pub metadata: Option<RepositoryMetadata>,
+    pub project_name: String,
+    pub foundation_id: String,
+}
+
+impl Repository {
+    /// Update repository's GitHub data.
+    #[allow(clippy::cast_possible_truncation)]
+    fn update_gh_data(
+        &mut self,
+        cfg: &TrackerCfg,
+        gh_repo: &repo_view::RepoViewRepository,
+        issues_activity: &HashMap<i64, OffsetDateTime>,
+    ) -> Result<bool> {
+        // Description
+        self.description.clone_from(&gh_repo.description);
+
+        // Homepage url
+        self.homepage_url.clone_from(&gh_repo.homepage_url);
+
+        // Languages
+        self.languages = gh_repo.languages.as_ref().and_then(|languages| {
+            languages.nodes.as_ref().map(|nodes| {
+                nodes
+                    .iter()
+                    .flatten()
+                    .map(|node| node.name.clone())
+                    .collect()
+            })
+        });
+
+        // Primary language (and its color)
+        self.primary_language = gh_repo
+            .primary_language
+            .as_ref()
+            .map(|language| language.name.clone());
+        self.primary_language_color = gh_repo
+            .primary_language
+            .as_ref()
+            .and_then(|language| language.color.clone());
+
+        // Issues enabled
+        self.has_issues_enabled = Some(gh_repo.has_issues_enabled);
+
+        // Empty (no commits yet)
+        self.is_empty = Some(gh_repo.is_empty);
+
+        // Latest release creation timestamp
+        self.latest_release_at = gh_repo.latest_release_at();
+
+        // Stars
+        self.stars = Some(gh_repo.stargazer_count as i32);
+
+        // Watchers to stars ratio (if enabled)
+        self.watch_star_ratio = if cfg.compute_watch_star_ratio && gh_repo.stargazer_count > 0 {
+            #[allow(clippy::cast_precision_loss)]
+            Some(gh_repo.watchers.total_count as f32 / gh_repo.stargazer_count as f32)
+        } else {
+            None
+        };
+
+        // Open and closed issues totals
+        self.open_issues_total = Some(gh_repo.open_issues.total_count as i32);
+        self.closed_issues_total = Some(gh_repo.closed_issues.total_count as i32);
+
+        // Open milestones
+        self.open_milestones = gh_repo
+            .open_milestones
+            .as_ref()
+            .map(|milestones| milestones.total_count as i32);
+
+        // Default branch protection
+        self.default_branch_protected = if cfg.fetch_branch_protection {
+            gh_repo
+                .default_branch_ref
+                .as_ref()
+                .map(|branch| branch.branch_protection_rule.is_some())
+        } else {
+            None
+        };
+
+        // Default branch latest commit SHA (if enabled)
+        self.head_sha = if cfg.fetch_head_sha {
+            gh_repo
+                .default_branch_ref
+                .as_ref()
+                .and_then(|branch| match &branch.target {
+                    Some(repo_view::RepoViewRepositoryDefaultBranchRefTarget::Commit(commit)) => {
+                        Some(commit.oid.clone())
+                    }
+                    _ => None,
+                })
+        } else {
+            None
+        };
+
+        // Topics
+        self.topics = gh_repo.repository_topics.nodes.as_ref().map(|nodes| {
+            nodes
+                .iter()
+                .flatten()
+                .map(|node| node.topic.name.clone())
+                .collect()
+        });
+
+        // Active (there has been some issues activity in the window configured)
+        let active_window_start =
+            OffsetDateTime::now_utc() - time::Duration::days(i64::from(cfg.active_window_days));
+        self.is_active = Some(
+            gh_repo
+                .issues_last_activity(issues_activity)
+                .is_some_and(|ts| ts >= active_window_start),
+        );
+
+        // Requires CLA (only when detection is enabled)
+        self.requires_cla = if cfg.detect_cla {
+            gh_repo.requires_cla()
+        } else {
+            None
+        };
+
+        // README badges (only when parsing them is enabled)
+        self.badges = if cfg.parse_badges {
+            gh_repo.badges()
+        } else {
+            None
+        };
+
+        // Query version
+        self.query_version = Some(REPO_VIEW_QUERY_VERSION);
+
+        // Raw response digest (if enabled). It isn't part of the digest, but
+        // its changes are written as well
+        let prev_raw_response_digest = self.raw_response_digest.take();
+        if cfg.track_raw_digest {
+            self.raw_response_digest = Some(compute_digest(gh_repo)?);
+        }
+
+        // Digest
+        let prev_digest = self.digest.clone();
+        self.update_digest(cfg.stars_in_digest)?;
+        Ok(self.digest != prev_digest || self.raw_response_digest != prev_raw_response_digest)
+    }
+
+    /// Update repository's digest, including the stars (and the watch to star
+    /// ratio derived from them) only if requested.
+    fn update_digest(&mut self, include_stars: bool) -> Result<()> {
+        let (stars, watch_star_ratio) = if include_stars {
+            (self.stars, self.watch_star_ratio)
+        } else {
+            (None, None)
+        };
+        let digest = compute_digest(&(
+            &self.description,
+            &self.homepage_url,
+            &self.languages,
+            &self.topics,
+            &stars,
+            &self.open_issues_total,
+            &self.closed_issues_total,
+            &self.default_branch_protected,
+            &self.query_version,
+            &self.is_active,
+            &self.requires_cla,
+            &self.top_contributors,
+            &self.primary_language,
+            &self.primary_language_color,
+            &self.commit_activity,
+            // Tuples can have up to 16 elements, so the latest fields are
+            // grouped (the encoding is the same as if they weren't)
+            (
+                &self.has_issues_enabled,
+                &self.open_milestones,
+                &self.is_empty,
+                &self.latest_release_at,
+                &self.owner_followers,
+                &self.head_sha,
+                &watch_star_ratio,
+                &self.badges,
+            ),
+        ))?;
+        self.digest = Some(digest);
+        Ok(())
+    }
+}
+
+/// Compute the digest of the data provided. The data is encoded straight into
+/// the hasher, so no intermediate buffer is allocated.
+fn compute_digest(data: &impl Serialize) -> Result<String> {
+    let mut hasher = Sha256::new();
+    bincode::serde::encode_into_std_write(data, &mut hasher, bincode::config::legacy())?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Project metadata obtained from the landscape file for a repository.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RepositoryMetadata {
+    pub category: String,
+    pub subcategory: String,
+    pub project_name: String,
+}
+
+/// Landscape file, only including the fields needed to extract the
+/// repositories metadata.
+#[derive(Debug, Deserialize)]
+struct Landscape {
+    landscape: Vec<LandscapeCategory>,
+}
+
+/// Landscape category.
+#[derive(Debug, Deserialize)]
+struct LandscapeCategory {
+    name: String,
+    #[serde(default)]
+    subcategories: Vec<LandscapeSubcategory>,
+}
+
+/// Landscape subcategory.
+#[derive(Debug, Deserialize)]
+struct LandscapeSubcategory {
+    name: String,
+    #[serde(default)]
+    items: Vec<LandscapeItem>,
+}
+
+/// Landscape item.
+#[derive(Debug, Deserialize)]
+struct LandscapeItem {
+    name: String,
+    repo_url: Option<String>,
+    #[serde(default)]
+    additional_repos: Vec<LandscapeAdditionalRepo>,
+}
+
+/// Landscape item's additional repository.
+#[derive(Debug, Deserialize)]
+struct LandscapeAdditionalRepo {
+    repo_url: String,
+}
+
+/// Load the repositories metadata from the landscape file at the location
+/// (local path or url) provided, indexed by their normalized url.
+async fn load_repositories_metadata(location: &str) -> Result<HashMap<String, RepositoryMetadata>> {
+    // Read landscape file
+    let data = if location.starts_with("http://") || location.starts_with("https://") {
+        let resp = reqwest::get(location).await?;
+        if resp.status() != StatusCode::OK {
+            bail!(
+                "unexpected status code getting metadata file: {}",
+                resp.status()
+            );
+        }
+        resp.text().await?
+    } else {
+        std::fs::read_to_string(location).context("error reading metadata file")?
+    };
+    let landscape: Landscape = serde_yaml::from_str(&data).context("invalid metadata file")?;
+
+    // Index the metadata of each item by its repositories urls
+    let mut repositories_metadata = HashMap::new();
+    for category in landscape.landscape {
+        for subcategory in category.subcategories {
+            for item in subcategory.items {
+                let metadata = RepositoryMetadata {
+                    category: category.name.clone(),
+                    subcategory: subcategory.name.clone(),
+                    project_name: item.name,
+                };
+                let repos_urls = item
+                    .repo_url
+                    .into_iter()
+                    .chain(item.additional_repos.into_iter().map(|r| r.repo_url));
+                for repo_url in repos_urls {
+                    repositories_metadata
+                        .insert(normalize_repository_url(&repo_url), metadata.clone());
+                }
+            }
+        }
+    }
+
+    Ok(repositories_metadata)
+}
+
+/// Normalize the repository url provided so that it can be used to match
+/// repositories across data sources.
+fn normalize_repository_url(url: &str) -> String {
+    url.trim().trim_end_matches('/').to_lowercase()
+}
+
+/// Label definition.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Label {
+    pub name: String,
+    pub color: String,
+    pub description: Option<String>,
+}
+
+/// Issue area.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSql, FromSql)]
+#[serde(rename_all = "kebab-case")]
+#[postgres(name = "area")]
+pub enum IssueArea {
+    #[postgres(name = "docs")]
+    Docs,
+}
+
+/// Issue kind.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSql, FromSql)]
+#[serde(rename_all = "kebab-case")]
+#[postgres(name = "kind")]
+pub enum IssueKind {
+    #[postgres(name = "bug")]
+    Bug,
+    #[postgres(name = "feature")]
+    Feature,
+    #[postgres(name = "enhancement")]
+    Enhancement,
+}
+
+/// Issue difficulty.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSql, FromSql)]
+#[serde(rename_all = "kebab-case")]
+#[postgres(name = "difficulty")]
+pub enum IssueDifficulty {
+    #[postgres(name = "easy")]
+    Easy,
+    #[postgres(name = "medium")]
+    Medium,
+    #[postgres(name = "hard")]
+    Hard,
+}
+
+/// Issue information.
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::struct_field_names)]
+pub(crate) struct Issue {
+    pub issue_id: i64,
+    pub title: String,
+    pub url: String,
+    pub number: i32,
+    pub labels: Vec<String>,
+    pub published_at: OffsetDateTime,
+    pub has_linked_prs: bool,
+    pub linked_pr_url: Option<String>,
+    pub linked_pr_state: Option<String>,
+    pub last_activity_at: Option<OffsetDateTime>,
+    pub reactions_by_type: Option<Vec<(String, i32)>>,
+    pub participants: i32,
+    pub is_pinned: bool,
+    pub is_available: Option<bool>,
+    pub needs_triage: Option<bool>,
+    pub first_response_seconds: Option<i64>,
+    pub digest: Option<String>,
+    pub area: Option<IssueArea>,
+    pub kind: Option<IssueKind>,
+    pub difficulty: Option<IssueDifficulty>,
+    pub mentor_available: Option<bool>,
+    pub mentor: Option<String>,
+    pub good_first_issue: Option<bool>,
+}
+
+impl Issue {
+    /// Update issue's digest.
+    pub(crate) fn update_digest(&mut self) {
+        let Ok(digest) = compute_digest(&(
+            &self.title,
+            &self.labels,
+            &self.has_linked_prs,
+            &self.linked_pr_url,
+            &self.linked_pr_state,
+            &self.last_activity_at,
+            &self.reactions_by_type,
+            &self.participants,
+            &self.is_pinned,
+            &self.difficulty,
+            &self.is_available,
+            &self.needs_triage,
+            &self.first_response_seconds,
+        )) else {
+            return;
+        };
+        self.digest = Some(digest);
+    }
+
+    /// Validate the issue using the rules provided, sanitizing it when possible
+    /// unless strict validation is enabled. An error is returned when the
+    /// issue must be rejected.
+    pub(crate) fn validate(
+        &mut self,
+        cfg: &IssueValidationCfg,
+        repo_created_at: Option<OffsetDateTime>,
+    ) -> Result<()> {
+        let mut sanitized = false;
+
+        // Title
+        let title = self.title.trim();
+        if title.is_empty() {
+            bail!("empty title");
+        }
+        if title.len() != self.title.len() {
+            if cfg.strict {
+                bail!("title with leading or trailing whitespace");
+            }
+            self.title = title.to_string();
+            sanitized = true;
+        }
+        if self.title.chars().count() > cfg.max_title_length {
+            if cfg.strict {
+                bail!("title too long");
+            }
+            self.title = self.title.chars().take(cfg.max_title_length).collect();
+            sanitized = true;
+        }
+
+        // Url
+        if self.url.is_empty() {
+            bail!("empty url");
+        }
+
+        // Timestamps
+        let now = OffsetDateTime::now_utc();
+        let anomaly =
+            if self.published_at > now + time::Duration::seconds(cfg.clock_skew_tolerance_secs) {
+                Some(("published in the future", now))
+            } else if let Some(repo_created_at) = repo_created_at
+                && self.published_at < repo_created_at
+            {
+                Some(("published before repository creation", repo_created_at))
+            } else {
+                None
+            };
+        if let Some((anomaly, clamped_published_at)) = anomaly {
+            match cfg.timestamp_anomalies {
+                _ if cfg.strict => bail!(anomaly),
+                TimestampAnomalyAction::Reject => bail!(anomaly),
+                TimestampAnomalyAction::Clamp => {
+                    warn!(self.number, anomaly, "clamping issue publication timestamp");
+                    self.published_at = clamped_published_at;
+                    sanitized = true;
+                }
+                TimestampAnomalyAction::Keep => {
+                    warn!(
+                        self.number,
+                        anomaly, "anomalous issue publication timestamp"
+                    );
+                }
+            }
+        }
+        if cfg.strict
+            && self
+                .last_activity_at
+                .is_some_and(|ts| ts < self.published_at)
+        {
+            bail!("last activity before publication");
+        }
+
+        if sanitized {
+            self.update_digest();
+        }
+        Ok(())
+    }
+
+    /// Prepare texts for text search document.
+    pub(crate) fn prepare_ts_texts(&self, repo: &Repository) -> IssueTsTexts {
+        // Weight A
+        let weight_a = repo.project_name.clone();
+
+        // Weight B
+        let weight_b = format!(
+            "{} {} {} {} {}",
+            &repo.foundation_id,
+            &repo.name,
+            &repo.description.clone().unwrap_or_default(),
+            &repo
+                .topics
+                .as_ref()
+                .map(|topics| topics.join(" "))
+                .unwrap_or_default(),
+            &repo
+                .languages
+                .as_ref()
+                .map(|languages| languages.join(" "))
+                .unwrap_or_default()
+        )
+        .trim()
+        .to_owned();
+
+        // Weight C
+        let weight_c = format!("{} {}", self.title, self.labels.join(" "));
+
+        IssueTsTexts {
+            weight_a,
+            weight_b,
+            weight_c,
+        }
+    }
+
+    /// Populate the issue with information extracted from the labels, like the
+    /// issue kind, its difficulty, etc. The difficulty labels provided (matched
+    /// case-insensitively) take precedence over the default ones.
+    pub(crate) fn populate_from_labels(
+        &mut self,
+        difficulty_labels: Option<&HashMap<String, IssueDifficulty>>,
+    ) {
+        for label in &self.labels {
+            // Area
+            if label.contains("docs") || label.contains("documentation") {
+                self.area = Some(IssueArea::Docs);
+                continue;
+            }
+
+            // Kind
+            if let Some(kind) = {
+                if label.contains("enhancement") || label.contains("improvement") {
+                    Some(IssueKind::Enhancement)
+                } else if label.contains("feature") {
+                    Some(IssueKind::Feature)
+                } else if label.contains("bug") {
+                    Some(IssueKind::Bug)
+                } else {
+                    None
+                }
+            } {
+                self.kind = Some(kind);
+                continue;
+            }
+
+            // Difficulty
+            let labels_easy = ["difficulty/easy", "level/easy"];
+            let labels_medium = ["difficulty/medium", "level/medium"];
+            let labels_hard = ["difficulty/hard", "level/hard"];
+            if let Some(difficulty) = {
+                if let Some((_, difficulty)) = difficulty_labels.and_then(|difficulty_labels| {
+                    difficulty_labels
+                        .iter()
+                        .find(|(difficulty_label, _)| difficulty_label.eq_ignore_ascii_case(label))
+                }) {
+                    Some(difficulty.clone())
+                } else if labels_easy.contains(&label.as_str()) {
+                    Some(IssueDifficulty::Easy)
+                } else if labels_medium.contains(&label.as_str()) {
+                    Some(IssueDifficulty::Medium)
+                } else if labels_hard.contains(&label.as_str()) {
+                    Some(IssueDifficulty::Hard)
+                } else {
+                    None
+                }
+            } {
+                self.difficulty = Some(difficulty);
+                continue;
+            }
+
+            // Mentor available
+            if label == "mentor available" || label == "mentorship" {
+                self.mentor_available = Some(true);
+                continue;
+            }
+
+            // Good first issue
+            if label == "good first issue" {
+                self.good_first_issue = Some(true);
+            }
+        }
+    }
+}
+
+/// Topic information.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+pub(crate) struct Topic {
+    pub name: String,
+    pub display_name: Option<String>,
+    pub short_description: Option<String>,
+    pub description: Option<String>,
+    pub featured: bool,
+    pub curated: bool,
+}
+
+/// Issue closed as completed, part of the recently closed issues feed.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ClosedIssue {
+    pub issue_id: i64,
+    pub title: String,
+    pub url: String,
+    pub number: i32,
+    pub closed_at: OffsetDateTime,
+}
+
+/// Reference to an issue from another one (cross-referenced in its timeline).
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct IssueReference {
+    pub issue_id: i64,
+    pub source_issue_id: i64,
+    pub source_url: String,
+}
+
+/// Texts used to build the issue's text search document.
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::struct_field_names)]
+pub(crate) struct IssueTsTexts {
+    pub weight_a: String,
+    pub weight_b: String,
+    pub weight_c: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::MockDB,
+        github::{MockGH, repo_view::*},
+    };
+    use futures::future;
+    use mockall::{Sequence, predicate::eq};
+    use std::sync::{Arc, LazyLock};
+    use time::format_description::well_known::Rfc3339;
+
+    const TOKEN1: &str = "0001";
+    const TOKEN2: &str = "0002";
+    const REPOSITORY_URL: &str = "https://repo1.url";
+    const ORG1_REPOSITORY_URL: &str = "https://github.com/org1/repo1";
+    const FAKE_ERROR: &str = "fake error";
+    const TESTDATA_PATH: &str = "src/testdata";
+
+    static REPOSITORY_ID: LazyLock<Uuid> =
+        LazyLock::new(|| Uuid::parse_str("00000000-0001-0000-0000-000000000000").unwrap());
+
+    #[test]
+    fn adaptive_page_size_cfg_next_page_size() {
+        let cfg = AdaptivePageSizeCfg::default();
+        assert_eq!(cfg.page_size(None), 50);
+        assert_eq!(cfg.page_size(Some(500)), 100);
+        assert_eq!(cfg.next_page_size(50, 1, 50), 100);
+        assert_eq!(cfg.next_page_size(50, 1, 20), 50);
+        assert_eq!(cfg.next_page_size(50, 2, 50), 50);
+        assert_eq!(cfg.next_page_size(50, 4, 50), 25);
+        assert_eq!(cfg.next_page_size(100, 1, 100), 100);
+        assert_eq!(cfg.next_page_size(10, 10, 10), 10);
+    }
+
+    #[test]
+    fn repository_update_gh_data_no_changes() {
+        let mut repo = Repository {
+            repository_id: *REPOSITORY_ID,
+            url: REPOSITORY_URL.to_string(),
+            stars: Some(0),
+            open_issues_total: Some(0),
+            closed_issues_total: Some(0),
+            digest: Some(
+                "bf95b753b2664eccee8f4636fe9a1def9f3d3a2bd88f6f1eccb940d6c682b2e6".to_string(),
+            ),
+            ..Default::default()
+        };
+        let gh_repo = RepoViewRepository {
+            closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+            contributing_guide: None,
+            created_at: "1980-01-01T00:00:00Z".to_string(),
+            default_branch_ref: None,
+            description: None,
+            github_contributing_guide: None,
+            has_issues_enabled: true,
+            homepage_url: Some(REPOSITORY_URL.to_string()),
+            is_empty: false,
+            is_private: false,
+            issues: RepoViewRepositoryIssues {
+                nodes: None,
+                page_info: RepoViewRepositoryIssuesPageInfo {
+                    end_cursor: None,
+                    has_next_page: false,
+                },
+            },
+            languages: None,
+            latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+            open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+            open_milestones: None,
+            pinned_issues: None,
+            primary_language: None,
+            pushed_at: None,
+            readme: None,
+            repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+            stargazer_count: 0,
+            watchers: RepoViewRepositoryWatchers { total_count: 0 },
+        };
+
+        assert!(
+            !repo
+                .update_gh_data(&TrackerCfg::default(), &gh_repo, &HashMap::new())
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn repository_update_gh_data_description_changed() {
+        let mut repo = Repository {
+            repository_id: *REPOSITORY_ID,
+            url: REPOSITORY_URL.to_string(),
+            stars: Some(0),
+            open_issues_total: Some(0),
+            closed_issues_total: Some(0),
+            digest: Some(
+                "bf95b753b2664eccee8f4636fe9a1def9f3d3a2bd88f6f1eccb940d6c682b2e6".to_string(),
+            ),
+            ..Default::default()
+        };
+        let gh_repo = RepoViewRepository {
+            closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+            contributing_guide: None,
+            created_at: "1980-01-01T00:00:00Z".to_string(),
+            default_branch_ref: None,
+            description: Some("description".to_string()),
+            github_contributing_guide: None,
+            has_issues_enabled: true,
+            homepage_url: Some(REPOSITORY_URL.to_string()),
+            is_empty: false,
+            is_private: false,
+            issues: RepoViewRepositoryIssues {
+                nodes: None,
+                page_info: RepoViewRepositoryIssuesPageInfo {
+                    end_cursor: None,
+                    has_next_page: false,
+                },
+            },
+            languages: None,
+            latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+            open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+            open_milestones: None,
+            pinned_issues: None,
+            primary_language: None,
+            pushed_at: None,
+            readme: None,
+            repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+            stargazer_count: 0,
+            watchers: RepoViewRepositoryWatchers { total_count: 0 },
+        };
+
+        assert!(
+            repo.update_gh_data(&TrackerCfg::default(), &gh_repo, &HashMap::new())
+                .unwrap()
+        );
+        assert_eq!(
+            repo.digest,
+            Some("022c989efb66b3c5a1bc7717df1f79b0d503c933fbe1606c8c18e604ab0c9c3d".to_string())
+        );
+    }
+
+    #[test]
+    fn repository_update_gh_data_issues_totals_changed() {
+        let mut repo = Repository {
+            repository_id: *REPOSITORY_ID,
+            url: REPOSITORY_URL.to_string(),
+            stars: Some(0),
+            open_issues_total: Some(0),
+            closed_issues_total: Some(0),
+            digest: Some(
+                "bf95b753b2664eccee8f4636fe9a1def9f3d3a2bd88f6f1eccb940d6c682b2e6".to_string(),
+            ),
+            ..Default::default()
+        };
+        let gh_repo = RepoViewRepository {
+            closed_issues: RepoViewRepositoryClosedIssues { total_count: 7 },
+            contributing_guide: None,
+            created_at: "1980-01-01T00:00:00Z".to_string(),
+            default_branch_ref: None,
+            description: None,
+            github_contributing_guide: None,
+            has_issues_enabled: true,
+            homepage_url: Some(REPOSITORY_URL.to_string()),
+            is_empty: false,
+            is_private: false,
+            issues: RepoViewRepositoryIssues {
+                nodes: None,
+                page_info: RepoViewRepositoryIssuesPageInfo {
+                    end_cursor: None,
+                    has_next_page: false,
+                },
+            },
+            languages: None,
+            latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+            open_issues: RepoViewRepositoryOpenIssues { total_count: 3 },
+            open_milestones: None,
+            pinned_issues: None,
+            primary_language: None,
+            pushed_at: None,
+            readme: None,
+            repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+            stargazer_count: 0,
+            watchers: RepoViewRepositoryWatchers { total_count: 0 },
+        };
+
+        assert!(
+            repo.update_gh_data(&TrackerCfg::default(), &gh_repo, &HashMap::new())
+                .unwrap()
+        );
+        assert_eq!(repo.open_issues_total, Some(3));
+        assert_eq!(repo.closed_issues_total, Some(7));
+    }
+
+    #[test]
+    fn repository_update_gh_data_default_branch_protected() {
+        let cfg = TrackerCfg {
+            fetch_branch_protection: true,
+            ..Default::default()
+        };
+        let mut repo = Repository {
+            repository_id: *REPOSITORY_ID,
+            url: REPOSITORY_URL.to_string(),
+            ..Default::default()
+        };
+        let gh_repo = RepoViewRepository {
+            closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+            contributing_guide: None,
+            created_at: "1980-01-01T00:00:00Z".to_string(),
+            default_branch_ref: Some(RepoViewRepositoryDefaultBranchRef {
+                branch_protection_rule: Some(
+                    RepoViewRepositoryDefaultBranchRefBranchProtectionRule {
+                        id: "rule1".to_string(),
+                    },
+                ),
+                target: None,
+            }),
+            description: None,
+            github_contributing_guide: None,
+            has_issues_enabled: true,
+            homepage_url: None,
+            is_empty: false,
+            is_private: false,
+            issues: RepoViewRepositoryIssues {
+                nodes: None,
+                page_info: RepoViewRepositoryIssuesPageInfo {
+                    end_cursor: None,
+                    has_next_page: false,
+                },
+            },
+            languages: None,
+            latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+            open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+            open_milestones: None,
+            pinned_issues: None,
+            primary_language: None,
+            pushed_at: None,
+            readme: None,
+            repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+            stargazer_count: 0,
+            watchers: RepoViewRepositoryWatchers { total_count: 0 },
+        };
+
+        assert!(
+            repo.update_gh_data(&cfg, &gh_repo, &HashMap::new())
+                .unwrap()
+        );
+        assert_eq!(repo.default_branch_protected, Some(true));
+
+        repo.update_gh_data(&TrackerCfg::default(), &gh_repo, &HashMap::new())
+            .unwrap();
+        assert_eq!(repo.default_branch_protected, None);
+    }
+
+    #[test]
+    fn repository_update_gh_data_watch_star_ratio() {
+        let cfg = TrackerCfg {
+            compute_watch_star_ratio: true,
+            ..Default::default()
+        };
+        let mut repo = Repository {
+            repository_id: *REPOSITORY_ID,
+            url: REPOSITORY_URL.to_string(),
+            ..Default::default()
+        };
+        let mut gh_repo = RepoViewRepository {
+            closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+            contributing_guide: None,
+            created_at: "1980-01-01T00:00:00Z".to_string(),
+            default_branch_ref: None,
+            description: None,
+            github_contributing_guide: None,
+            has_issues_enabled: true,
+            homepage_url: None,
+            is_empty: false,
+            is_private: false,
+            issues: RepoViewRepositoryIssues {
+                nodes: None,
+                page_info: RepoViewRepositoryIssuesPageInfo {
+                    end_cursor: None,
+                    has_next_page: false,
+                },
+            },
+            languages: None,
+            latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+            open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+            open_milestones: None,
+            pinned_issues: None,
+            primary_language: None,
+            pushed_at: None,
+            readme: None,
+            repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+            stargazer_count: 40,
+            watchers: RepoViewRepositoryWatchers { total_count: 10 },
+        };
+
+        assert!(
+            repo.update_gh_data(&cfg, &gh_repo, &HashMap::new())
+                .unwrap()
+        );
+        assert_eq!(repo.watch_star_ratio, Some(0.25));
+
+        gh_repo.stargazer_count = 0;
+        repo.update_gh_data(&cfg, &gh_repo, &HashMap::new())
+            .unwrap();
+        assert_eq!(repo.watch_star_ratio, None);
+
+        gh_repo.stargazer_count = 40;
+        repo.update_gh_data(&TrackerCfg::default(), &gh_repo, &HashMap::new())
+            .unwrap();
+        assert_eq!(repo.watch_star_ratio, None);
+    }
+
+    #[test]
+    fn repository_update_gh_data_raw_digest() {
+        let cfg = TrackerCfg {
+            track_raw_digest: true,
+            ..Default::default()
+        };
+        let mut repo = Repository {
+            repository_id: *REPOSITORY_ID,
+            url: REPOSITORY_URL.to_string(),
+            ..Default::default()
+        };
+        let mut gh_repo = RepoViewRepository {
+            closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+            contributing_guide: None,
+            created_at: "1980-01-01T00:00:00Z".to_string(),
+            default_branch_ref: None,
+            description: None,
+            github_contributing_guide: None,
+            has_issues_enabled: true,
+            homepage_url: None,
+            is_empty: false,
+            is_private: false,
+            issues: RepoViewRepositoryIssues {
+                nodes: None,
+                page_info: RepoViewRepositoryIssuesPageInfo {
+                    end_cursor: None,
+                    has_next_page: false,
+                },
+            },
+            languages: None,
+            latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+            open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+            open_milestones: None,
+            pinned_issues: None,
+            primary_language: None,
+            pushed_at: None,
+            readme: None,
+            repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+            stargazer_count: 0,
+            watchers: RepoViewRepositoryWatchers { total_count: 0 },
+        };
+
+        assert!(
+            repo.update_gh_data(&cfg, &gh_repo, &HashMap::new())
+                .unwrap()
+        );
+        assert!(repo.raw_response_digest.is_some());
+        assert!(
+            !repo
+                .update_gh_data(&cfg, &gh_repo, &HashMap::new())
+                .unwrap()
+        );
+
+        // Changes in fields not part of the digest are detected as well
+        let digest = repo.digest.clone();
+        gh_repo.issues.page_info.end_cursor = Some("cursor".to_string());
+        assert!(
+            repo.update_gh_data(&cfg, &gh_repo, &HashMap::new())
+                .unwrap()
+        );
+        assert_eq!(repo.digest, digest);
+
+        repo.update_gh_data(&TrackerCfg::default(), &gh_repo, &HashMap::new())
+            .unwrap();
+        assert_eq!(repo.raw_response_digest, None);
+    }
+
+    #[test]
+    fn repository_update_gh_data_head_sha() {
+        let cfg = TrackerCfg {
+            fetch_head_sha: true,
+            ..Default::default()
+        };
+        let mut repo = Repository {
+            repository_id: *REPOSITORY_ID,
+            url: REPOSITORY_URL.to_string(),
+            ..Default::default()
+        };
+        let gh_repo = RepoViewRepository {
+            closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+            contributing_guide: None,
+            created_at: "1980-01-01T00:00:00Z".to_string(),
+            default_branch_ref: Some(RepoViewRepositoryDefaultBranchRef {
+                branch_protection_rule: None,
+                target: Some(RepoViewRepositoryDefaultBranchRefTarget::Commit(
+                    RepoViewRepositoryDefaultBranchRefTargetOnCommit {
+                        oid: "sha1".to_string(),
+                    },
+                )),
+            }),
+            description: None,
+            github_contributing_guide: None,
+            has_issues_enabled: true,
+            homepage_url: None,
+            is_empty: false,
+            is_private: false,
+            issues: RepoViewRepositoryIssues {
+                nodes: None,
+                page_info: RepoViewRepositoryIssuesPageInfo {
+                    end_cursor: None,
+                    has_next_page: false,
+                },
+            },
+            languages: None,
+            latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+            open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+            open_milestones: None,
+            pinned_issues: None,
+            primary_language: None,
+            pushed_at: None,
+            readme: None,
+            repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+            stargazer_count: 0,
+            watchers: RepoViewRepositoryWatchers { total_count: 0 },
+        };
+
+        assert!(
+            repo.update_gh_data(&cfg, &gh_repo, &HashMap::new())
+                .unwrap()
+        );
+        assert_eq!(repo.head_sha, Some("sha1".to_string()));
+
+        let mut gh_repo = gh_repo;
+        gh_repo.default_branch_ref = Some(RepoViewRepositoryDefaultBranchRef {
+            branch_protection_rule: None,
+            target: Some(RepoViewRepositoryDefaultBranchRefTarget::Commit(
+                RepoViewRepositoryDefaultBranchRefTargetOnCommit {
+                    oid: "sha2".to_string(),
+                },
+            )),
+        });
+        assert!(
+            repo.update_gh_data(&cfg, &gh_repo, &HashMap::new())
+                .unwrap()
+        );
+        assert_eq!(repo.head_sha, Some("sha2".to_string()));
+
+        repo.update_gh_data(&TrackerCfg::default(), &gh_repo, &HashMap::new())
+            .unwrap();
+        assert_eq!(repo.head_sha, None);
+    }
+
+    #[test]
+    fn repository_update_gh_data_primary_language() {
+        let mut repo = Repository {
+            repository_id: *REPOSITORY_ID,
+            url: REPOSITORY_URL.to_string(),
+            ..Default::default()
+        };
+        let gh_repo = RepoViewRepository {
+            closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+            contributing_guide: None,
+            created_at: "1980-01-01T00:00:00Z".to_string(),
+            default_branch_ref: None,
+            description: None,
+            github_contributing_guide: None,
+            has_issues_enabled: true,
+            homepage_url: None,
+            is_empty: false,
+            is_private: false,
+            issues: RepoViewRepositoryIssues {
+                nodes: None,
+                page_info: RepoViewRepositoryIssuesPageInfo {
+                    end_cursor: None,
+                    has_next_page: false,
+                },
+            },
+            languages: None,
+            latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+            open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+            open_milestones: None,
+            pinned_issues: None,
+            primary_language: Some(RepoViewRepositoryPrimaryLanguage {
+                color: Some("#dea584".to_string()),
+                name: "Rust".to_string(),
+            }),
+            pushed_at: None,
+            readme: None,
+            repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+            stargazer_count: 0,
+            watchers: RepoViewRepositoryWatchers { total_count: 0 },
+        };
+
+        assert!(
+            repo.update_gh_data(&TrackerCfg::default(), &gh_repo, &HashMap::new())
+                .unwrap()
+        );
+        assert_eq!(repo.primary_language, Some("Rust".to_string()));
+        assert_eq!(repo.primary_language_color, Some("#dea584".to_string()));
+    }
+
+    #[test]
+    fn repository_update_gh_data_open_milestones() {
+        let mut repo = Repository {
+            repository_id: *REPOSITORY_ID,
+            url: REPOSITORY_URL.to_string(),
+            ..Default::default()
+        };
+        let gh_repo = RepoViewRepository {
+            closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+            contributing_guide: None,
+            created_at: "1980-01-01T00:00:00Z".to_string(),
+            default_branch_ref: None,
+            description: None,
+            github_contributing_guide: None,
+            has_issues_enabled: true,
+            homepage_url: None,
+            is_empty: false,
+            is_private: false,
+            issues: RepoViewRepositoryIssues {
+                nodes: None,
+                page_info: RepoViewRepositoryIssuesPageInfo {
+                    end_cursor: None,
+                    has_next_page: false,
+                },
+            },
+            languages: None,
+            latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+            open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+            open_milestones: Some(RepoViewRepositoryOpenMilestones { total_count: 2 }),
+            pinned_issues: None,
+            primary_language: None,
+            pushed_at: None,
+            readme: None,
+            repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+            stargazer_count: 0,
+            watchers: RepoViewRepositoryWatchers { total_count: 0 },
+        };
+
+        assert!(
+            repo.update_gh_data(&TrackerCfg::default(), &gh_repo, &HashMap::new())
+                .unwrap()
+        );
+        assert_eq!(repo.open_milestones, Some(2));
+    }
+
+    #[test]
+    fn repository_update_gh_data_abandonment_inputs() {
+        let mut repo = Repository {
+            repository_id: *REPOSITORY_ID,
+            url: REPOSITORY_URL.to_string(),
+            ..Default::default()
+        };
+        let gh_repo = RepoViewRepository {
+            closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+            contributing_guide: None,
+            created_at: "1980-01-01T00:00:00Z".to_string(),
+            default_branch_ref: None,
+            description: None,
+            github_contributing_guide: None,
+            has_issues_enabled: true,
+            homepage_url: None,
+            is_empty: true,
+            is_private: false,
+            issues: RepoViewRepositoryIssues {
+                nodes: None,
+                page_info: RepoViewRepositoryIssuesPageInfo {
+                    end_cursor: None,
+                    has_next_page: false,
+                },
+            },
+            languages: None,
+            latest_release: RepoViewRepositoryLatestRelease {
+                nodes: Some(vec![Some(RepoViewRepositoryLatestReleaseNodes {
+                    created_at: "2025-01-02T03:04:05Z".to_string(),
+                })]),
+            },
+            open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+            open_milestones: None,
+            pinned_issues: None,
+            primary_language: None,
+            pushed_at: None,
+            readme: None,
+            repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+            stargazer_count: 0,
+            watchers: RepoViewRepositoryWatchers { total_count: 0 },
+        };
+
+        assert!(
+            repo.update_gh_data(&TrackerCfg::default(), &gh_repo, &HashMap::new())
+                .unwrap()
+        );
+        assert_eq!(repo.is_empty, Some(true));
+        assert_eq!(
+            repo.latest_release_at,
+            Some(OffsetDateTime::parse("2025-01-02T03:04:05Z", &Rfc3339).unwrap())
+        );
+    }
+
+    #[test]
+    fn repository_update_gh_data_requires_cla() {
+        let cfg = TrackerCfg {
+            detect_cla: true,
+            ..Default::default()
+        };
+        let mut repo = Repository {
+            repository_id: *REPOSITORY_ID,
+            url: REPOSITORY_URL.to_string(),
+            ..Default::default()
+        };
+        let mut gh_repo = RepoViewRepository {
+            closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+            contributing_guide: None,
+            created_at: "1980-01-01T00:00:00Z".to_string(),
+            default_branch_ref: None,
+            description: None,
+            github_contributing_guide: Some(RepoViewRepositoryGithubContributingGuide::Blob(
+                RepoViewRepositoryGithubContributingGuideOnBlob {
+                    text: Some("All commits must include a Signed-off-by line.".to_string()),
+                },
+            )),
+            has_issues_enabled: true,
+            homepage_url: None,
+            is_empty: false,
+            is_private: false,
+            issues: RepoViewRepositoryIssues {
+                nodes: None,
+                page_info: RepoViewRepositoryIssuesPageInfo {
+                    end_cursor: None,
+                    has_next_page: false,
+                },
+            },
+            languages: None,
+            latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+            open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+            open_milestones: None,
+            pinned_issues: None,
+            primary_language: None,
+            pushed_at: None,
+            readme: None,
+            repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+            stargazer_count: 0,
+            watchers: RepoViewRepositoryWatchers { total_count: 0 },
+        };
+
+        repo.update_gh_data(&cfg, &gh_repo, &HashMap::new())
+            .unwrap();
+        assert_eq!(repo.requires_cla, Some(true));
+
+        gh_repo.contributing_guide = Some(RepoViewRepositoryContributingGuide::Blob(
+            RepoViewRepositoryContributingGuideOnBlob {
+                text: Some("Please open an issue first.".to_string()),
+            },
+        ));
+        repo.update_gh_data(&cfg, &gh_repo, &HashMap::new())
+            .unwrap();
+        assert_eq!(repo.requires_cla, Some(false));
+
+        repo.update_gh_data(&TrackerCfg::default(), &gh_repo, &HashMap::new())
+            .unwrap();
+        assert_eq!(repo.requires_cla, None);
+    }
+
+    #[test]
+    fn repository_update_gh_data_badges() {
+        let cfg = TrackerCfg {
+            parse_badges: true,
+            ..Default::default()
+        };
+        let mut repo = Repository {
+            repository_id: *REPOSITORY_ID,
+            url: REPOSITORY_URL.to_string(),
+            ..Default::default()
+        };
+        let mut gh_repo = RepoViewRepository {
+            closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+            contributing_guide: None,
+            created_at: "1980-01-01T00:00:00Z".to_string(),
+            default_branch_ref: None,
+            description: None,
+            github_contributing_guide: None,
+            has_issues_enabled: true,
+            homepage_url: None,
+            is_empty: false,
+            is_private: false,
+            issues: RepoViewRepositoryIssues {
+                nodes: None,
+                page_info: RepoViewRepositoryIssuesPageInfo {
+                    end_cursor: None,
+                    has_next_page: false,
+                },
+            },
+            languages: None,
+            latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+            open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+            open_milestones: None,
+            pinned_issues: None,
+            primary_language: None,
+            pushed_at: None,
+            readme: Some(RepoViewRepositoryReadme::Blob(
+                RepoViewRepositoryReadmeOnBlob {
+                    text: Some(
+                        "# Project\n\
+                    [![Build Status](https://ci.url/badge.svg)](https://ci.url)\n\
+                    [![ Coverage ](https://cov.url/badge.svg)](https://cov.url)\n\
+                    ![Logo](https://logo.url/logo.png)\n"
+                            .to_string(),
+                    ),
+                },
+            )),
+            repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+            stargazer_count: 0,
+            watchers: RepoViewRepositoryWatchers { total_count: 0 },
+        };
+
+        repo.update_gh_data(&cfg, &gh_repo, &HashMap::new())
+            .unwrap();
+        assert_eq!(
+            repo.badges,
+            Some(vec![
+                (
+                    "Build Status".to_string(),
+                    "https://ci.url/badge.svg".to_string()
+                ),
+                (
+                    "Coverage".to_string(),
+                    "https://cov.url/badge.svg".to_string()
+                ),
+            ])
+        );
+
+        gh_repo.readme = None;
+        repo.update_gh_data(&cfg, &gh_repo, &HashMap::new())
+            .unwrap();
+        assert_eq!(repo.badges, None);
+    }
+
+    #[test]
+    fn repository_update_gh_data_is_active() {
+        let mut repo = Repository {
+            repository_id: *REPOSITORY_ID,
+            url: REPOSITORY_URL.to_string(),
+            ..Default::default()
+        };
+        let mut gh_repo = RepoViewRepository {
+            closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+            contributing_guide: None,
+            created_at: "1980-01-01T00:00:00Z".to_string(),
+            default_branch_ref: None,
+            description: None,
+            github_contributing_guide: None,
+            has_issues_enabled: true,
+            homepage_url: None,
+            is_empty: false,
+            is_private: false,
+            issues: RepoViewRepositoryIssues {
+                nodes: Some(vec![Some(RepoViewRepositoryIssuesNodes {
+                    assignees: RepoViewRepositoryIssuesNodesAssignees { total_count: 0 },
+                    closed_by_pull_requests_references: None,
+                    comments: RepoViewRepositoryIssuesNodesComments { total_count: 0 },
+                    database_id: Some(1),
+                    id: "issue1_id".to_string(),
+                    locked: false,
+                    title: "issue1".to_string(),
+                    url: "issue1_url".to_string(),
+                    number: 1,
+                    participants: RepoViewRepositoryIssuesNodesParticipants { total_count: 0 },
+                    published_at: Some("1985-04-12T23:20:50.52Z".to_string()),
+                    reactions: RepoViewRepositoryIssuesNodesReactions { total_count: 0 },
+                    reaction_groups: None,
+                    updated_at: OffsetDateTime::now_utc().format(&Rfc3339).unwrap(),
+                    labels: None,
+                })]),
+                page_info: RepoViewRepositoryIssuesPageInfo {
+                    end_cursor: None,
+                    has_next_page: false,
+                },
+            },
+            languages: None,
+            latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+            open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+            open_milestones: None,
+            pinned_issues: None,
+            primary_language: None,
+            pushed_at: None,
+            readme: None,
+            repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+            stargazer_count: 0,
+            watchers: RepoViewRepositoryWatchers { total_count: 0 },
+        };
+
+        repo.update_gh_data(&TrackerCfg::default(), &gh_repo, &HashMap::new())
+            .unwrap();
+        assert_eq!(repo.is_active, Some(true));
+
+        let issues_activity = HashMap::from([(1, OffsetDateTime::UNIX_EPOCH)]);
+        repo.update_gh_data(&TrackerCfg::default(), &gh_repo, &issues_activity)
+            .unwrap();
+        assert_eq!(repo.is_active, Some(false));
+
+        gh_repo.issues.nodes = None;
+        repo.update_gh_data(&TrackerCfg::default(), &gh_repo, &HashMap::new())
+            .unwrap();
+        assert_eq!(repo.is_active, Some(false));
+    }
+
+    #[test]
+    fn repository_issues_reactions_breakdown() {
+        let gh_repo = RepoViewRepository {
+            closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+            contributing_guide: None,
+            created_at: "1980-01-01T00:00:00Z".to_string(),
+            default_branch_ref: None,
+            description: None,
+            github_contributing_guide: None,
+            has_issues_enabled: true,
+            homepage_url: None,
+            is_empty: false,
+            is_private: false,
+            issues: RepoViewRepositoryIssues {
+                nodes: Some(vec![Some(RepoViewRepositoryIssuesNodes {
+                    assignees: RepoViewRepositoryIssuesNodesAssignees { total_count: 0 },
+                    closed_by_pull_requests_references: None,
+                    comments: RepoViewRepositoryIssuesNodesComments { total_count: 0 },
+                    database_id: Some(1),
+                    id: "issue1_id".to_string(),
+                    locked: false,
+                    title: "issue1".to_string(),
+                    url: "issue1_url".to_string(),
+                    number: 1,
+                    participants: RepoViewRepositoryIssuesNodesParticipants { total_count: 0 },
+                    published_at: Some("1985-04-12T23:20:50.52Z".to_string()),
+                    reactions: RepoViewRepositoryIssuesNodesReactions { total_count: 0 },
+                    reaction_groups: Some(vec![
+                        RepoViewRepositoryIssuesNodesReactionGroups {
+                            content: ReactionContent::CONFUSED,
+                            reactors: RepoViewRepositoryIssuesNodesReactionGroupsReactors {
+                                total_count: 0,
+                            },
+                        },
+                        RepoViewRepositoryIssuesNodesReactionGroups {
+                            content: ReactionContent::THUMBS_UP,
+                            reactors: RepoViewRepositoryIssuesNodesReactionGroupsReactors {
+                                total_count: 3,
+                            },
+                        },
+                    ]),
+                    updated_at: "1985-04-12T23:20:50.52Z".to_string(),
+                    labels: Some(RepoViewRepositoryIssuesNodesLabels {
+                        nodes: Some(vec![]),
+                    }),
+                })]),
+                page_info: RepoViewRepositoryIssuesPageInfo {
+                    end_cursor: None,
+                    has_next_page: false,
+                },
+            },
+            languages: None,
+            latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+            open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+            open_milestones: None,
+            pinned_issues: None,
+            primary_language: None,
+            pushed_at: None,
+            readme: None,
+            repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+            stargazer_count: 0,
+            watchers: RepoViewRepositoryWatchers { total_count: 0 },
+        };
+
+        let issues = gh_repo.issues(&TrackerCfg::default(), None, &HashMap::new());
+        assert_eq!(
+            issues[0].reactions_by_type,
+            Some(vec![("thumbs_up".to_string(), 3)])
+        );
+    }
+
+    #[test]
+    fn repository_issues_participants() {
+        let gh_repo = RepoViewRepository {
+            closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+            contributing_guide: None,
+            created_at: "1980-01-01T00:00:00Z".to_string(),
+            default_branch_ref: None,
+            description: None,
+            github_contributing_guide: None,
+            has_issues_enabled: true,
+            homepage_url: None,
+            is_empty: false,
+            is_private: false,
+            issues: RepoViewRepositoryIssues {
+                nodes: Some(vec![Some(RepoViewRepositoryIssuesNodes {
+                    assignees: RepoViewRepositoryIssuesNodesAssignees { total_count: 0 },
+                    closed_by_pull_requests_references: None,
+                    comments: RepoViewRepositoryIssuesNodesComments { total_count: 0 },
+                    database_id: Some(1),
+                    id: "issue1_id".to_string(),
+                    locked: false,
+                    title: "issue1".to_string(),
+                    url: "issue1_url".to_string(),
+                    number: 1,
+                    participants: RepoViewRepositoryIssuesNodesParticipants { total_count: 4 },
+                    published_at: Some("1985-04-12T23:20:50.52Z".to_string()),
+                    reactions: RepoViewRepositoryIssuesNodesReactions { total_count: 0 },
+                    reaction_groups: None,
+                    updated_at: "1985-04-12T23:20:50.52Z".to_string(),
+                    labels: Some(RepoViewRepositoryIssuesNodesLabels {
+                        nodes: Some(vec![]),
+                    }),
+                })]),
+                page_info: RepoViewRepositoryIssuesPageInfo {
+                    end_cursor: None,
+                    has_next_page: false,
+                },
+            },
+            languages: None,
+            latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+            open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+            open_milestones: None,
+            pinned_issues: None,
+            primary_language: None,
+            pushed_at: None,
+            readme: None,
+            repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+            stargazer_count: 0,
+            watchers: RepoViewRepositoryWatchers { total_count: 0 },
+        };
+
+        let issues = gh_repo.issues(&TrackerCfg::default(), None, &HashMap::new());
+        assert_eq!(issues[0].participants, 4);
+    }
+
+    #[test]
+    fn repository_issues_pinned() {
+        let gh_repo = RepoViewRepository {
+            closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+            contributing_guide: None,
+            created_at: "1980-01-01T00:00:00Z".to_string(),
+            default_branch_ref: None,
+            description: None,
+            github_contributing_guide: None,
+            has_issues_enabled: true,
+            homepage_url: None,
+            is_empty: false,
+            is_private: false,
+            issues: RepoViewRepositoryIssues {
+                nodes: Some(vec![Some(RepoViewRepositoryIssuesNodes {
+                    assignees: RepoViewRepositoryIssuesNodesAssignees { total_count: 0 },
+                    closed_by_pull_requests_references: None,
+                    comments: RepoViewRepositoryIssuesNodesComments { total_count: 0 },
+                    database_id: Some(1),
+                    id: "issue1_id".to_string(),
+                    locked: false,
+                    title: "issue1".to_string(),
+                    url: "issue1_url".to_string(),
+                    number: 1,
+                    participants: RepoViewRepositoryIssuesNodesParticipants { total_count: 0 },
+                    published_at: Some("1985-04-12T23:20:50.52Z".to_string()),
+                    reactions: RepoViewRepositoryIssuesNodesReactions { total_count: 0 },
+                    reaction_groups: None,
+                    updated_at: "1985-04-12T23:20:50.52Z".to_string(),
+                    labels: Some(RepoViewRepositoryIssuesNodesLabels {
+                        nodes: Some(vec![]),
+                    }),
+                })]),
+                page_info: RepoViewRepositoryIssuesPageInfo {
+                    end_cursor: None,
+                    has_next_page: false,
+                },
+            },
+            languages: None,
+            latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+            open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+            open_milestones: None,
+            pinned_issues: Some(RepoViewRepositoryPinnedIssues {
+                nodes: Some(vec![Some(RepoViewRepositoryPinnedIssuesNodes {
+                    issue: RepoViewRepositoryPinnedIssuesNodesIssue {
+                        database_id: Some(1),
+                    },
+                })]),
+            }),
+            primary_language: None,
+            pushed_at: None,
+            readme: None,
+            repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+            stargazer_count: 0,
+            watchers: RepoViewRepositoryWatchers { total_count: 0 },
+        };
+
+        let issues = gh_repo.issues(&TrackerCfg::default(), None, &HashMap::new());
+        assert!(issues[0].is_pinned);
+    }
+
+    #[test]
+    fn repository_issues_linked_pr_details() {
+        let gh_repo = RepoViewRepository {
+            closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+            contributing_guide: None,
+            created_at: "1980-01-01T00:00:00Z".to_string(),
+            default_branch_ref: None,
+            description: None,
+            github_contributing_guide: None,
+            has_issues_enabled: true,
+            homepage_url: None,
+            is_empty: false,
+            is_private: false,
+            issues: RepoViewRepositoryIssues {
+                nodes: Some(vec![Some(RepoViewRepositoryIssuesNodes {
+                    assignees: RepoViewRepositoryIssuesNodesAssignees { total_count: 0 },
+                    closed_by_pull_requests_references: Some(
+                        RepoViewRepositoryIssuesNodesClosedByPullRequestsReferences {
+                            nodes: Some(vec![Some(
+                                RepoViewRepositoryIssuesNodesClosedByPullRequestsReferencesNodes {
+                                    number: 2,
+                                    state: PullRequestState::MERGED,
+                                    url: "pr2_url".to_string(),
+                                },
+                            )]),
+                        },
+                    ),
+                    comments: RepoViewRepositoryIssuesNodesComments { total_count: 0 },
+                    database_id: Some(1),
+                    id: "issue1_id".to_string(),
+                    locked: false,
+                    title: "issue1".to_string(),
+                    url: "issue1_url".to_string(),
+                    number: 1,
+                    participants: RepoViewRepositoryIssuesNodesParticipants { total_count: 0 },
+                    published_at: Some("1985-04-12T23:20:50.52Z".to_string()),
+                    reactions: RepoViewRepositoryIssuesNodesReactions { total_count: 0 },
+                    reaction_groups: None,
+                    updated_at: "1985-04-12T23:20:50.52Z".to_string(),
+                    labels: Some(RepoViewRepositoryIssuesNodesLabels {
+                        nodes: Some(vec![]),
+                    }),
+                })]),
+                page_info: RepoViewRepositoryIssuesPageInfo {
+                    end_cursor: None,
+                    has_next_page: false,
+                },
+            },
+            languages: None,
+            latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+            open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+            open_milestones: None,
+            pinned_issues: None,
+            primary_language: None,
+            pushed_at: None,
+            readme: None,
+            repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+            stargazer_count: 0,
+            watchers: RepoViewRepositoryWatchers { total_count: 0 },
+        };
+        let cfg = TrackerCfg {
+            fetch_linked_pr_details: true,
+            ..Default::default()
+        };
+
+        let issues = gh_repo.issues(&cfg, None, &HashMap::new());
+        assert!(issues[0].has_linked_prs);
+        assert_eq!(issues[0].linked_pr_url, Some("pr2_url".to_string()));
+        assert_eq!(issues[0].linked_pr_state, Some("merged".to_string()));
+
+        let issues = gh_repo.issues(&TrackerCfg::default(), None, &HashMap::new());
+        assert!(issues[0].has_linked_prs);
+        assert_eq!(issues[0].linked_pr_url, None);
+        assert_eq!(issues[0].linked_pr_state, None);
+    }
+
+    #[test]
+    fn issue_node_is_available() {
+        let mut node = RepoViewRepositoryIssuesNodes {
+            assignees: RepoViewRepositoryIssuesNodesAssignees { total_count: 0 },
+            closed_by_pull_requests_references: None,
+            comments: RepoViewRepositoryIssuesNodesComments { total_count: 0 },
+            database_id: Some(1),
+            id: "issue1_id".to_string(),
+            locked: false,
+            title: "issue1".to_string(),
+            url: "issue1_url".to_string(),
+            number: 1,
+            participants: RepoViewRepositoryIssuesNodesParticipants { total_count: 0 },
+            published_at: Some("1985-04-12T23:20:50.52Z".to_string()),
+            reactions: RepoViewRepositoryIssuesNodesReactions { total_count: 0 },
+            reaction_groups: None,
+            updated_at: "1985-04-12T23:20:50.52Z".to_string(),
+            labels: None,
+        };
+        assert!(node.is_available());
+
+        let linked_pr = |state| {
+            Some(
+                RepoViewRepositoryIssuesNodesClosedByPullRequestsReferences {
+                    nodes: Some(vec![Some(
+                        RepoViewRepositoryIssuesNodesClosedByPullRequestsReferencesNodes {
+                            number: 2,
+                            state,
+                            url: "pr2_url".to_string(),
+                        },
+                    )]),
+                },
+            )
+        };
+        node.closed_by_pull_requests_references = linked_pr(PullRequestState::CLOSED);
+        assert!(node.is_available());
+        node.closed_by_pull_requests_references = linked_pr(PullRequestState::OPEN);
+        assert!(!node.is_available());
+
+        node.closed_by_pull_requests_references = None;
+        node.locked = true;
+        assert!(!node.is_available());
+
+        node.locked = false;
+        node.assignees.total_count = 1;
+        assert!(!node.is_available());
+    }
+
+    #[test]
+    fn issue_node_needs_triage() {
+        let label = |name: &str| {
+            Some(RepoViewRepositoryIssuesNodesLabelsNodes {
+                color: "ffffff".to_string(),
+                description: None,
+                name: name.to_string(),
+            })
+        };
+        let mut node = RepoViewRepositoryIssuesNodes {
+            assignees: RepoViewRepositoryIssuesNodesAssignees { total_count: 0 },
+            closed_by_pull_requests_references: None,
+            comments: RepoViewRepositoryIssuesNodesComments { total_count: 0 },
+            database_id: Some(1),
+            id: "issue1_id".to_string(),
+            locked: false,
+            title: "issue1".to_string(),
+            url: "issue1_url".to_string(),
+            number: 1,
+            participants: RepoViewRepositoryIssuesNodesParticipants { total_count: 0 },
+            published_at: Some("1985-04-12T23:20:50.52Z".to_string()),
+            reactions: RepoViewRepositoryIssuesNodesReactions { total_count: 0 },
+            reaction_groups: None,
+            updated_at: "1985-04-12T23:20:50.52Z".to_string(),
+            labels: Some(RepoViewRepositoryIssuesNodesLabels {
+                nodes: Some(vec![label("Help Wanted")]),
+            }),
+        };
+        assert!(node.needs_triage("help wanted"));
+
+        node.assignees.total_count = 1;
+        assert!(!node.needs_triage("help wanted"));
+
+        node.assignees.total_count = 0;
+        node.labels = Some(RepoViewRepositoryIssuesNodesLabels {
+            nodes: Some(vec![label("help wanted"), label("bug")]),
+        });
+        assert!(!node.needs_triage("help wanted"));
+    }
+
+    #[test]
+    fn repository_labels_deduplicated() {
+        let label = |name: &str| {
+            Some(RepoViewRepositoryIssuesNodesLabelsNodes {
+                color: "ffffff".to_string(),
+                description: None,
+                name: name.to_string(),
+            })
+        };
+        let issue = |number: i64, labels| {
+            Some(RepoViewRepositoryIssuesNodes {
+                assignees: RepoViewRepositoryIssuesNodesAssignees { total_count: 0 },
+                closed_by_pull_requests_references: None,
+                comments: RepoViewRepositoryIssuesNodesComments { total_count: 0 },
+                database_id: Some(number),
+                id: format!("issue{number}_id"),
+                locked: false,
+                title: format!("issue{number}"),
+                url: format!("issue{number}_url"),
+                number,
+                participants: RepoViewRepositoryIssuesNodesParticipants { total_count: 0 },
+                published_at: Some("1985-04-12T23:20:50.52Z".to_string()),
+                reactions: RepoViewRepositoryIssuesNodesReactions { total_count: 0 },
+                reaction_groups: None,
+                updated_at: "1985-04-12T23:20:50.52Z".to_string(),
+                labels: Some(RepoViewRepositoryIssuesNodesLabels {
+                    nodes: Some(labels),
+                }),
+            })
+        };
+        let gh_repo = RepoViewRepository {
+            closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+            contributing_guide: None,
+            created_at: "1980-01-01T00:00:00Z".to_string(),
+            default_branch_ref: None,
+            description: None,
+            github_contributing_guide: None,
+            has_issues_enabled: true,
+            homepage_url: None,
+            is_empty: false,
+            is_private: false,
+            issues: RepoViewRepositoryIssues {
+                nodes: Some(vec![
+                    issue(1, vec![label("bug"), label("help wanted")]),
+                    issue(2, vec![label("help wanted")]),
+                ]),
+                page_info: RepoViewRepositoryIssuesPageInfo {
+                    end_cursor: None,
+                    has_next_page: false,
+                },
+            },
+            languages: None,
+            latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+            open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+            open_milestones: None,
+            pinned_issues: None,
+            primary_language: None,
+            pushed_at: None,
+            readme: None,
+            repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+            stargazer_count: 0,
+            watchers: RepoViewRepositoryWatchers { total_count: 0 },
+        };
+
+        let labels: Vec<String> = gh_repo.labels().into_iter().map(|l| l.name).collect();
+        assert_eq!(labels, vec!["bug", "help wanted"]);
+    }
+
+    #[test]
+    fn repository_update_digest() {
+        let mut repo = Repository {
+            repository_id: *REPOSITORY_ID,
+            url: REPOSITORY_URL.to_string(),
+            stars: Some(0),
+            ..Default::default()
+        };
+
+        repo.update_digest(true).unwrap();
+        assert_eq!(
+            repo.digest,
+            Some("8ddb5decab702caf72ba67a88019ee310830b250dddb1771eb9ebd1c50e2196a".to_string())
+        );
+    }
+
+    #[test]
+    fn repository_update_digest_without_stars() {
+        let mut repo = Repository {
+            repository_id: *REPOSITORY_ID,
+            url: REPOSITORY_URL.to_string(),
+            stars: Some(0),
+            ..Default::default()
+        };
+
+        repo.update_digest(false).unwrap();
+        let digest = repo.digest.clone();
+        repo.stars = Some(100);
+        repo.watch_star_ratio = Some(0.25);
+        repo.update_digest(false).unwrap();
+        assert_eq!(repo.digest, digest);
+        repo.update_digest(true).unwrap();
+        assert_ne!(repo.digest, digest);
+    }
+
+    #[test]
+    fn compute_digest_matches_buffered_encoding() {
+        let data = (
+            Some("title".to_string()),
+            vec![1, 2, 3],
+            None::<bool>,
+            7_i32,
+        );
+        let buffer = bincode::serde::encode_to_vec(&data, bincode::config::legacy()).unwrap();
+
+        assert_eq!(
+            compute_digest(&data).unwrap(),
+            hex::encode(Sha256::digest(buffer))
+        );
+    }
+
+    #[test]
+    fn issue_update_digest() {
+        let mut issue = Issue {
+            issue_id: 1,
+            title: "issue1".to_string(),
+            url: "issue1_url".to_string(),
+            number: 1,
+            labels: vec!["label1".to_string()],
+            published_at: OffsetDateTime::parse("1985-04-12T23:20:50.52Z", &Rfc3339).unwrap(),
+            has_linked_prs: false,
+            linked_pr_url: None,
+            linked_pr_state: None,
+            last_activity_at: None,
+            reactions_by_type: None,
+            participants: 0,
+            is_pinned: false,
+            is_available: None,
+            needs_triage: None,
+            first_response_seconds: None,
+            digest: None,
+            area: None,
+            kind: None,
+            difficulty: None,
+            mentor_available: None,
+            mentor: None,
+            good_first_issue: None,
+        };
+
+        issue.update_digest();
+        assert_eq!(
+            issue.digest,
+            Some("24357c4e8d21e66b5c5c66eb3e4d3b26377534487740b16b980bc7c3ed27b1ad".to_string())
+        );
+    }
+
+    #[test]
+    fn issue_prepare_ts_texts() {
+        let repo = Repository {
+            repository_id: *REPOSITORY_ID,
+            name: "repo".to_string(),
+            description: Some("description".to_string()),
+            url: REPOSITORY_URL.to_string(),
+            topics: Some(vec!["topic1".to_string(), "topic2".to_string()]),
+            languages: Some(vec!["language1".to_string()]),
+            project_name: "project".to_string(),
+            foundation_id: "foundation".to_string(),
+            ..Default::default()
+        };
+        let issue = Issue {
+            issue_id: 1,
+            title: "issue1".to_string(),
+            url: "issue1_url".to_string(),
+            number: 1,
+            labels: vec!["label1".to_string(), "label2".to_string()],
+            published_at: OffsetDateTime::parse("1985-04-12T23:20:50.52Z", &Rfc3339).unwrap(),
+            has_linked_prs: false,
+            linked_pr_url: None,
+            linked_pr_state: None,
+            last_activity_at: None,
+            reactions_by_type: None,
+            participants: 0,
+            is_pinned: false,
+            is_available: None,
+            needs_triage: None,
+            first_response_seconds: None,
+            digest: None,
+            area: None,
+            kind: None,
+            difficulty: None,
+            mentor_available: None,
+            mentor: None,
+            good_first_issue: None,
+        };
+
+        assert_eq!(
+            issue.prepare_ts_texts(&repo),
+            IssueTsTexts {
+                weight_a: "project".to_string(),
+                weight_b: "foundation repo description topic1 topic2 language1".to_string(),
+                weight_c: "issue1 label1 label2".to_string(),
+            },
+        );
+    }
+
+    #[test]
+    fn issue_populate_from_labels() {
+        let mut issue = Issue {
+            issue_id: 1,
+            title: "issue1".to_string(),
+            url: "issue1_url".to_string(),
+            number: 1,
+            labels: vec![
+                "documentation".to_string(),
+                "bug".to_string(),
+                "difficulty/medium".to_string(),
+                "mentor available".to_string(),
+                "good first issue".to_string(),
+            ],
+            published_at: OffsetDateTime::parse("1985-04-12T23:20:50.52Z", &Rfc3339).unwrap(),
+            has_linked_prs: false,
+            linked_pr_url: None,
+            linked_pr_state: None,
+            last_activity_at: None,
+            reactions_by_type: None,
+            participants: 0,
+            is_pinned: false,
+            is_available: None,
+            needs_triage: None,
+            first_response_seconds: None,
+            digest: None,
+            area: None,
+            kind: None,
+            difficulty: None,
+            mentor_available: None,
+            mentor: None,
+            good_first_issue: None,
+        };
+
+        issue.populate_from_labels(None);
+        assert_eq!(issue.area, Some(IssueArea::Docs));
+        assert_eq!(issue.kind, Some(IssueKind::Bug));
+        assert_eq!(issue.difficulty, Some(IssueDifficulty::Medium));
+        assert_eq!(issue.mentor_available, Some(true));
+        assert_eq!(issue.good_first_issue, Some(true));
+
+        issue.labels = vec!["Level:Beginner".to_string()];
+        issue.difficulty = None;
+        let difficulty_labels =
+            HashMap::from([("level:beginner".to_string(), IssueDifficulty::Easy)]);
+        issue.populate_from_labels(Some(&difficulty_labels));
+        assert_eq!(issue.difficulty, Some(IssueDifficulty::Easy));
+    }
+
+    #[test]
+    fn remove_duplicated_issues_keeps_oldest() {
+        let issue = |issue_id: i64, title: &str, published_at: &str| Issue {
+            issue_id,
+            title: title.to_string(),
+            url: format!("issue{issue_id}_url"),
+            number: 1,
+            labels: vec![],
+            published_at: OffsetDateTime::parse(published_at, &Rfc3339).unwrap(),
+            has_linked_prs: false,
+            linked_pr_url: None,
+            linked_pr_state: None,
+            last_activity_at: None,
+            reactions_by_type: None,
+            participants: 0,
+            is_pinned: false,
+            is_available: None,
+            needs_triage: None,
+            first_response_seconds: None,
+            digest: None,
+            area: None,
+            kind: None,
+            difficulty: None,
+            mentor_available: None,
+            mentor: None,
+            good_first_issue: None,
+        };
+        let mut issues = vec![
+            issue(1, "duplicated", "2022-01-02T00:00:00Z"),
+            issue(2, "unique", "2022-01-03T00:00:00Z"),
+            issue(3, "duplicated", "2022-01-01T00:00:00Z"),
+        ];
+
+        remove_duplicated_issues(&mut issues);
+        assert_eq!(
+            issues.iter().map(|i| i.issue_id).collect::<Vec<i64>>(),
+            vec![2, 3]
+        );
+    }
+
+    #[tokio::test]
+    async fn get_token_with_most_budget_picks_highest_remaining() {
+        let pool = Pool::from(vec![
+            TOKEN1.to_string(),
+            TOKEN2.to_string(),
+            "0003".to_string(),
+        ]);
+        let mut gh = MockGH::new();
+        gh.expect_remaining_budget().returning(|token| match token {
+            TOKEN1 => Some(10),
+            TOKEN2 => Some(100),
+            _ => None,
+        });
+        let gh: DynGH = Arc::new(gh);
+
+        let gh_token = get_token_with_most_budget(&pool, &gh).await;
+        assert_eq!(*gh_token, TOKEN2);
+        assert_eq!(pool.status().available, 2);
+    }
+
+    #[test]
+    fn token_labels_fall_back_to_position() {
+        let tokens = [TOKEN1.to_string(), TOKEN2.to_string()];
+        let labels = HashMap::from([(TOKEN2.to_string(), "bot".to_string())]);
+        let token_labels = TokenLabels::new(tokens.iter(), labels).unwrap();
+        assert_eq!(token_labels.get(TOKEN1), "0");
+        assert_eq!(token_labels.get(TOKEN2), "bot");
+        assert_eq!(token_labels.get("0003"), "unknown");
+    }
+
+    #[test]
+    fn token_labels_containing_token_rejected() {
+        let tokens = [TOKEN1.to_string()];
+        let labels = HashMap::from([(TOKEN1.to_string(), format!("bot-{TOKEN1}"))]);
+        assert!(TokenLabels::new(tokens.iter(), labels).is_err());
+    }
+
+    #[test]
+    fn token_labels_metrics() {
+        let tokens = [TOKEN1.to_string(), TOKEN2.to_string()];
+        let labels = HashMap::from([(TOKEN1.to_string(), "bot".to_string())]);
+        let token_labels = TokenLabels::new(tokens.iter(), labels).unwrap();
+        let mut gh = MockGH::new();
+        gh.expect_remaining_budget().returning(|token| match token {
+            TOKEN1 => Some(10),
+            _ => None,
+        });
+        let gh: DynGH = Arc::new(gh);
+
+        let metrics = token_labels.metrics(&gh);
+        assert!(metrics.contains("# TYPE clotributor_tracker_token_remaining_budget gauge\n"));
+        assert!(metrics.contains("clotributor_tracker_token_remaining_budget{token=\"bot\"} 10\n"));
+        assert!(!metrics.contains(TOKEN1));
+    }
+
+    #[test]
+    fn remove_duplicated_repositories_keeps_first() {
+        let repository = |url: &str| Repository {
+            url: url.to_string(),
+            ..Default::default()
+        };
+        let mut repositories = vec![
+            repository("https://github.com/org1/repo1"),
+            repository("https://github.com/org1/repo2"),
+            repository("https://github.com/Org1/Repo1/"),
+            repository("https://github.com/org1/repo2"),
+        ];
+
+        assert_eq!(remove_duplicated_repositories(&mut repositories), 2);
+        assert_eq!(
+            repositories
+                .iter()
+                .map(|r| r.url.as_str())
+                .collect::<Vec<&str>>(),
+            vec![
+                "https://github.com/org1/repo1",
+                "https://github.com/org1/repo2"
+            ]
+        );
+    }
+
+    #[test]
+    fn interleave_by_owner_round_robin() {
+        let repository = |url: &str| Repository {
+            url: url.to_string(),
+            ..Default::default()
+        };
+        let repositories = vec![
+            repository("https://github.com/org1/repo1"),
+            repository("https://github.com/org1/repo2"),
+            repository("https://github.com/org1/repo3"),
+            repository("https://github.com/org2/repo1"),
+            repository("https://github.com/Org3/repo1"),
+            repository("https://github.com/org3/repo2"),
+        ];
+
+        assert_eq!(
+            interleave_by_owner(repositories)
+                .iter()
+                .map(|r| r.url.as_str())
+                .collect::<Vec<&str>>(),
+            vec![
+                "https://github.com/org1/repo1",
+                "https://github.com/org2/repo1",
+                "https://github.com/Org3/repo1",
+                "https://github.com/org1/repo2",
+                "https://github.com/org3/repo2",
+                "https://github.com/org1/repo3",
+            ]
+        );
+    }
+
+    #[test]
+    fn issue_score_weights_score() {
+        let weights = IssueScoreWeights::default();
+        let zero = time::Duration::ZERO;
+        let month = time::Duration::days(30);
+
+        assert!((weights.score(zero, zero, 0, 0) - 2.0).abs() < f64::EPSILON);
+        assert!((weights.score(month, month, 0, 0) - 1.0).abs() < f64::EPSILON);
+        assert!(weights.score(month, zero, 0, 0) > weights.score(month, month, 0, 0));
+        assert!(weights.score(month, month, 10, 0) > weights.score(month, month, 1, 0));
+        assert!(weights.score(month, month, 0, 10) > weights.score(month, month, 0, 1));
+    }
+
+    #[test]
+    fn run_progress_record() {
+        let progress = RunProgress::new(Some(3));
+        progress.record(&Ok(()));
+        progress.record(&Err(format_err!(FAKE_ERROR)));
+
+        assert_eq!(progress.tracked.load(Ordering::Relaxed), 2);
+        assert_eq!(progress.failed.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn run_progress_metrics() {
+        let progress = RunProgress::new(Some(3));
+        progress.record(&Ok(()));
+        progress.record(&Err(format_err!(FAKE_ERROR)));
+
+        let metrics = progress.metrics();
+        assert!(metrics.contains(
+            "# TYPE clotributor_tracker_repositories_tracked gauge\n\
+             clotributor_tracker_repositories_tracked 2\n"
+        ));
+        assert!(metrics.contains("clotributor_tracker_repositories_failed 1\n"));
+        assert!(metrics.contains("clotributor_tracker_run_duration_seconds "));
+        assert!(metrics.contains("clotributor_tracker_last_run_timestamp_seconds "));
+    }
+
+    #[tokio::test]
+    async fn push_metrics_to_pushgateway() {
+        let mut server = mockito::Server::new_async().await;
+        let push_req = server
+            .mock("PUT", "/metrics/job/clotributor_tracker")
+            .match_body("metrics")
+            .with_status(200)
+            .create_async()
+            .await;
+
+        push_metrics(&server.url(), "metrics".to_string())
+            .await
+            .unwrap();
+        push_req.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn push_metrics_unexpected_status() {
+        let mut server = mockito::Server::new_async().await;
+        let push_req = server
+            .mock("PUT", "/metrics/job/clotributor_tracker")
+            .with_status(500)
+            .create_async()
+            .await;
+
+        let result = push_metrics(&server.url(), "metrics".to_string()).await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "unexpected status code pushing metrics: 500 Internal Server Error"
+        );
+        push_req.assert_async().await;
+    }
+
+    #[test]
+    fn issue_close_rate_undefined_without_opened_issues() {
+        assert_eq!(issue_close_rate(4, 5), Some(1.25));
+        assert_eq!(issue_close_rate(0, 5), None);
+    }
+
+    #[test]
+    fn digest_cache_matches_same_issues_and_digests() {
+        let issue = |issue_id: i64, digest: &str| Issue {
+            issue_id,
+            title: format!("issue{issue_id}"),
+            url: format!("issue{issue_id}_url"),
+            number: 1,
+            labels: vec![],
+            published_at: OffsetDateTime::now_utc(),
+            has_linked_prs: false,
+            linked_pr_url: None,
+            linked_pr_state: None,
+            last_activity_at: None,
+            reactions_by_type: None,
+            participants: 0,
+            is_pinned: false,
+            is_available: None,
+            needs_triage: None,
+            first_response_seconds: None,
+            digest: Some(digest.to_string()),
+            area: None,
+            kind: None,
+            difficulty: None,
+            mentor_available: None,
+            mentor: None,
+            good_first_issue: None,
+        };
+        let cache = DigestCache::new(HashMap::from([(
+            *REPOSITORY_ID,
+            vec![(2, "digest2".to_string()), (1, "digest1".to_string())],
+        )]));
+
+        assert!(cache.matches(*REPOSITORY_ID, &[issue(1, "digest1"), issue(2, "digest2")]));
+        assert!(!cache.matches(*REPOSITORY_ID, &[issue(1, "digest1"), issue(2, "digest3")]));
+        assert!(!cache.matches(*REPOSITORY_ID, &[issue(1, "digest1")]));
+        assert!(cache.matches(Uuid::nil(), &[]));
+        assert!(cache.size_bytes() > 0);
+        assert_eq!(cache.hits.load(Ordering::Relaxed), 2);
+        assert_eq!(cache.misses.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn digest_cache_metrics() {
+        let cache = DigestCache::new(HashMap::from([(
+            *REPOSITORY_ID,
+            vec![(1, "digest1".to_string())],
+        )]));
+        cache.matches(*REPOSITORY_ID, &[]);
+
+        let metrics = cache.metrics();
+        assert!(metrics.contains(
+            "# TYPE clotributor_tracker_digest_cache_repositories gauge\n\
+             clotributor_tracker_digest_cache_repositories 1\n"
+        ));
+        assert!(metrics.contains(&format!(
+            "clotributor_tracker_digest_cache_size_bytes {}\n",
+            cache.size_bytes()
+        )));
+        assert!(metrics.contains("clotributor_tracker_digest_cache_hits 0\n"));
+        assert!(metrics.contains("clotributor_tracker_digest_cache_misses 1\n"));
+    }
+
+    #[test]
+    fn issue_validate_lenient() {
+        let mut issue = Issue {
+            issue_id: 1,
+            title: " issue1 title ".to_string(),
+            url: "issue1_url".to_string(),
+            number: 1,
+            labels: vec![],
+            published_at: OffsetDateTime::now_utc(),
+            has_linked_prs: false,
+            linked_pr_url: None,
+            linked_pr_state: None,
+            last_activity_at: None,
+            reactions_by_type: None,
+            participants: 0,
+            is_pinned: false,
+            is_available: None,
+            needs_triage: None,
+            first_response_seconds: None,
+            digest: None,
+            area: None,
+            kind: None,
+            difficulty: None,
+            mentor_available: None,
+            mentor: None,
+            good_first_issue: None,
+        };
+        let cfg = IssueValidationCfg {
+            max_title_length: 6,
+            ..Default::default()
+        };
+        issue.validate(&cfg, None).unwrap();
+        assert_eq!(issue.title, "issue1");
+        assert!(issue.digest.is_some());
+
+        issue.title = "  ".to_string();
+        assert_eq!(
+            issue.validate(&cfg, None).unwrap_err().to_string(),
+            "empty title"
+        );
+    }
+
+    #[test]
+    fn issue_validate_strict() {
+        let cfg = IssueValidationCfg {
+            strict: true,
+            max_title_length: 6,
+            ..Default::default()
+        };
+        let valid_issue = Issue {
+            issue_id: 1,
+            title: "issue1".to_string(),
+            url: "issue1_url".to_string(),
+            number: 1,
+            labels: vec![],
+            published_at: OffsetDateTime::now_utc() - time::Duration::days(1),
+            has_linked_prs: false,
+            linked_pr_url: None,
+            linked_pr_state: None,
+            last_activity_at: None,
+            reactions_by_type: None,
+            participants: 0,
+            is_pinned: false,
+            is_available: None,
+            needs_triage: None,
+            first_response_seconds: None,
+            digest: None,
+            area: None,
+            kind: None,
+            difficulty: None,
+            mentor_available: None,
+            mentor: None,
+            good_first_issue: None,
+        };
+        valid_issue.clone().validate(&cfg, None).unwrap();
+
+        let mut issue = Issue {
+            title: "issue1 title".to_string(),
+            ..valid_issue.clone()
+        };
+        assert_eq!(
+            issue.validate(&cfg, None).unwrap_err().to_string(),
+            "title too long"
+        );
+
+        let mut issue = Issue {
+            published_at: OffsetDateTime::now_utc() + time::Duration::days(1),
+            ..valid_issue.clone()
+        };
+        assert_eq!(
+            issue.validate(&cfg, None).unwrap_err().to_string(),
+            "published in the future"
+        );
+
+        let mut issue = Issue {
+            last_activity_at: Some(valid_issue.published_at - time::Duration::days(1)),
+            ..valid_issue
+        };
+        assert_eq!(
+            issue.validate(&cfg, None).unwrap_err().to_string(),
+            "last activity before publication"
+        );
+    }
+
+    #[test]
+    fn issue_validate_timestamp_anomalies() {
+        let repo_created_at = OffsetDateTime::now_utc() - time::Duration::days(10);
+        let issue = Issue {
+            issue_id: 1,
+            title: "issue1".to_string(),
+            url: "issue1_url".to_string(),
+            number: 1,
+            labels: vec![],
+            published_at: repo_created_at - time::Duration::days(1),
+            has_linked_prs: false,
+            linked_pr_url: None,
+            linked_pr_state: None,
+            last_activity_at: None,
+            reactions_by_type: None,
+            participants: 0,
+            is_pinned: false,
+            is_available: None,
+            needs_triage: None,
+            first_response_seconds: None,
+            digest: None,
+            area: None,
+            kind: None,
+            difficulty: None,
+            mentor_available: None,
+            mentor: None,
+            good_first_issue: None,
+        };
+
+        let mut kept_issue = issue.clone();
+        kept_issue
+            .validate(&IssueValidationCfg::default(), Some(repo_created_at))
+            .unwrap();
+        assert_eq!(kept_issue.published_at, issue.published_at);
+
+        let cfg = IssueValidationCfg {
+            timestamp_anomalies: TimestampAnomalyAction::Clamp,
+            ..Default::default()
+        };
+        let mut clamped_issue = issue.clone();
+        clamped_issue.validate(&cfg, Some(repo_created_at)).unwrap();
+        assert_eq!(clamped_issue.published_at, repo_created_at);
+        assert!(clamped_issue.digest.is_some());
+
+        let cfg = IssueValidationCfg {
+            timestamp_anomalies: TimestampAnomalyAction::Reject,
+            ..Default::default()
+        };
+        assert_eq!(
+            issue
+                .clone()
+                .validate(&cfg, Some(repo_created_at))
+                .unwrap_err()
+                .to_string(),
+            "published before repository creation"
+        );
+        let mut future_issue = Issue {
+            published_at: OffsetDateTime::now_utc() + time::Duration::seconds(60),
+            ..issue
+        };
+        future_issue.validate(&cfg, Some(repo_created_at)).unwrap();
+    }
+
+    #[test]
+    fn retries_backoff_delay_respects_max_delay() {
+        let cfg = RetriesCfg {
+            max_attempts: 100,
+            base_delay_ms: 1_000,
+            max_delay_ms: 5_000,
+            ..Default::default()
+        };
+
+        let delay = cfg.backoff_delay(1);
+        assert!(delay >= Duration::from_millis(500) && delay <= Duration::from_secs(1));
+        let delay = cfg.backoff_delay(3);
+        assert!(delay >= Duration::from_secs(2) && delay <= Duration::from_secs(4));
+        for attempt in [4, 10, 64, 99] {
+            assert!(cfg.backoff_delay(attempt) <= Duration::from_secs(5));
+        }
+    }
+
+    #[tokio::test]
+    async fn track_topics_registers_topics_with_and_without_metadata() {
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_topics_to_track().times(1).returning(|| {
+            Box::pin(future::ready(Ok(vec![
+                "topic1".to_string(),
+                "topic2".to_string(),
+            ])))
+        });
+        gh.expect_topic()
+            .withf(|token, name| token == TOKEN1 && name == "topic1")
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(Some(Topic {
+                    name: "topic1".to_string(),
+                    description: Some("description".to_string()),
+                    featured: true,
+                    ..Default::default()
+                }))))
+            });
+        gh.expect_topic()
+            .withf(|token, name| token == TOKEN1 && name == "topic2")
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Ok(None))));
+        db.expect_register_topic()
+            .with(eq(Topic {
+                name: "topic1".to_string(),
+                description: Some("description".to_string()),
+                featured: true,
+                ..Default::default()
+            }))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_register_topic()
+            .with(eq(Topic {
+                name: "topic2".to_string(),
+                ..Default::default()
+            }))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+
+        track_topics(Arc::new(db), Arc::new(gh), TOKEN1)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn load_repositories_metadata_from_local_file() {
+        let repositories_metadata =
+            load_repositories_metadata(&format!("{TESTDATA_PATH}/landscape.yml"))
+                .await
+                .unwrap();
+
+        let project1_metadata = RepositoryMetadata {
+            category: "Orchestration & Management".to_string(),
+            subcategory: "Scheduling & Orchestration".to_string(),
+            project_name: "Project1".to_string(),
+        };
+        assert_eq!(
+            repositories_metadata,
+            HashMap::from([
+                (
+                    "https://github.com/org1/repo1".to_string(),
+                    project1_metadata.clone()
+                ),
+                (
+                    "https://github.com/org1/repo2".to_string(),
+                    project1_metadata
+                ),
+                (
+                    "https://github.com/org3/repo3".to_string(),
+                    RepositoryMetadata {
+                        category: "Runtime".to_string(),
+                        subcategory: "Container Runtime".to_string(),
+                        project_name: "Project3".to_string(),
+                    }
+                ),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn load_repositories_metadata_file_not_found() {
+        let result = load_repositories_metadata(&format!("{TESTDATA_PATH}/not-found.yml")).await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error reading metadata file"
+        );
+    }
+
+    #[tokio::test]
+    async fn run_repository_metadata_updated_when_metadata_file_provided() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override(
+                "tracker.metadataFile",
+                format!("{TESTDATA_PATH}/landscape.yml"),
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: ORG1_REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        db.expect_update_repository_metadata()
+            .withf(|repository_id, metadata| {
+                *repository_id == *REPOSITORY_ID
+                    && *metadata
+                        == Some(&RepositoryMetadata {
+                            category: "Orchestration & Management".to_string(),
+                            subcategory: "Scheduling & Orchestration".to_string(),
+                            project_name: "Project1".to_string(),
+                        })
+            })
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Ok(()))));
+        gh.expect_repository()
+            .times(1)
+            .returning(|_, _, _, _, _, _| Box::pin(future::ready(Err(format_err!(FAKE_ERROR)))));
+
+        let result = run(&cfg, Arc::new(db), Arc::new(gh)).await;
+        assert_eq!(result.unwrap_err().root_cause().to_string(), FAKE_ERROR);
+    }
+
+    #[tokio::test]
+    async fn run_error_getting_github_tokens() {
+        let cfg = Config::builder().build().unwrap();
+        let db = MockDB::new();
+        let gh = MockGH::new();
+
+        let result = run(&cfg, Arc::new(db), Arc::new(gh)).await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            r#"configuration property "creds.githubTokens" not found"#
+        );
+    }
+
+    #[tokio::test]
+    async fn run_empty_list_of_github_tokens_provided() {
+        let cfg = setup_test_config(&[]);
+        let db = MockDB::new();
+        let gh = MockGH::new();
+
+        let result = run(&cfg, Arc::new(db), Arc::new(gh)).await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "GitHub tokens not found in config file (creds.githubTokens)"
+        );
+    }
+
+    #[tokio::test]
+    async fn run_invalid_github_max_qps() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("github.maxQps", 0)
+            .unwrap()
+            .build()
+            .unwrap();
+        let db = MockDB::new();
+        let gh = MockGH::new();
+
+        let result = run(&cfg, Arc::new(db), Arc::new(gh)).await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "invalid GitHub max queries per second value (github.maxQps): 0"
+        );
+    }
+
+    #[tokio::test]
+    async fn run_error_getting_repositories_to_track() {
+        let cfg = setup_test_config(&[TOKEN1]);
+        let mut db = MockDB::new();
+        let gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Err(format_err!(FAKE_ERROR)))));
+
+        let result = run(&cfg, Arc::new(db), Arc::new(gh)).await;
+        assert_eq!(result.unwrap_err().to_string(), FAKE_ERROR);
+    }
+
+    #[tokio::test]
+    async fn run_no_repositories_found() {
+        let cfg = setup_test_config(&[TOKEN1]);
+        let mut db = MockDB::new();
+        let gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Ok(vec![]))));
+
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn run_untracked_repositories_disabled_when_enabled() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.autoDisableAfterDays", 90)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let gh = MockGH::new();
+
+        db.expect_disable_untracked_repositories()
+            .withf(|max_age_days, group| *max_age_days == 90 && group.is_none())
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Ok(vec![REPOSITORY_URL.to_string()]))));
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Ok(vec![]))));
+
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn run_converted_issues_unregistered_when_enabled() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.convertedIssuesMaxAgeDays", 30)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let gh = MockGH::new();
+
+        db.expect_unregister_converted_issues()
+            .withf(|max_age_days, group| *max_age_days == 30 && group.is_none())
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Ok(2))));
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Ok(vec![]))));
+
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn repair_invalid_window() {
+        let cfg = setup_test_config(&[TOKEN1]);
+        let db = MockDB::new();
+        let gh = MockGH::new();
+
+        let now = OffsetDateTime::now_utc();
+        let result = repair(
+            &cfg,
+            Arc::new(db),
+            Arc::new(gh),
+            now,
+            now - time::Duration::days(1),
+        )
+        .await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "invalid repair window: since must be before until"
+        );
+    }
+
+    #[tokio::test]
+    async fn repair_repositories_tracked_within_window() {
+        let cfg = setup_test_config(&[TOKEN1]);
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        let until = OffsetDateTime::now_utc();
+        let since = until - time::Duration::days(1);
+        db.expect_get_repositories_to_repair()
+            .withf(move |group, s, u, clock_skew_tolerance| {
+                group.is_none() && *s == since && *u == until && *clock_skew_tolerance == 0
+            })
+            .times(1)
+            .returning(|_, _, _, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .times(1)
+            .returning(|_, _, _, _, _, _| Box::pin(future::ready(Err(format_err!(FAKE_ERROR)))));
+
+        let result = repair(&cfg, Arc::new(db), Arc::new(gh), since, until).await;
+        assert_eq!(result.unwrap_err().root_cause().to_string(), FAKE_ERROR);
+    }
+
+    #[tokio::test]
+    async fn run_no_repositories_found_in_group() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.group", "group1")
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .withf(|group, _| group.is_some_and(|group| group == "group1"))
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Ok(vec![]))));
+
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn run_clock_skew_tolerance_used_getting_repositories() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.clockSkewToleranceSeconds", 120)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .withf(|group, clock_skew_tolerance| group.is_none() && *clock_skew_tolerance == 120)
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Ok(vec![]))));
+
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn run_error_getting_repository_data_from_gh() {
+        let cfg = setup_test_config(&[TOKEN1]);
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .withf(|token, repository_url, issues_filter_label, _, _, _| {
+                token == TOKEN1 && repository_url == REPOSITORY_URL && issues_filter_label.is_none()
+            })
+            .times(1)
+            .returning(|_, _, _, _, _, _| Box::pin(future::ready(Err(format_err!(FAKE_ERROR)))));
+
+        let result = run(&cfg, Arc::new(db), Arc::new(gh)).await;
+        assert_eq!(result.unwrap_err().root_cause().to_string(), FAKE_ERROR);
+    }
+
+    #[tokio::test]
+    async fn run_progress_not_logged_when_interval_is_zero() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.progressLogInterval", 0)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .times(1)
+            .returning(|_, _, _, _, _, _| Box::pin(future::ready(Err(format_err!(FAKE_ERROR)))));
+
+        let result = run(&cfg, Arc::new(db), Arc::new(gh)).await;
+        assert_eq!(result.unwrap_err().root_cause().to_string(), FAKE_ERROR);
+    }
+
+    #[tokio::test]
+    async fn run_error_getting_repository_data_from_gh_when_streaming_repositories() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.streamRepositories", true)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_stream_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                let repositories = stream::iter(vec![Ok(Repository {
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                })]);
+                Box::pin(future::ready(Ok(repositories.boxed())))
+            });
+        gh.expect_repository()
+            .times(1)
+            .returning(|_, _, _, _, _, _| Box::pin(future::ready(Err(format_err!(FAKE_ERROR)))));
+
+        let result = run(&cfg, Arc::new(db), Arc::new(gh)).await;
+        assert_eq!(result.unwrap_err().root_cause().to_string(), FAKE_ERROR);
+    }
+
+    #[tokio::test]
+    async fn run_spotlight_issues_updated_when_enabled() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.spotlight.size", 5)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .times(1)
+            .returning(|_, _, _, _, _, _| Box::pin(future::ready(Err(format_err!(FAKE_ERROR)))));
+        db.expect_update_spotlight_issues()
+            .with(eq(SpotlightCfg {
+                size: 5,
+                ..Default::default()
+            }))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+
+        let result = run(&cfg, Arc::new(db), Arc::new(gh)).await;
+        assert_eq!(result.unwrap_err().root_cause().to_string(), FAKE_ERROR);
+    }
+
+    #[tokio::test]
+    async fn run_beginner_leaderboard_updated_when_enabled() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.beginnerLeaderboard.size", 5)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .times(1)
+            .returning(|_, _, _, _, _, _| Box::pin(future::ready(Err(format_err!(FAKE_ERROR)))));
+        db.expect_update_beginner_leaderboard()
+            .with(eq(BeginnerLeaderboardCfg {
+                size: 5,
+                ..Default::default()
+            }))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+
+        let result = run(&cfg, Arc::new(db), Arc::new(gh)).await;
+        assert_eq!(result.unwrap_err().root_cause().to_string(), FAKE_ERROR);
+    }
+
+    #[tokio::test]
+    async fn run_topic_stats_updated_when_enabled() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.computeTopicStats", true)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .times(1)
+            .returning(|_, _, _, _, _, _| Box::pin(future::ready(Err(format_err!(FAKE_ERROR)))));
+        db.expect_update_topic_stats()
+            .times(1)
+            .returning(|| Box::pin(future::ready(Ok(()))));
+
+        let result = run(&cfg, Arc::new(db), Arc::new(gh)).await;
+        assert_eq!(result.unwrap_err().root_cause().to_string(), FAKE_ERROR);
+    }
+
+    #[tokio::test]
+    async fn run_org_stats_updated_when_enabled() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.computeOrgStats", true)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .times(1)
+            .returning(|_, _, _, _, _, _| Box::pin(future::ready(Err(format_err!(FAKE_ERROR)))));
+        db.expect_update_org_stats()
+            .times(1)
+            .returning(|| Box::pin(future::ready(Ok(()))));
+
+        let result = run(&cfg, Arc::new(db), Arc::new(gh)).await;
+        assert_eq!(result.unwrap_err().root_cause().to_string(), FAKE_ERROR);
+    }
+
+    #[tokio::test]
+    async fn run_closed_issues_unregistered_on_transient_error_when_enabled() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.resilientIssueSync", true)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .times(1)
+            .returning(|_, _, _, _, _, _| {
+                Box::pin(future::ready(Err(Error::new(UnexpectedStatus(
+                    StatusCode::BAD_GATEWAY,
+                )))))
+            });
+        db.expect_get_repository_issues()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| {
+                Box::pin(future::ready(Ok([1, 2]
+                    .into_iter()
+                    .map(|i| Issue {
+                        issue_id: i,
+                        title: format!("issue{i}"),
+                        url: format!("issue{i}_url"),
+                        number: i32::try_from(i).unwrap(),
+                        labels: vec![],
+                        published_at: OffsetDateTime::now_utc(),
+                        has_linked_prs: false,
+                        linked_pr_url: None,
+                        linked_pr_state: None,
+                        last_activity_at: None,
+                        reactions_by_type: None,
+                        participants: 0,
+                        is_pinned: false,
+                        is_available: None,
+                        needs_triage: None,
+                        first_response_seconds: None,
+                        digest: None,
+                        area: None,
+                        kind: None,
+                        difficulty: None,
+                        mentor_available: None,
+                        mentor: None,
+                        good_first_issue: None,
+                    })
+                    .collect())))
+            });
+        gh.expect_recently_closed_issues()
+            .withf(|token, repository_url, _, completed_only| {
+                token == TOKEN1 && repository_url == REPOSITORY_URL && !completed_only
+            })
+            .times(1)
+            .returning(|_, _, _, _| {
+                Box::pin(future::ready(Ok(vec![ClosedIssue {
+                    issue_id: 2,
+                    title: "issue2".to_string(),
+                    url: "issue2_url".to_string(),
+                    number: 2,
+                    closed_at: OffsetDateTime::UNIX_EPOCH,
+                }])))
+            });
+        db.expect_unregister_issue()
+            .with(eq(2))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+
+        let result = run(&cfg, Arc::new(db), Arc::new(gh)).await;
+        assert_eq!(
+            result.unwrap_err().root_cause().to_string(),
+            "unexpected status code: 502 Bad Gateway"
+        );
+    }
+
+    #[tokio::test]
+    async fn run_closed_issues_not_refreshed_on_non_transient_error() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.resilientIssueSync", true)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .times(1)
+            .returning(|_, _, _, _, _, _| Box::pin(future::ready(Err(format_err!(FAKE_ERROR)))));
+
+        let result = run(&cfg, Arc::new(db), Arc::new(gh)).await;
+        assert_eq!(result.unwrap_err().root_cause().to_string(), FAKE_ERROR);
+    }
+
+    #[tokio::test]
+    async fn run_repository_not_found_skipped_when_enabled() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.skipNotFoundRepositories", true)
+            .unwrap()
+            .set_override("tracker.retries.maxAttempts", 3)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .times(1)
+            .returning(|_, _, _, _, _, _| Box::pin(future::ready(Err(RepositoryNotFound.into()))));
+        db.expect_update_repository_last_track_ts()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn run_repository_tracked_with_org_sso_authorized_token() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("creds.githubOrgTokens.org1", vec![TOKEN2])
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    url: ORG1_REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .withf(|token, repository_url, _, _, _, _| {
+                token == TOKEN2 && repository_url == ORG1_REPOSITORY_URL
+            })
+            .times(1)
+            .returning(|_, _, _, _, _, _| Box::pin(future::ready(Err(format_err!(FAKE_ERROR)))));
+
+        let result = run(&cfg, Arc::new(db), Arc::new(gh)).await;
+        assert_eq!(result.unwrap_err().root_cause().to_string(), FAKE_ERROR);
+    }
+
+    #[tokio::test]
+    async fn run_repository_skipped_when_no_org_sso_authorized_token() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("creds.githubOrgTokens.org1", Vec::<String>::new())
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    url: ORG1_REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn run_only_repositories_allowed_by_url_filter_tracked() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override(
+                "tracker.urlAllowPatterns",
+                vec!["^https://github.com/org1/"],
+            )
+            .unwrap()
+            .set_override("tracker.urlBlockPatterns", vec!["/repo2$"])
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![
+                    Repository {
+                        url: REPOSITORY_URL.to_string(),
+                        ..Default::default()
+                    },
+                    Repository {
+                        url: ORG1_REPOSITORY_URL.to_string(),
+                        ..Default::default()
+                    },
+                    Repository {
+                        url: "https://github.com/org1/repo2".to_string(),
+                        ..Default::default()
+                    },
+                ])))
+            });
+        gh.expect_repository()
+            .withf(|_, repository_url, _, _, _, _| repository_url == ORG1_REPOSITORY_URL)
+            .times(1)
+            .returning(|_, _, _, _, _, _| Box::pin(future::ready(Err(format_err!(FAKE_ERROR)))));
+
+        let result = run(&cfg, Arc::new(db), Arc::new(gh)).await;
+        assert_eq!(result.unwrap_err().root_cause().to_string(), FAKE_ERROR);
+    }
+
+    #[tokio::test]
+    async fn run_error_getting_repository_data_from_gh_after_retries() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.retries.maxAttempts", 3)
+            .unwrap()
+            .set_override("tracker.retries.baseDelayMs", 0)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .times(3)
+            .returning(|_, _, _, _, _, _| Box::pin(future::ready(Err(format_err!(FAKE_ERROR)))));
+
+        let result = run(&cfg, Arc::new(db), Arc::new(gh)).await;
+        assert_eq!(result.unwrap_err().root_cause().to_string(), FAKE_ERROR);
+    }
+
+    #[tokio::test]
+    async fn run_retries_limited_by_retry_budget() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.retries.maxAttempts", 3)
+            .unwrap()
+            .set_override("tracker.retries.baseDelayMs", 0)
+            .unwrap()
+            .set_override("tracker.maxTotalRetries", 1)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .times(2)
+            .returning(|_, _, _, _, _, _| Box::pin(future::ready(Err(format_err!(FAKE_ERROR)))));
+
+        let result = run(&cfg, Arc::new(db), Arc::new(gh)).await;
+        assert_eq!(result.unwrap_err().root_cause().to_string(), FAKE_ERROR);
+    }
+
+    #[tokio::test]
+    async fn run_token_switched_on_auth_error_when_enabled() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1, TOKEN2]))
+            .set_override("tracker.retries.maxAttempts", 2)
+            .unwrap()
+            .set_override("tracker.retries.switchTokenOnAuthError", true)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+        let mut seq = Sequence::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .withf(|token, _, _, _, _, _| token == TOKEN2)
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _, _, _, _, _| Box::pin(future::ready(Err(AuthError.into()))));
+        gh.expect_repository()
+            .withf(|token, _, _, _, _, _| token == TOKEN1)
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _, _, _, _, _| Box::pin(future::ready(Err(format_err!(FAKE_ERROR)))));
+
+        let result = run(&cfg, Arc::new(db), Arc::new(gh)).await;
+        assert_eq!(result.unwrap_err().root_cause().to_string(), FAKE_ERROR);
+    }
+
+    #[tokio::test]
+    async fn run_track_failure_recorded_when_enabled() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.recordFailures", true)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .times(1)
+            .returning(|_, _, _, _, _, _| {
+                let err = Error::new(UnexpectedStatus(StatusCode::FORBIDDEN)).context(AuthError);
+                Box::pin(future::ready(Err(err)))
+            });
+        db.expect_record_track_failure()
+            .withf(|repository_id, category, status, _, _| {
+                *repository_id == *REPOSITORY_ID
+                    && *category == TrackFailureCategory::Auth
+                    && *status == Some(403)
+            })
+            .times(1)
+            .returning(|_, _, _, _, _| Box::pin(future::ready(Ok(()))));
+
+        let result = run(&cfg, Arc::new(db), Arc::new(gh)).await;
+        assert!(result.unwrap_err().is::<AuthError>());
+    }
+
+    #[tokio::test]
+    async fn track_failure_category_from_error() {
+        let err = Error::new(UnexpectedStatus(StatusCode::UNAUTHORIZED))
+            .context(AuthError)
+            .context("error fetching repository");
+        assert_eq!(
+            TrackFailureCategory::from_error(&err),
+            (TrackFailureCategory::Auth, Some(401))
+        );
+
+        let err = Error::new(UnexpectedStatus(StatusCode::TOO_MANY_REQUESTS));
+        assert_eq!(
+            TrackFailureCategory::from_error(&err),
+            (TrackFailureCategory::RateLimited, Some(429))
+        );
+
+        let err = Error::new(UnexpectedStatus(StatusCode::FORBIDDEN))
+            .context(RateLimited)
+            .context("error fetching repository");
+        assert_eq!(
+            TrackFailureCategory::from_error(&err),
+            (TrackFailureCategory::RateLimited, Some(403))
+        );
+
+        let err = Error::new(RepositoryNotFound);
+        assert_eq!(
+            TrackFailureCategory::from_error(&err),
+            (TrackFailureCategory::NotFound, None)
+        );
+
+        let elapsed = timeout(Duration::ZERO, future::pending::<()>())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            TrackFailureCategory::from_error(&Error::new(elapsed)),
+            (TrackFailureCategory::Timeout, None)
+        );
+
+        let err = format_err!(FAKE_ERROR);
+        assert_eq!(
+            TrackFailureCategory::from_error(&err),
+            (TrackFailureCategory::Other, None)
+        );
+    }
+
+    #[test]
+    fn retry_budget_exhausted() {
+        let retry_budget = RetryBudget::new(2);
+        assert!(retry_budget.acquire());
+        assert!(retry_budget.acquire());
+        assert!(!retry_budget.acquire());
+        assert!(!retry_budget.acquire());
+    }
+
+    #[test]
+    fn url_filter_allows() {
+        let allow = vec!["^https://github.com/org1/".to_string()];
+        let block = vec!["/repo2$".to_string()];
+
+        let url_filter = UrlFilter::new(None, None).unwrap();
+        assert!(url_filter.allows(REPOSITORY_URL));
+
+        let url_filter = UrlFilter::new(Some(&allow), Some(&block)).unwrap();
+        assert!(url_filter.allows(ORG1_REPOSITORY_URL));
+        assert!(!url_filter.allows(REPOSITORY_URL));
+        assert!(!url_filter.allows("https://github.com/org1/repo2"));
+
+        assert!(UrlFilter::new(Some(&vec!["(".to_string()]), None).is_err());
+    }
+
+    #[tokio::test]
+    #[allow(clippy::too_many_lines)]
+    async fn run_register_one_issue_and_unregister_another_successfully() {
+        let cfg = setup_test_config(&[TOKEN1]);
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .withf(|token, repository_url, issues_filter_label, _, _, _| {
+                token == TOKEN1 && repository_url == REPOSITORY_URL && issues_filter_label.is_none()
+            })
+            .times(1)
+            .returning(|_, _, _, _, _, _| {
+                Box::pin(future::ready(Ok(RepoViewRepository {
+                    closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+                    contributing_guide: None,
+                    created_at: "1980-01-01T00:00:00Z".to_string(),
+                    default_branch_ref: None,
+                    description: Some("description".to_string()),
+                    github_contributing_guide: None,
+                    has_issues_enabled: true,
+                    homepage_url: None,
+                    is_empty: false,
+                    is_private: false,
+                    issues: RepoViewRepositoryIssues {
+                        nodes: Some(vec![Some(RepoViewRepositoryIssuesNodes {
+                            assignees: RepoViewRepositoryIssuesNodesAssignees { total_count: 0 },
+                            closed_by_pull_requests_references: Some(
+                                RepoViewRepositoryIssuesNodesClosedByPullRequestsReferences {
+                                    nodes: Some(vec![
+                                        Some(
+                                            RepoViewRepositoryIssuesNodesClosedByPullRequestsReferencesNodes {
+                                                number: 1,
+                                                state: PullRequestState::OPEN,
+                                                url: "pr1_url".to_string(),
+                                            },
+                                        ),
+                                    ]),
+                                },
+                            ),
+                            comments: RepoViewRepositoryIssuesNodesComments { total_count: 0 },
+                            database_id: Some(1),
+                            id: "issue1_id".to_string(),
+                            locked: false,
+                            title: "issue1".to_string(),
+                            url: "issue1_url".to_string(),
+                            number: 1,
+                            participants: RepoViewRepositoryIssuesNodesParticipants { total_count: 0 },
+                            published_at: Some("1985-04-12T23:20:50.52Z".to_string()),
+                            reactions: RepoViewRepositoryIssuesNodesReactions { total_count: 0 },
+                            reaction_groups: None,
+                            updated_at: "1985-04-12T23:20:50.52Z".to_string(),
+                            labels: Some(RepoViewRepositoryIssuesNodesLabels {
+                                nodes: Some(vec![
+                                    Some(RepoViewRepositoryIssuesNodesLabelsNodes {
+                                        color: "ffffff".to_string(),
+                                        description: None,
+                                        name: "good first issue".to_string(),
+                                    }),
+                                    Some(RepoViewRepositoryIssuesNodesLabelsNodes {
+                                        color: "ffffff".to_string(),
+                                        description: None,
+                                        name: "bug".to_string(),
+                                    }),
+                                    Some(RepoViewRepositoryIssuesNodesLabelsNodes {
+                                        color: "ffffff".to_string(),
+                                        description: None,
+                                        name: "difficulty/easy".to_string(),
+                                    }),
+                                ]),
+                            }),
+                        })]),
+                        page_info: RepoViewRepositoryIssuesPageInfo {
+                            end_cursor: None,
+                            has_next_page: false,
+                        },
+                    },
+                    languages: None,
+                    latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+                    open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+                    open_milestones: None,
+                    pinned_issues: None,
+                    primary_language: None,
+                    pushed_at: None,
+                    readme: None,
+                    repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+                    stargazer_count: 0,
+                    watchers: RepoViewRepositoryWatchers { total_count: 0 },
+                })))
+            });
+        db.expect_update_repository_gh_data()
+            .with(eq(Repository {
+                repository_id: *REPOSITORY_ID,
+                url: REPOSITORY_URL.to_string(),
+                description: Some("description".to_string()),
+                stars: Some(0),
+                open_issues_total: Some(0),
+                closed_issues_total: Some(0),
+                query_version: Some(REPO_VIEW_QUERY_VERSION),
+                is_active: Some(false),
+                has_issues_enabled: Some(true),
+                open_milestones: None,
+                is_empty: Some(false),
+                latest_release_at: None,
+                owner_followers: None,
+                head_sha: None,
+                raw_response_digest: None,
+                watch_star_ratio: None,
+                badges: None,
+                issue_close_rate: None,
+                digest: Some(
+                    "e500dc5bc777e64b6e109e7a5f5896942fb34f057c76a84da01a897a0011014a".to_string(),
+                ),
+                ..Default::default()
+            }))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_get_repository_issues()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| {
+                Box::pin(future::ready(Ok(vec![Issue {
+                    issue_id: 2,
+                    title: "issue2".to_string(),
+                    url: "issue2_url".to_string(),
+                    number: 2,
+                    labels: vec![],
+                    published_at: OffsetDateTime::now_utc(),
+                    has_linked_prs: true,
+                    linked_pr_url: None,
+                    linked_pr_state: None,
+                    last_activity_at: None,
+                    reactions_by_type: None,
+                    participants: 0,
+                    is_pinned: false,
+                    is_available: None,
+                    needs_triage: None,
+                    first_response_seconds: None,
+                    digest: None,
+                    area: None,
+                    kind: None,
+                    difficulty: None,
+                    mentor_available: None,
+                    mentor: None,
+                    good_first_issue: None,
+                }])))
+            });
+        db.expect_register_issue()
+            .with(
+                eq(Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: REPOSITORY_URL.to_string(),
+                    description: Some("description".to_string()),
+                    stars: Some(0),
+                    open_issues_total: Some(0),
+                    closed_issues_total: Some(0),
+                    query_version: Some(REPO_VIEW_QUERY_VERSION),
+                    is_active: Some(false),
+                    has_issues_enabled: Some(true),
+                    open_milestones: None,
+                    is_empty: Some(false),
+                    latest_release_at: None,
+                    owner_followers: None,
+                    head_sha: None,
+                    raw_response_digest: None,
+                    watch_star_ratio: None,
+                    badges: None,
+                    issue_close_rate: None,
+                    digest: Some(
+                        "e500dc5bc777e64b6e109e7a5f5896942fb34f057c76a84da01a897a0011014a"
+                            .to_string(),
+                    ),
+                    ..Default::default()
+                }),
+                eq(Issue {
+                    issue_id: 1,
+                    title: "issue1".to_string(),
+                    url: "issue1_url".to_string(),
+                    number: 1,
+                    labels: vec![
+                        "good first issue".to_string(),
+                        "bug".to_string(),
+                        "difficulty/easy".to_string(),
+                    ],
+                    published_at: OffsetDateTime::parse("1985-04-12T23:20:50.52Z", &Rfc3339)
+                        .unwrap(),
+                    has_linked_prs: true,
+                    linked_pr_url: None,
+                    linked_pr_state: None,
+                    last_activity_at: None,
+                    reactions_by_type: None,
+                    participants: 0,
+                    is_pinned: false,
+                    is_available: None,
+                    needs_triage: None,
+                    first_response_seconds: None,
+                    digest: Some(
+                        "3ccd65dffe0dd689ab1a41e5204c4256f3ac93d8758017ec7c4fe410c6f4b836"
+                            .to_string(),
+                    ),
+                    area: None,
+                    kind: Some(IssueKind::Bug),
+                    difficulty: Some(IssueDifficulty::Easy),
+                    mentor_available: None,
+                    mentor: None,
+                    good_first_issue: Some(true),
+                }),
+            )
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Ok(()))));
+        db.expect_unregister_issue()
+            .with(eq(2))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_update_repository_last_track_ts()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[allow(clippy::too_many_lines)]
+    async fn run_issues_first_response_computed_when_enabled() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.computeFirstResponse", true)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .withf(|token, repository_url, issues_filter_label, _, _, _| {
+                token == TOKEN1 && repository_url == REPOSITORY_URL && issues_filter_label.is_none()
+            })
+            .times(1)
+            .returning(|_, _, _, _, _, _| {
+                Box::pin(future::ready(Ok(RepoViewRepository {
+                    closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+                    contributing_guide: None,
+                    created_at: "1980-01-01T00:00:00Z".to_string(),
+                    default_branch_ref: None,
+                    description: Some("description".to_string()),
+                    github_contributing_guide: None,
+                    has_issues_enabled: true,
+                    homepage_url: None,
+                    is_empty: false,
+                    is_private: false,
+                    issues: RepoViewRepositoryIssues {
+                        nodes: Some(vec![Some(RepoViewRepositoryIssuesNodes {
+                            assignees: RepoViewRepositoryIssuesNodesAssignees { total_count: 0 },
+                            closed_by_pull_requests_references: Some(
+                                RepoViewRepositoryIssuesNodesClosedByPullRequestsReferences {
+                                    nodes: Some(vec![
+                                        Some(
+                                            RepoViewRepositoryIssuesNodesClosedByPullRequestsReferencesNodes {
+                                                number: 1,
+                                                state: PullRequestState::OPEN,
+                                                url: "pr1_url".to_string(),
+                                            },
+                                        ),
+                                    ]),
+                                },
+                            ),
+                            comments: RepoViewRepositoryIssuesNodesComments { total_count: 0 },
+                            database_id: Some(1),
+                            id: "issue1_id".to_string(),
+                            locked: false,
+                            title: "issue1".to_string(),
+                            url: "issue1_url".to_string(),
+                            number: 1,
+                            participants: RepoViewRepositoryIssuesNodesParticipants { total_count: 0 },
+                            published_at: Some("1985-04-12T23:20:50.52Z".to_string()),
+                            reactions: RepoViewRepositoryIssuesNodesReactions { total_count: 0 },
+                            reaction_groups: None,
+                            updated_at: "1985-04-12T23:20:50.52Z".to_string(),
+                            labels: Some(RepoViewRepositoryIssuesNodesLabels {
+                                nodes: Some(vec![
+                                    Some(RepoViewRepositoryIssuesNodesLabelsNodes {
+                                        color: "ffffff".to_string(),
+                                        description: None,
+                                        name: "good first issue".to_string(),
+                                    }),
+                                    Some(RepoViewRepositoryIssuesNodesLabelsNodes {
+                                        color: "ffffff".to_string(),
+                                        description: None,
+                                        name: "bug".to_string(),
+                                    }),
+                                    Some(RepoViewRepositoryIssuesNodesLabelsNodes {
+                                        color: "ffffff".to_string(),
+                                        description: None,
+                                        name: "difficulty/easy".to_string(),
+                                    }),
+                                ]),
+                            }),
+                        })]),
+                        page_info: RepoViewRepositoryIssuesPageInfo {
+                            end_cursor: None,
+                            has_next_page: false,
+                        },
+                    },
+                    languages: None,
+                    latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+                    open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+                    open_milestones: None,
+                    pinned_issues: None,
+                    primary_language: None,
+                    pushed_at: None,
+                    readme: None,
+                    repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+                    stargazer_count: 0,
+                    watchers: RepoViewRepositoryWatchers { total_count: 0 },
+                })))
+            });
+        db.expect_update_repository_gh_data()
+            .with(eq(Repository {
+                repository_id: *REPOSITORY_ID,
+                url: REPOSITORY_URL.to_string(),
+                description: Some("description".to_string()),
+                stars: Some(0),
+                open_issues_total: Some(0),
+                closed_issues_total: Some(0),
+                query_version: Some(REPO_VIEW_QUERY_VERSION),
+                is_active: Some(false),
+                has_issues_enabled: Some(true),
+                open_milestones: None,
+                is_empty: Some(false),
+                latest_release_at: None,
+                owner_followers: None,
+                head_sha: None,
+                raw_response_digest: None,
+                watch_star_ratio: None,
+                badges: None,
+                issue_close_rate: None,
+                digest: Some(
+                    "e500dc5bc777e64b6e109e7a5f5896942fb34f057c76a84da01a897a0011014a".to_string(),
+                ),
+                ..Default::default()
+            }))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_get_repository_issues()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| {
+                Box::pin(future::ready(Ok(vec![Issue {
+                    issue_id: 2,
+                    title: "issue2".to_string(),
+                    url: "issue2_url".to_string(),
+                    number: 2,
+                    labels: vec![],
+                    published_at: OffsetDateTime::now_utc(),
+                    has_linked_prs: true,
+                    linked_pr_url: None,
+                    linked_pr_state: None,
+                    last_activity_at: None,
+                    reactions_by_type: None,
+                    participants: 0,
+                    is_pinned: false,
+                    is_available: None,
+                    needs_triage: None,
+                    first_response_seconds: None,
+                    digest: None,
+                    area: None,
+                    kind: None,
+                    difficulty: None,
+                    mentor_available: None,
+                    mentor: None,
+                    good_first_issue: None,
+                }])))
+            });
+        gh.expect_issues_first_response()
+            .withf(|token, ids| token == TOKEN1 && ids == ["issue1_id".to_string()])
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Ok(HashMap::from([(1, 3600)])))));
+        db.expect_register_issue()
+            .withf(|_, issue| issue.issue_id == 1 && issue.first_response_seconds == Some(3600))
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Ok(()))));
+        db.expect_unregister_issue()
+            .with(eq(2))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_update_repository_last_track_ts()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[allow(clippy::too_many_lines)]
+    async fn run_issue_labels_history_recorded_when_enabled() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.trackLabelHistory", true)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .withf(|token, repository_url, issues_filter_label, _, _, _| {
+                token == TOKEN1 && repository_url == REPOSITORY_URL && issues_filter_label.is_none()
+            })
+            .times(1)
+            .returning(|_, _, _, _, _, _| {
+                Box::pin(future::ready(Ok(RepoViewRepository {
+                    closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+                    contributing_guide: None,
+                    created_at: "1980-01-01T00:00:00Z".to_string(),
+                    default_branch_ref: None,
+                    description: Some("description".to_string()),
+                    github_contributing_guide: None,
+                    has_issues_enabled: true,
+                    homepage_url: None,
+                    is_empty: false,
+                    is_private: false,
+                    issues: RepoViewRepositoryIssues {
+                        nodes: Some(vec![Some(RepoViewRepositoryIssuesNodes {
+                            assignees: RepoViewRepositoryIssuesNodesAssignees { total_count: 0 },
+                            closed_by_pull_requests_references: Some(
+                                RepoViewRepositoryIssuesNodesClosedByPullRequestsReferences {
+                                    nodes: Some(vec![
+                                        Some(
+                                            RepoViewRepositoryIssuesNodesClosedByPullRequestsReferencesNodes {
+                                                number: 1,
+                                                state: PullRequestState::OPEN,
+                                                url: "pr1_url".to_string(),
+                                            },
+                                        ),
+                                    ]),
+                                },
+                            ),
+                            comments: RepoViewRepositoryIssuesNodesComments { total_count: 0 },
+                            database_id: Some(1),
+                            id: "issue1_id".to_string(),
+                            locked: false,
+                            title: "issue1".to_string(),
+                            url: "issue1_url".to_string(),
+                            number: 1,
+                            participants: RepoViewRepositoryIssuesNodesParticipants { total_count: 0 },
+                            published_at: Some("1985-04-12T23:20:50.52Z".to_string()),
+                            reactions: RepoViewRepositoryIssuesNodesReactions { total_count: 0 },
+                            reaction_groups: None,
+                            updated_at: "1985-04-12T23:20:50.52Z".to_string(),
+                            labels: Some(RepoViewRepositoryIssuesNodesLabels {
+                                nodes: Some(vec![
+                                    Some(RepoViewRepositoryIssuesNodesLabelsNodes {
+                                        color: "ffffff".to_string(),
+                                        description: None,
+                                        name: "good first issue".to_string(),
+                                    }),
+                                    Some(RepoViewRepositoryIssuesNodesLabelsNodes {
+                                        color: "ffffff".to_string(),
+                                        description: None,
+                                        name: "bug".to_string(),
+                                    }),
+                                    Some(RepoViewRepositoryIssuesNodesLabelsNodes {
+                                        color: "ffffff".to_string(),
+                                        description: None,
+                                        name: "difficulty/easy".to_string(),
+                                    }),
+                                ]),
+                            }),
+                        })]),
+                        page_info: RepoViewRepositoryIssuesPageInfo {
+                            end_cursor: None,
+                            has_next_page: false,
+                        },
+                    },
+                    languages: None,
+                    latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+                    open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+                    open_milestones: None,
+                    pinned_issues: None,
+                    primary_language: None,
+                    pushed_at: None,
+                    readme: None,
+                    repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+                    stargazer_count: 0,
+                    watchers: RepoViewRepositoryWatchers { total_count: 0 },
+                })))
+            });
+        db.expect_update_repository_gh_data()
+            .with(eq(Repository {
+                repository_id: *REPOSITORY_ID,
+                url: REPOSITORY_URL.to_string(),
+                description: Some("description".to_string()),
+                stars: Some(0),
+                open_issues_total: Some(0),
+                closed_issues_total: Some(0),
+                query_version: Some(REPO_VIEW_QUERY_VERSION),
+                is_active: Some(false),
+                has_issues_enabled: Some(true),
+                open_milestones: None,
+                is_empty: Some(false),
+                latest_release_at: None,
+                owner_followers: None,
+                head_sha: None,
+                raw_response_digest: None,
+                watch_star_ratio: None,
+                badges: None,
+                issue_close_rate: None,
+                digest: Some(
+                    "e500dc5bc777e64b6e109e7a5f5896942fb34f057c76a84da01a897a0011014a".to_string(),
+                ),
+                ..Default::default()
+            }))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_get_repository_issues()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| {
+                Box::pin(future::ready(Ok(vec![Issue {
+                    issue_id: 2,
+                    title: "issue2".to_string(),
+                    url: "issue2_url".to_string(),
+                    number: 2,
+                    labels: vec![],
+                    published_at: OffsetDateTime::now_utc(),
+                    has_linked_prs: true,
+                    linked_pr_url: None,
+                    linked_pr_state: None,
+                    last_activity_at: None,
+                    reactions_by_type: None,
+                    participants: 0,
+                    is_pinned: false,
+                    is_available: None,
+                    needs_triage: None,
+                    first_response_seconds: None,
+                    digest: None,
+                    area: None,
+                    kind: None,
+                    difficulty: None,
+                    mentor_available: None,
+                    mentor: None,
+                    good_first_issue: None,
+                }])))
+            });
+        db.expect_register_issue()
+            .with(
+                eq(Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: REPOSITORY_URL.to_string(),
+                    description: Some("description".to_string()),
+                    stars: Some(0),
+                    open_issues_total: Some(0),
+                    closed_issues_total: Some(0),
+                    query_version: Some(REPO_VIEW_QUERY_VERSION),
+                    is_active: Some(false),
+                    has_issues_enabled: Some(true),
+                    open_milestones: None,
+                    is_empty: Some(false),
+                    latest_release_at: None,
+                    owner_followers: None,
+                    head_sha: None,
+                    raw_response_digest: None,
+                    watch_star_ratio: None,
+                    badges: None,
+                    issue_close_rate: None,
+                    digest: Some(
+                        "e500dc5bc777e64b6e109e7a5f5896942fb34f057c76a84da01a897a0011014a"
+                            .to_string(),
+                    ),
+                    ..Default::default()
+                }),
+                eq(Issue {
+                    issue_id: 1,
+                    title: "issue1".to_string(),
+                    url: "issue1_url".to_string(),
+                    number: 1,
+                    labels: vec![
+                        "good first issue".to_string(),
+                        "bug".to_string(),
+                        "difficulty/easy".to_string(),
+                    ],
+                    published_at: OffsetDateTime::parse("1985-04-12T23:20:50.52Z", &Rfc3339)
+                        .unwrap(),
+                    has_linked_prs: true,
+                    linked_pr_url: None,
+                    linked_pr_state: None,
+                    last_activity_at: None,
+                    reactions_by_type: None,
+                    participants: 0,
+                    is_pinned: false,
+                    is_available: None,
+                    needs_triage: None,
+                    first_response_seconds: None,
+                    digest: Some(
+                        "3ccd65dffe0dd689ab1a41e5204c4256f3ac93d8758017ec7c4fe410c6f4b836"
+                            .to_string(),
+                    ),
+                    area: None,
+                    kind: Some(IssueKind::Bug),
+                    difficulty: Some(IssueDifficulty::Easy),
+                    mentor_available: None,
+                    mentor: None,
+                    good_first_issue: Some(true),
+                }),
+            )
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Ok(()))));
+        db.expect_record_issue_labels()
+            .withf(|issue_id, labels, _| {
+                *issue_id == 1
+                    && labels
+                        == [
+                            "good first issue".to_string(),
+                            "bug".to_string(),
+                            "difficulty/easy".to_string(),
+                        ]
+            })
+            .times(1)
+            .returning(|_, _, _| Box::pin(future::ready(Ok(()))));
+        db.expect_unregister_issue()
+            .with(eq(2))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_update_repository_last_track_ts()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[allow(clippy::too_many_lines)]
+    async fn run_issues_unregistrations_deferred_when_enabled() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.deferUnregistrations", true)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .withf(|token, repository_url, issues_filter_label, _, _, _| {
+                token == TOKEN1 && repository_url == REPOSITORY_URL && issues_filter_label.is_none()
+            })
+            .times(1)
+            .returning(|_, _, _, _, _, _| {
+                Box::pin(future::ready(Ok(RepoViewRepository {
+                    closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+                    contributing_guide: None,
+                    created_at: "1980-01-01T00:00:00Z".to_string(),
+                    default_branch_ref: None,
+                    description: Some("description".to_string()),
+                    github_contributing_guide: None,
+                    has_issues_enabled: true,
+                    homepage_url: None,
+                    is_empty: false,
+                    is_private: false,
+                    issues: RepoViewRepositoryIssues {
+                        nodes: Some(vec![Some(RepoViewRepositoryIssuesNodes {
+                            assignees: RepoViewRepositoryIssuesNodesAssignees { total_count: 0 },
+                            closed_by_pull_requests_references: Some(
+                                RepoViewRepositoryIssuesNodesClosedByPullRequestsReferences {
+                                    nodes: Some(vec![
+                                        Some(
+                                            RepoViewRepositoryIssuesNodesClosedByPullRequestsReferencesNodes {
+                                                number: 1,
+                                                state: PullRequestState::OPEN,
+                                                url: "pr1_url".to_string(),
+                                            },
+                                        ),
+                                    ]),
+                                },
+                            ),
+                            comments: RepoViewRepositoryIssuesNodesComments { total_count: 0 },
+                            database_id: Some(1),
+                            id: "issue1_id".to_string(),
+                            locked: false,
+                            title: "issue1".to_string(),
+                            url: "issue1_url".to_string(),
+                            number: 1,
+                            participants: RepoViewRepositoryIssuesNodesParticipants { total_count: 0 },
+                            published_at: Some("1985-04-12T23:20:50.52Z".to_string()),
+                            reactions: RepoViewRepositoryIssuesNodesReactions { total_count: 0 },
+                            reaction_groups: None,
+                            updated_at: "1985-04-12T23:20:50.52Z".to_string(),
+                            labels: Some(RepoViewRepositoryIssuesNodesLabels {
+                                nodes: Some(vec![
+                                    Some(RepoViewRepositoryIssuesNodesLabelsNodes {
+                                        color: "ffffff".to_string(),
+                                        description: None,
+                                        name: "good first issue".to_string(),
+                                    }),
+                                    Some(RepoViewRepositoryIssuesNodesLabelsNodes {
+                                        color: "ffffff".to_string(),
+                                        description: None,
+                                        name: "bug".to_string(),
+                                    }),
+                                    Some(RepoViewRepositoryIssuesNodesLabelsNodes {
+                                        color: "ffffff".to_string(),
+                                        description: None,
+                                        name: "difficulty/easy".to_string(),
+                                    }),
+                                ]),
+                            }),
+                        })]),
+                        page_info: RepoViewRepositoryIssuesPageInfo {
+                            end_cursor: None,
+                            has_next_page: false,
+                        },
+                    },
+                    languages: None,
+                    latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+                    open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+                    open_milestones: None,
+                    pinned_issues: None,
+                    primary_language: None,
+                    pushed_at: None,
+                    readme: None,
+                    repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+                    stargazer_count: 0,
+                    watchers: RepoViewRepositoryWatchers { total_count: 0 },
+                })))
+            });
+        db.expect_update_repository_gh_data()
+            .with(eq(Repository {
+                repository_id: *REPOSITORY_ID,
+                url: REPOSITORY_URL.to_string(),
+                description: Some("description".to_string()),
+                stars: Some(0),
+                open_issues_total: Some(0),
+                closed_issues_total: Some(0),
+                query_version: Some(REPO_VIEW_QUERY_VERSION),
+                is_active: Some(false),
+                has_issues_enabled: Some(true),
+                open_milestones: None,
+                is_empty: Some(false),
+                latest_release_at: None,
+                owner_followers: None,
+                head_sha: None,
+                raw_response_digest: None,
+                watch_star_ratio: None,
+                badges: None,
+                issue_close_rate: None,
+                digest: Some(
+                    "e500dc5bc777e64b6e109e7a5f5896942fb34f057c76a84da01a897a0011014a".to_string(),
+                ),
+                ..Default::default()
+            }))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_get_repository_issues()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| {
+                Box::pin(future::ready(Ok(vec![Issue {
+                    issue_id: 2,
+                    title: "issue2".to_string(),
+                    url: "issue2_url".to_string(),
+                    number: 2,
+                    labels: vec![],
+                    published_at: OffsetDateTime::now_utc(),
+                    has_linked_prs: true,
+                    linked_pr_url: None,
+                    linked_pr_state: None,
+                    last_activity_at: None,
+                    reactions_by_type: None,
+                    participants: 0,
+                    is_pinned: false,
+                    is_available: None,
+                    needs_triage: None,
+                    first_response_seconds: None,
+                    digest: None,
+                    area: None,
+                    kind: None,
+                    difficulty: None,
+                    mentor_available: None,
+                    mentor: None,
+                    good_first_issue: None,
+                }])))
+            });
+        db.expect_register_issue()
+            .with(
+                eq(Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: REPOSITORY_URL.to_string(),
+                    description: Some("description".to_string()),
+                    stars: Some(0),
+                    open_issues_total: Some(0),
+                    closed_issues_total: Some(0),
+                    query_version: Some(REPO_VIEW_QUERY_VERSION),
+                    is_active: Some(false),
+                    has_issues_enabled: Some(true),
+                    open_milestones: None,
+                    is_empty: Some(false),
+                    latest_release_at: None,
+                    owner_followers: None,
+                    head_sha: None,
+                    raw_response_digest: None,
+                    watch_star_ratio: None,
+                    badges: None,
+                    issue_close_rate: None,
+                    digest: Some(
+                        "e500dc5bc777e64b6e109e7a5f5896942fb34f057c76a84da01a897a0011014a"
+                            .to_string(),
+                    ),
+                    ..Default::default()
+                }),
+                eq(Issue {
+                    issue_id: 1,
+                    title: "issue1".to_string(),
+                    url: "issue1_url".to_string(),
+                    number: 1,
+                    labels: vec![
+                        "good first issue".to_string(),
+                        "bug".to_string(),
+                        "difficulty/easy".to_string(),
+                    ],
+                    published_at: OffsetDateTime::parse("1985-04-12T23:20:50.52Z", &Rfc3339)
+                        .unwrap(),
+                    has_linked_prs: true,
+                    linked_pr_url: None,
+                    linked_pr_state: None,
+                    last_activity_at: None,
+                    reactions_by_type: None,
+                    participants: 0,
+                    is_pinned: false,
+                    is_available: None,
+                    needs_triage: None,
+                    first_response_seconds: None,
+                    digest: Some(
+                        "3ccd65dffe0dd689ab1a41e5204c4256f3ac93d8758017ec7c4fe410c6f4b836"
+                            .to_string(),
+                    ),
+                    area: None,
+                    kind: Some(IssueKind::Bug),
+                    difficulty: Some(IssueDifficulty::Easy),
+                    mentor_available: None,
+                    mentor: None,
+                    good_first_issue: Some(true),
+                }),
+            )
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Ok(()))));
+        db.expect_unregister_issue().times(0);
+        db.expect_unregister_issues()
+            .withf(|issues_ids| issues_ids == [2])
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_update_repository_last_track_ts()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[allow(clippy::too_many_lines)]
+    async fn run_issues_cursor_persisted_when_enabled() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.persistIssuesCursor", true)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .withf(|_, _, _, issues_cursor, _, _| issues_cursor.is_none())
+            .times(1)
+            .returning(|_, _, _, _, _, _| {
+                Box::pin(future::ready(Ok(RepoViewRepository {
+                    closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+                    contributing_guide: None,
+                    created_at: "1980-01-01T00:00:00Z".to_string(),
+                    default_branch_ref: None,
+                    description: None,
+                    github_contributing_guide: None,
+                    has_issues_enabled: true,
+                    homepage_url: None,
+                    is_empty: false,
+                    is_private: false,
+                    issues: RepoViewRepositoryIssues {
+                        nodes: Some(vec![Some(RepoViewRepositoryIssuesNodes {
+                            assignees: RepoViewRepositoryIssuesNodesAssignees { total_count: 0 },
+                            closed_by_pull_requests_references: None,
+                            comments: RepoViewRepositoryIssuesNodesComments { total_count: 0 },
+                            database_id: Some(1),
+                            id: "issue1_id".to_string(),
+                            locked: false,
+                            title: "issue1".to_string(),
+                            url: "issue1_url".to_string(),
+                            number: 1,
+                            participants: RepoViewRepositoryIssuesNodesParticipants {
+                                total_count: 0,
+                            },
+                            published_at: Some("1985-04-12T23:20:50.52Z".to_string()),
+                            reactions: RepoViewRepositoryIssuesNodesReactions { total_count: 0 },
+                            reaction_groups: None,
+                            updated_at: "1985-04-12T23:20:50.52Z".to_string(),
+                            labels: Some(RepoViewRepositoryIssuesNodesLabels {
+                                nodes: Some(vec![]),
+                            }),
+                        })]),
+                        page_info: RepoViewRepositoryIssuesPageInfo {
+                            end_cursor: Some("cursor1".to_string()),
+                            has_next_page: true,
+                        },
+                    },
+                    languages: None,
+                    latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+                    open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+                    open_milestones: None,
+                    pinned_issues: None,
+                    primary_language: None,
+                    pushed_at: None,
+                    readme: None,
+                    repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+                    stargazer_count: 0,
+                    watchers: RepoViewRepositoryWatchers { total_count: 0 },
+                })))
+            });
+        db.expect_update_repository_gh_data()
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_get_repository_issues()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| {
+                let issue = Issue {
+                    issue_id: 2,
+                    title: "issue2".to_string(),
+                    url: "issue2_url".to_string(),
+                    number: 2,
+                    labels: vec![],
+                    published_at: OffsetDateTime::now_utc(),
+                    has_linked_prs: false,
+                    linked_pr_url: None,
+                    linked_pr_state: None,
+                    last_activity_at: None,
+                    reactions_by_type: None,
+                    participants: 0,
+                    is_pinned: false,
+                    is_available: None,
+                    needs_triage: None,
+                    first_response_seconds: None,
+                    digest: Some("digest2".to_string()),
+                    area: None,
+                    kind: None,
+                    difficulty: None,
+                    mentor_available: None,
+                    mentor: None,
+                    good_first_issue: None,
+                };
+                Box::pin(future::ready(Ok(vec![
+                    issue.clone(),
+                    Issue {
+                        issue_id: 3,
+                        title: "issue3".to_string(),
+                        url: "issue3_url".to_string(),
+                        number: 3,
+                        published_at: OffsetDateTime::parse("1980-01-01T00:00:00Z", &Rfc3339)
+                            .unwrap(),
+                        digest: Some("digest3".to_string()),
+                        ..issue
+                    },
+                ])))
+            });
+        db.expect_register_issue()
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Ok(()))));
+        db.expect_unregister_issue()
+            .with(eq(2))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_update_repository_issues_cursor()
+            .withf(|repository_id, issues_cursor| {
+                *repository_id == *REPOSITORY_ID
+                    && issues_cursor.is_some_and(|issues_cursor| issues_cursor == "cursor1")
+            })
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Ok(()))));
+        db.expect_update_repository_last_track_ts()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[allow(clippy::too_many_lines)]
+    async fn run_issues_page_size_adapted_when_enabled() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.adaptiveIssuesPageSize.highCost", 2)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: REPOSITORY_URL.to_string(),
+                    issues_page_size: Some(20),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .withf(|_, _, _, _, issues_page_size, _| *issues_page_size == 20)
+            .times(1)
+            .returning(|_, _, _, _, _, _| {
+                Box::pin(future::ready(Ok(RepoViewRepository {
+                    closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+                    contributing_guide: None,
+                    created_at: "1980-01-01T00:00:00Z".to_string(),
+                    default_branch_ref: None,
+                    description: None,
+                    github_contributing_guide: None,
+                    has_issues_enabled: true,
+                    homepage_url: None,
+                    is_empty: false,
+                    is_private: false,
+                    issues: RepoViewRepositoryIssues {
+                        nodes: Some(vec![Some(RepoViewRepositoryIssuesNodes {
+                            assignees: RepoViewRepositoryIssuesNodesAssignees { total_count: 0 },
+                            closed_by_pull_requests_references: None,
+                            comments: RepoViewRepositoryIssuesNodesComments { total_count: 0 },
+                            database_id: Some(1),
+                            id: "issue1_id".to_string(),
+                            locked: false,
+                            title: "issue1".to_string(),
+                            url: "issue1_url".to_string(),
+                            number: 1,
+                            participants: RepoViewRepositoryIssuesNodesParticipants {
+                                total_count: 0,
+                            },
+                            published_at: Some("1985-04-12T23:20:50.52Z".to_string()),
+                            reactions: RepoViewRepositoryIssuesNodesReactions { total_count: 0 },
+                            reaction_groups: None,
+                            updated_at: "1985-04-12T23:20:50.52Z".to_string(),
+                            labels: Some(RepoViewRepositoryIssuesNodesLabels {
+                                nodes: Some(vec![]),
+                            }),
+                        })]),
+                        page_info: RepoViewRepositoryIssuesPageInfo {
+                            end_cursor: None,
+                            has_next_page: false,
+                        },
+                    },
+                    languages: None,
+                    latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+                    open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+                    open_milestones: None,
+                    pinned_issues: None,
+                    primary_language: None,
+                    pushed_at: None,
+                    readme: None,
+                    repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+                    stargazer_count: 0,
+                    watchers: RepoViewRepositoryWatchers { total_count: 0 },
+                })))
+            });
+        gh.expect_last_query_cost()
+            .with(eq(TOKEN1))
+            .times(1)
+            .returning(|_| Some(3));
+        db.expect_update_repository_issues_page_size()
+            .with(eq(*REPOSITORY_ID), eq(10))
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Ok(()))));
+        db.expect_update_repository_gh_data()
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_get_repository_issues()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(vec![]))));
+        db.expect_register_issue()
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Ok(()))));
+        db.expect_update_repository_last_track_ts()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[allow(clippy::too_many_lines)]
+    async fn run_issues_unregistered_only_for_reasons_configured() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.unregisterReasons", vec!["closed", "deleted"])
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .times(1)
+            .returning(|_, _, _, _, _, _| {
+                Box::pin(future::ready(Ok(RepoViewRepository {
+                    closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+                    contributing_guide: None,
+                    created_at: "1980-01-01T00:00:00Z".to_string(),
+                    default_branch_ref: None,
+                    description: None,
+                    github_contributing_guide: None,
+                    has_issues_enabled: true,
+                    homepage_url: None,
+                    is_empty: false,
+                    is_private: false,
+                    issues: RepoViewRepositoryIssues {
+                        nodes: None,
+                        page_info: RepoViewRepositoryIssuesPageInfo {
+                            end_cursor: None,
+                            has_next_page: false,
+                        },
+                    },
+                    languages: None,
+                    latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+                    open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+                    open_milestones: None,
+                    pinned_issues: None,
+                    primary_language: None,
+                    pushed_at: None,
+                    readme: None,
+                    repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+                    stargazer_count: 0,
+                    watchers: RepoViewRepositoryWatchers { total_count: 0 },
+                })))
+            });
+        db.expect_update_repository_gh_data()
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_get_repository_issues()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| {
+                let issue = Issue {
+                    issue_id: 1,
+                    title: "issue1".to_string(),
+                    url: "issue1_url".to_string(),
+                    number: 1,
+                    labels: vec![],
+                    published_at: OffsetDateTime::now_utc(),
+                    has_linked_prs: false,
+                    linked_pr_url: None,
+                    linked_pr_state: None,
+                    last_activity_at: None,
+                    reactions_by_type: None,
+                    participants: 0,
+                    is_pinned: false,
+                    is_available: None,
+                    needs_triage: None,
+                    first_response_seconds: None,
+                    digest: Some("digest1".to_string()),
+                    area: None,
+                    kind: None,
+                    difficulty: None,
+                    mentor_available: None,
+                    mentor: None,
+                    good_first_issue: None,
+                };
+                Box::pin(future::ready(Ok(vec![
+                    issue.clone(),
+                    Issue {
+                        issue_id: 2,
+                        number: 2,
+                        ..issue
+                    },
+                ])))
+            });
+        gh.expect_issue_absence_reason()
+            .times(2)
+            .returning(|_, _, number| {
+                let reason = if number == 1 {
+                    IssueAbsenceReason::Closed
+                } else {
+                    IssueAbsenceReason::Open
+                };
+                Box::pin(future::ready(Ok(reason)))
+            });
+        db.expect_unregister_issue()
+            .with(eq(1))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_update_repository_last_track_ts()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn run_repository_visibility_updated_when_it_became_public() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.detectPublicTransitions", true)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: REPOSITORY_URL.to_string(),
+                    is_private: Some(true),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .times(1)
+            .returning(|_, _, _, _, _, _| {
+                Box::pin(future::ready(Ok(RepoViewRepository {
+                    closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+                    contributing_guide: None,
+                    created_at: "1980-01-01T00:00:00Z".to_string(),
+                    default_branch_ref: None,
+                    description: None,
+                    github_contributing_guide: None,
+                    has_issues_enabled: true,
+                    homepage_url: None,
+                    is_empty: false,
+                    is_private: false,
+                    issues: RepoViewRepositoryIssues {
+                        nodes: None,
+                        page_info: RepoViewRepositoryIssuesPageInfo {
+                            end_cursor: None,
+                            has_next_page: false,
+                        },
+                    },
+                    languages: None,
+                    latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+                    open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+                    open_milestones: None,
+                    pinned_issues: None,
+                    primary_language: None,
+                    pushed_at: None,
+                    readme: None,
+                    repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+                    stargazer_count: 0,
+                    watchers: RepoViewRepositoryWatchers { total_count: 0 },
+                })))
+            });
+        db.expect_has_column()
+            .withf(|table, column| table == "repository" && column == "is_private")
+            .returning(|_, _| true);
+        db.expect_update_repository_visibility()
+            .with(eq(*REPOSITORY_ID), eq(false))
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Ok(()))));
+        db.expect_update_repository_gh_data()
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_get_repository_issues()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(vec![]))));
+        db.expect_update_repository_last_track_ts()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn run_repository_visibility_not_tracked_without_column() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.detectPublicTransitions", true)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .times(1)
+            .returning(|_, _, _, _, _, _| {
+                Box::pin(future::ready(Ok(RepoViewRepository {
+                    closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+                    contributing_guide: None,
+                    created_at: "1980-01-01T00:00:00Z".to_string(),
+                    default_branch_ref: None,
+                    description: None,
+                    github_contributing_guide: None,
+                    has_issues_enabled: true,
+                    homepage_url: None,
+                    is_empty: false,
+                    is_private: false,
+                    issues: RepoViewRepositoryIssues {
+                        nodes: None,
+                        page_info: RepoViewRepositoryIssuesPageInfo {
+                            end_cursor: None,
+                            has_next_page: false,
+                        },
+                    },
+                    languages: None,
+                    latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+                    open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+                    open_milestones: None,
+                    pinned_issues: None,
+                    primary_language: None,
+                    pushed_at: None,
+                    readme: None,
+                    repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+                    stargazer_count: 0,
+                    watchers: RepoViewRepositoryWatchers { total_count: 0 },
+                })))
+            });
+        db.expect_has_column()
+            .withf(|table, column| table == "repository" && column == "is_private")
+            .returning(|_, _| false);
+        db.expect_update_repository_gh_data()
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_get_repository_issues()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(vec![]))));
+        db.expect_update_repository_last_track_ts()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn run_issue_close_rate_updated_when_enabled() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.issueCloseRateWindowDays", 90)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .times(1)
+            .returning(|_, _, _, _, _, _| {
+                Box::pin(future::ready(Ok(RepoViewRepository {
+                    closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+                    contributing_guide: None,
+                    created_at: "1980-01-01T00:00:00Z".to_string(),
+                    default_branch_ref: None,
+                    description: None,
+                    github_contributing_guide: None,
+                    has_issues_enabled: true,
+                    homepage_url: None,
+                    is_empty: false,
+                    is_private: false,
+                    issues: RepoViewRepositoryIssues {
+                        nodes: None,
+                        page_info: RepoViewRepositoryIssuesPageInfo {
+                            end_cursor: None,
+                            has_next_page: false,
+                        },
+                    },
+                    languages: None,
+                    latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+                    open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+                    open_milestones: None,
+                    pinned_issues: None,
+                    primary_language: None,
+                    pushed_at: None,
+                    readme: None,
+                    repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+                    stargazer_count: 0,
+                    watchers: RepoViewRepositoryWatchers { total_count: 0 },
+                })))
+            });
+        db.expect_update_repository_gh_data()
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_get_repository_issues()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(vec![]))));
+        gh.expect_issues_opened_and_closed()
+            .withf(|token, repository_url, _| token == TOKEN1 && repository_url == REPOSITORY_URL)
+            .times(1)
+            .returning(|_, _, _| Box::pin(future::ready(Ok((8, 6)))));
+        db.expect_update_repository_issue_close_rate()
+            .with(eq(*REPOSITORY_ID), eq(Some(0.75)))
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Ok(()))));
+        db.expect_update_repository_last_track_ts()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn run_issues_sync_skipped_when_unchanged_in_digest_cache() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.digestCache", true)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_issues_digests()
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(HashMap::new()))));
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .times(1)
+            .returning(|_, _, _, _, _, _| {
+                Box::pin(future::ready(Ok(RepoViewRepository {
+                    closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+                    contributing_guide: None,
+                    created_at: "1980-01-01T00:00:00Z".to_string(),
+                    default_branch_ref: None,
+                    description: None,
+                    github_contributing_guide: None,
+                    has_issues_enabled: true,
+                    homepage_url: None,
+                    is_empty: false,
+                    is_private: false,
+                    issues: RepoViewRepositoryIssues {
+                        nodes: None,
+                        page_info: RepoViewRepositoryIssuesPageInfo {
+                            end_cursor: None,
+                            has_next_page: false,
+                        },
+                    },
+                    languages: None,
+                    latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+                    open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+                    open_milestones: None,
+                    pinned_issues: None,
+                    primary_language: None,
+                    pushed_at: None,
+                    readme: None,
+                    repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+                    stargazer_count: 0,
+                    watchers: RepoViewRepositoryWatchers { total_count: 0 },
+                })))
+            });
+        db.expect_update_repository_gh_data()
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_update_repository_last_track_ts()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[allow(clippy::too_many_lines)]
+    async fn run_issues_converted_to_discussions_flagged_when_enabled() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.trackDiscussionConversions", true)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .times(1)
+            .returning(|_, _, _, _, _, _| {
+                Box::pin(future::ready(Ok(RepoViewRepository {
+                    closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+                    contributing_guide: None,
+                    created_at: "1980-01-01T00:00:00Z".to_string(),
+                    default_branch_ref: None,
+                    description: None,
+                    github_contributing_guide: None,
+                    has_issues_enabled: true,
+                    homepage_url: None,
+                    is_empty: false,
+                    is_private: false,
+                    issues: RepoViewRepositoryIssues {
+                        nodes: None,
+                        page_info: RepoViewRepositoryIssuesPageInfo {
+                            end_cursor: None,
+                            has_next_page: false,
+                        },
+                    },
+                    languages: None,
+                    latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+                    open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+                    open_milestones: None,
+                    pinned_issues: None,
+                    primary_language: None,
+                    pushed_at: None,
+                    readme: None,
+                    repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+                    stargazer_count: 0,
+                    watchers: RepoViewRepositoryWatchers { total_count: 0 },
+                })))
+            });
+        db.expect_update_repository_gh_data()
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_get_repository_issues()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| {
+                let issue = Issue {
+                    issue_id: 1,
+                    title: "issue1".to_string(),
+                    url: "issue1_url".to_string(),
+                    number: 1,
+                    labels: vec![],
+                    published_at: OffsetDateTime::now_utc(),
+                    has_linked_prs: false,
+                    linked_pr_url: None,
+                    linked_pr_state: None,
+                    last_activity_at: None,
+                    reactions_by_type: None,
+                    participants: 0,
+                    is_pinned: false,
+                    is_available: None,
+                    needs_triage: None,
+                    first_response_seconds: None,
+                    digest: Some("digest1".to_string()),
+                    area: None,
+                    kind: None,
+                    difficulty: None,
+                    mentor_available: None,
+                    mentor: None,
+                    good_first_issue: None,
+                };
+                Box::pin(future::ready(Ok(vec![
+                    issue.clone(),
+                    Issue {
+                        issue_id: 2,
+                        number: 2,
+                        ..issue
+                    },
+                ])))
+            });
+        db.expect_has_column()
+            .withf(|table, column| table == "issue" && column == "converted_to_discussion_url")
+            .returning(|_, _| true);
+        gh.expect_issue_discussion_url()
+            .times(2)
+            .returning(|_, _, number| {
+                let discussion_url = (number == 2).then(|| "discussion2_url".to_string());
+                Box::pin(future::ready(Ok(discussion_url)))
+            });
+        db.expect_flag_issue_converted_to_discussion()
+            .withf(|issue_id, discussion_url| *issue_id == 2 && discussion_url == "discussion2_url")
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Ok(()))));
+        db.expect_unregister_issue()
+            .with(eq(1))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_update_repository_last_track_ts()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[allow(clippy::too_many_lines)]
+    async fn run_issues_converted_to_discussions_unregistered_without_column() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.trackDiscussionConversions", true)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .times(1)
+            .returning(|_, _, _, _, _, _| {
+                Box::pin(future::ready(Ok(RepoViewRepository {
+                    closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+                    contributing_guide: None,
+                    created_at: "1980-01-01T00:00:00Z".to_string(),
+                    default_branch_ref: None,
+                    description: None,
+                    github_contributing_guide: None,
+                    has_issues_enabled: true,
+                    homepage_url: None,
+                    is_empty: false,
+                    is_private: false,
+                    issues: RepoViewRepositoryIssues {
+                        nodes: None,
+                        page_info: RepoViewRepositoryIssuesPageInfo {
+                            end_cursor: None,
+                            has_next_page: false,
+                        },
+                    },
+                    languages: None,
+                    latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+                    open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+                    open_milestones: None,
+                    pinned_issues: None,
+                    primary_language: None,
+                    pushed_at: None,
+                    readme: None,
+                    repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+                    stargazer_count: 0,
+                    watchers: RepoViewRepositoryWatchers { total_count: 0 },
+                })))
+            });
+        db.expect_update_repository_gh_data()
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_get_repository_issues()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| {
+                let issue = Issue {
+                    issue_id: 1,
+                    title: "issue1".to_string(),
+                    url: "issue1_url".to_string(),
+                    number: 1,
+                    labels: vec![],
+                    published_at: OffsetDateTime::now_utc(),
+                    has_linked_prs: false,
+                    linked_pr_url: None,
+                    linked_pr_state: None,
+                    last_activity_at: None,
+                    reactions_by_type: None,
+                    participants: 0,
+                    is_pinned: false,
+                    is_available: None,
+                    needs_triage: None,
+                    first_response_seconds: None,
+                    digest: Some("digest1".to_string()),
+                    area: None,
+                    kind: None,
+                    difficulty: None,
+                    mentor_available: None,
+                    mentor: None,
+                    good_first_issue: None,
+                };
+                Box::pin(future::ready(Ok(vec![
+                    issue.clone(),
+                    Issue {
+                        issue_id: 2,
+                        number: 2,
+                        ..issue
+                    },
+                ])))
+            });
+        db.expect_has_column()
+            .withf(|table, column| table == "issue" && column == "converted_to_discussion_url")
+            .returning(|_, _| false);
+        db.expect_unregister_issue()
+            .times(2)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_update_repository_last_track_ts()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn run_contributors_fetched_when_enabled() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.fetchContributors", true)
+            .unwrap()
+            .set_override("tracker.topContributorsCount", 5)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .times(1)
+            .returning(|_, _, _, _, _, _| {
+                Box::pin(future::ready(Ok(RepoViewRepository {
+                    closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+                    contributing_guide: None,
+                    created_at: "1980-01-01T00:00:00Z".to_string(),
+                    default_branch_ref: None,
+                    description: None,
+                    github_contributing_guide: None,
+                    has_issues_enabled: true,
+                    homepage_url: None,
+                    is_empty: false,
+                    is_private: false,
+                    issues: RepoViewRepositoryIssues {
+                        nodes: None,
+                        page_info: RepoViewRepositoryIssuesPageInfo {
+                            end_cursor: None,
+                            has_next_page: false,
+                        },
+                    },
+                    languages: None,
+                    latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+                    open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+                    open_milestones: None,
+                    pinned_issues: None,
+                    primary_language: None,
+                    pushed_at: None,
+                    readme: None,
+                    repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+                    stargazer_count: 0,
+                    watchers: RepoViewRepositoryWatchers { total_count: 0 },
+                })))
+            });
+        gh.expect_contributors()
+            .withf(|token, repository_url, limit| {
+                token == TOKEN1 && repository_url == REPOSITORY_URL && *limit == 5
+            })
+            .times(1)
+            .returning(|_, _, _| Box::pin(future::ready(Ok(vec![("user1".to_string(), 5)]))));
+        db.expect_update_repository_gh_data()
+            .withf(|repository| repository.top_contributors == Some(vec![("user1".to_string(), 5)]))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_get_repository_issues()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(vec![]))));
+        db.expect_update_repository_last_track_ts()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn run_commit_activity_fetched_when_enabled() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.fetchCommitActivity", true)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .times(1)
+            .returning(|_, _, _, _, _, _| {
+                Box::pin(future::ready(Ok(RepoViewRepository {
+                    closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+                    contributing_guide: None,
+                    created_at: "1980-01-01T00:00:00Z".to_string(),
+                    default_branch_ref: None,
+                    description: None,
+                    github_contributing_guide: None,
+                    has_issues_enabled: true,
+                    homepage_url: None,
+                    is_empty: false,
+                    is_private: false,
+                    issues: RepoViewRepositoryIssues {
+                        nodes: None,
+                        page_info: RepoViewRepositoryIssuesPageInfo {
+                            end_cursor: None,
+                            has_next_page: false,
+                        },
+                    },
+                    languages: None,
+                    latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+                    open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+                    open_milestones: None,
+                    pinned_issues: None,
+                    primary_language: None,
+                    pushed_at: None,
+                    readme: None,
+                    repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+                    stargazer_count: 0,
+                    watchers: RepoViewRepositoryWatchers { total_count: 0 },
+                })))
+            });
+        gh.expect_commit_activity()
+            .withf(|token, repository_url| token == TOKEN1 && repository_url == REPOSITORY_URL)
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Ok(Some(vec![3, 0, 7])))));
+        db.expect_update_repository_gh_data()
+            .withf(|repository| repository.commit_activity == Some(vec![3, 0, 7]))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_get_repository_issues()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(vec![]))));
+        db.expect_update_repository_last_track_ts()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
+    }
 
-        repo.update_digest().unwrap();
-        assert_eq!(
-            repo.digest,
-            Some("cdb032de4c6cb506da0606e0934e69ad1ae64773ffaa76f9d6e28192067c43cf".to_string())
-        );
+    #[tokio::test]
+    async fn run_owner_followers_fetched_when_enabled() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.fetchOwnerFollowers", true)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .times(1)
+            .returning(|_, _, _, _, _, _| {
+                Box::pin(future::ready(Ok(RepoViewRepository {
+                    closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+                    contributing_guide: None,
+                    created_at: "1980-01-01T00:00:00Z".to_string(),
+                    default_branch_ref: None,
+                    description: None,
+                    github_contributing_guide: None,
+                    has_issues_enabled: true,
+                    homepage_url: None,
+                    is_empty: false,
+                    is_private: false,
+                    issues: RepoViewRepositoryIssues {
+                        nodes: None,
+                        page_info: RepoViewRepositoryIssuesPageInfo {
+                            end_cursor: None,
+                            has_next_page: false,
+                        },
+                    },
+                    languages: None,
+                    latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+                    open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+                    open_milestones: None,
+                    pinned_issues: None,
+                    primary_language: None,
+                    pushed_at: None,
+                    readme: None,
+                    repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+                    stargazer_count: 0,
+                    watchers: RepoViewRepositoryWatchers { total_count: 0 },
+                })))
+            });
+        gh.expect_owner_followers()
+            .withf(|token, repository_url| token == TOKEN1 && repository_url == REPOSITORY_URL)
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Ok(42))));
+        db.expect_update_repository_gh_data()
+            .withf(|repository| repository.owner_followers == Some(42))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_get_repository_issues()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(vec![]))));
+        db.expect_update_repository_last_track_ts()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
     }
 
-    #[test]
-    fn issue_update_digest() {
-        let mut issue = Issue {
-            issue_id: 1,
-            title: "issue1".to_string(),
-            url: "issue1_url".to_string(),
-            number: 1,
-            labels: vec!["label1".to_string()],
-            published_at: OffsetDateTime::parse("1985-04-12T23:20:50.52Z", &Rfc3339).unwrap(),
-            has_linked_prs: false,
-            digest: None,
-            area: None,
-            kind: None,
-            difficulty: None,
-            mentor_available: None,
-            mentor: None,
-            good_first_issue: None,
-        };
+    #[tokio::test]
+    async fn run_dormant_repository_issues_sync_skipped_when_enabled() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.dormantRepoCutoffDays", 365)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
 
-        issue.update_digest();
-        assert_eq!(
-            issue.digest,
-            Some("bfd1f875bce09b3edc4adc1553431e887ae70f429d549cbd746adc722243aafd".to_string())
-        );
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .times(1)
+            .returning(|_, _, _, _, _, _| {
+                Box::pin(future::ready(Ok(RepoViewRepository {
+                    closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+                    contributing_guide: None,
+                    created_at: "1980-01-01T00:00:00Z".to_string(),
+                    default_branch_ref: None,
+                    description: None,
+                    github_contributing_guide: None,
+                    has_issues_enabled: true,
+                    homepage_url: None,
+                    is_empty: false,
+                    is_private: false,
+                    issues: RepoViewRepositoryIssues {
+                        nodes: None,
+                        page_info: RepoViewRepositoryIssuesPageInfo {
+                            end_cursor: None,
+                            has_next_page: false,
+                        },
+                    },
+                    languages: None,
+                    latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+                    open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+                    open_milestones: None,
+                    pinned_issues: None,
+                    primary_language: None,
+                    pushed_at: Some("1985-04-12T23:20:50.52Z".to_string()),
+                    readme: None,
+                    repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+                    stargazer_count: 0,
+                    watchers: RepoViewRepositoryWatchers { total_count: 0 },
+                })))
+            });
+        db.expect_update_repository_gh_data()
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_get_repository_issues().times(0);
+        db.expect_update_repository_last_track_ts()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
     }
 
-    #[test]
-    fn issue_prepare_ts_texts() {
-        let repo = Repository {
-            repository_id: *REPOSITORY_ID,
-            name: "repo".to_string(),
-            description: Some("description".to_string()),
-            url: REPOSITORY_URL.to_string(),
-            topics: Some(vec!["topic1".to_string(), "topic2".to_string()]),
-            languages: Some(vec!["language1".to_string()]),
-            project_name: "project".to_string(),
-            foundation_id: "foundation".to_string(),
-            ..Default::default()
-        };
-        let issue = Issue {
-            issue_id: 1,
-            title: "issue1".to_string(),
-            url: "issue1_url".to_string(),
-            number: 1,
-            labels: vec!["label1".to_string(), "label2".to_string()],
-            published_at: OffsetDateTime::parse("1985-04-12T23:20:50.52Z", &Rfc3339).unwrap(),
-            has_linked_prs: false,
-            digest: None,
-            area: None,
-            kind: None,
-            difficulty: None,
-            mentor_available: None,
-            mentor: None,
-            good_first_issue: None,
-        };
+    #[tokio::test]
+    #[allow(clippy::too_many_lines)]
+    async fn run_repositories_updated_in_batches_when_enabled() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.repositoriesBatchSize", 2)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
 
-        assert_eq!(
-            issue.prepare_ts_texts(&repo),
-            IssueTsTexts {
-                weight_a: "project".to_string(),
-                weight_b: "foundation repo description topic1 topic2 language1".to_string(),
-                weight_c: "issue1 label1 label2".to_string(),
-            },
-        );
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok((1..=3)
+                    .map(|i| Repository {
+                        repository_id: Uuid::from_u128(i),
+                        url: format!("https://github.com/org/repo{i}"),
+                        ..Default::default()
+                    })
+                    .collect())))
+            });
+        gh.expect_repository()
+            .times(3)
+            .returning(|_, _, _, _, _, _| {
+                Box::pin(future::ready(Ok(RepoViewRepository {
+                    closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+                    contributing_guide: None,
+                    created_at: "1980-01-01T00:00:00Z".to_string(),
+                    default_branch_ref: None,
+                    description: None,
+                    github_contributing_guide: None,
+                    has_issues_enabled: true,
+                    homepage_url: None,
+                    is_empty: false,
+                    is_private: false,
+                    issues: RepoViewRepositoryIssues {
+                        nodes: None,
+                        page_info: RepoViewRepositoryIssuesPageInfo {
+                            end_cursor: None,
+                            has_next_page: false,
+                        },
+                    },
+                    languages: None,
+                    latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+                    open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+                    open_milestones: None,
+                    pinned_issues: None,
+                    primary_language: None,
+                    pushed_at: None,
+                    readme: None,
+                    repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+                    stargazer_count: 0,
+                    watchers: RepoViewRepositoryWatchers { total_count: 0 },
+                })))
+            });
+        db.expect_batch_update_repositories()
+            .withf(|repositories| {
+                repositories
+                    .iter()
+                    .map(|r| r.repository_id)
+                    .eq([Uuid::from_u128(1), Uuid::from_u128(2)])
+            })
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_batch_update_repositories()
+            .withf(|repositories| {
+                repositories
+                    .iter()
+                    .map(|r| r.repository_id)
+                    .eq([Uuid::from_u128(3)])
+            })
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_get_repository_issues()
+            .times(3)
+            .returning(|_| Box::pin(future::ready(Ok(vec![]))));
+        db.expect_update_repository_last_track_ts()
+            .times(3)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
     }
 
-    #[test]
-    fn issue_populate_from_labels() {
-        let mut issue = Issue {
-            issue_id: 1,
-            title: "issue1".to_string(),
-            url: "issue1_url".to_string(),
-            number: 1,
-            labels: vec![
-                "documentation".to_string(),
-                "bug".to_string(),
-                "difficulty/medium".to_string(),
-                "mentor available".to_string(),
-                "good first issue".to_string(),
-            ],
-            published_at: OffsetDateTime::parse("1985-04-12T23:20:50.52Z", &Rfc3339).unwrap(),
-            has_linked_prs: false,
-            digest: None,
-            area: None,
-            kind: None,
-            difficulty: None,
-            mentor_available: None,
-            mentor: None,
-            good_first_issue: None,
-        };
+    #[tokio::test]
+    #[allow(clippy::too_many_lines)]
+    async fn run_recently_closed_issues_updated_when_enabled() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.recentlyClosedDays", 7)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
 
-        issue.populate_from_labels();
-        assert_eq!(issue.area, Some(IssueArea::Docs));
-        assert_eq!(issue.kind, Some(IssueKind::Bug));
-        assert_eq!(issue.difficulty, Some(IssueDifficulty::Medium));
-        assert_eq!(issue.mentor_available, Some(true));
-        assert_eq!(issue.good_first_issue, Some(true));
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .times(1)
+            .returning(|_, _, _, _, _, _| {
+                Box::pin(future::ready(Ok(RepoViewRepository {
+                    closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+                    contributing_guide: None,
+                    created_at: "1980-01-01T00:00:00Z".to_string(),
+                    default_branch_ref: None,
+                    description: None,
+                    github_contributing_guide: None,
+                    has_issues_enabled: true,
+                    homepage_url: None,
+                    is_empty: false,
+                    is_private: false,
+                    issues: RepoViewRepositoryIssues {
+                        nodes: None,
+                        page_info: RepoViewRepositoryIssuesPageInfo {
+                            end_cursor: None,
+                            has_next_page: false,
+                        },
+                    },
+                    languages: None,
+                    latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+                    open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+                    open_milestones: None,
+                    pinned_issues: None,
+                    primary_language: None,
+                    pushed_at: None,
+                    readme: None,
+                    repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+                    stargazer_count: 0,
+                    watchers: RepoViewRepositoryWatchers { total_count: 0 },
+                })))
+            });
+        db.expect_update_repository_gh_data()
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_get_repository_issues()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(vec![]))));
+        gh.expect_recently_closed_issues()
+            .withf(|token, repository_url, since, completed_only| {
+                let expected_since = OffsetDateTime::now_utc() - time::Duration::days(7);
+                token == TOKEN1
+                    && repository_url == REPOSITORY_URL
+                    && (expected_since - *since).abs() < time::Duration::minutes(1)
+                    && *completed_only
+            })
+            .times(1)
+            .returning(|_, _, _, _| {
+                Box::pin(future::ready(Ok(vec![ClosedIssue {
+                    issue_id: 1,
+                    title: "issue1".to_string(),
+                    url: "issue1_url".to_string(),
+                    number: 1,
+                    closed_at: OffsetDateTime::UNIX_EPOCH,
+                }])))
+            });
+        db.expect_update_recently_closed_issues()
+            .withf(|repository_id, closed_issues, _| {
+                *repository_id == *REPOSITORY_ID
+                    && closed_issues.len() == 1
+                    && closed_issues[0].issue_id == 1
+            })
+            .times(1)
+            .returning(|_, _, _| Box::pin(future::ready(Ok(()))));
+        db.expect_update_repository_last_track_ts()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
     }
 
     #[tokio::test]
-    async fn run_error_getting_github_tokens() {
-        let cfg = Config::builder().build().unwrap();
+    async fn run_invalid_sync_concurrency() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.maxConcurrentSyncTxns", 0)
+            .unwrap()
+            .build()
+            .unwrap();
         let db = MockDB::new();
         let gh = MockGH::new();
 
         let result = run(&cfg, Arc::new(db), Arc::new(gh)).await;
         assert_eq!(
             result.unwrap_err().to_string(),
-            r#"configuration property "creds.githubTokens" not found"#
+            "invalid tracker configuration: maxConcurrentSyncTxns must be greater than 0"
         );
     }
 
     #[tokio::test]
-    async fn run_empty_list_of_github_tokens_provided() {
-        let cfg = setup_test_config(&[]);
-        let db = MockDB::new();
-        let gh = MockGH::new();
+    async fn run_invalid_adaptive_issues_page_size() {
+        for (min, initial, max) in [(0, 50, 100), (60, 50, 100), (10, 50, 40), (10, 50, 101)] {
+            let cfg = Config::builder()
+                .add_source(setup_test_config(&[TOKEN1]))
+                .set_override("tracker.adaptiveIssuesPageSize.min", min)
+                .unwrap()
+                .set_override("tracker.adaptiveIssuesPageSize.initial", initial)
+                .unwrap()
+                .set_override("tracker.adaptiveIssuesPageSize.max", max)
+                .unwrap()
+                .build()
+                .unwrap();
+            let db = MockDB::new();
+            let gh = MockGH::new();
 
-        let result = run(&cfg, Arc::new(db), Arc::new(gh)).await;
-        assert_eq!(
-            result.unwrap_err().to_string(),
-            "GitHub tokens not found in config file (creds.githubTokens)"
-        );
+            let result = run(&cfg, Arc::new(db), Arc::new(gh)).await;
+            assert_eq!(
+                result.unwrap_err().to_string(),
+                "invalid tracker configuration: adaptiveIssuesPageSize must satisfy 1 <= min <= initial <= max <= 100"
+            );
+        }
     }
 
     #[tokio::test]
-    async fn run_error_getting_repositories_to_track() {
-        let cfg = setup_test_config(&[TOKEN1]);
+    async fn run_issues_synced_when_sync_concurrency_limited() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.maxConcurrentSyncTxns", 1)
+            .unwrap()
+            .build()
+            .unwrap();
         let mut db = MockDB::new();
-        let gh = MockGH::new();
+        let mut gh = MockGH::new();
 
         db.expect_get_repositories_to_track()
             .times(1)
-            .returning(|| Box::pin(future::ready(Err(format_err!(FAKE_ERROR)))));
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .times(1)
+            .returning(|_, _, _, _, _, _| {
+                Box::pin(future::ready(Ok(RepoViewRepository {
+                    closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+                    contributing_guide: None,
+                    created_at: "1980-01-01T00:00:00Z".to_string(),
+                    default_branch_ref: None,
+                    description: None,
+                    github_contributing_guide: None,
+                    has_issues_enabled: true,
+                    homepage_url: None,
+                    is_empty: false,
+                    is_private: false,
+                    issues: RepoViewRepositoryIssues {
+                        nodes: None,
+                        page_info: RepoViewRepositoryIssuesPageInfo {
+                            end_cursor: None,
+                            has_next_page: false,
+                        },
+                    },
+                    languages: None,
+                    latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+                    open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+                    open_milestones: None,
+                    pinned_issues: None,
+                    primary_language: None,
+                    pushed_at: None,
+                    readme: None,
+                    repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+                    stargazer_count: 0,
+                    watchers: RepoViewRepositoryWatchers { total_count: 0 },
+                })))
+            });
+        db.expect_update_repository_gh_data()
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_get_repository_issues()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(vec![]))));
+        db.expect_update_repository_last_track_ts()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
 
-        let result = run(&cfg, Arc::new(db), Arc::new(gh)).await;
-        assert_eq!(result.unwrap_err().to_string(), FAKE_ERROR);
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
     }
 
     #[tokio::test]
-    async fn run_no_repositories_found() {
+    async fn run_issues_sync_skipped_when_issues_disabled() {
         let cfg = setup_test_config(&[TOKEN1]);
         let mut db = MockDB::new();
-        let gh = MockGH::new();
+        let mut gh = MockGH::new();
 
         db.expect_get_repositories_to_track()
             .times(1)
-            .returning(|| Box::pin(future::ready(Ok(vec![]))));
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
+                    repository_id: *REPOSITORY_ID,
+                    url: REPOSITORY_URL.to_string(),
+                    ..Default::default()
+                }])))
+            });
+        gh.expect_repository()
+            .times(1)
+            .returning(|_, _, _, _, _, _| {
+                Box::pin(future::ready(Ok(RepoViewRepository {
+                    closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+                    contributing_guide: None,
+                    created_at: "1980-01-01T00:00:00Z".to_string(),
+                    default_branch_ref: None,
+                    description: None,
+                    github_contributing_guide: None,
+                    has_issues_enabled: false,
+                    homepage_url: None,
+                    is_empty: false,
+                    is_private: false,
+                    issues: RepoViewRepositoryIssues {
+                        nodes: None,
+                        page_info: RepoViewRepositoryIssuesPageInfo {
+                            end_cursor: None,
+                            has_next_page: false,
+                        },
+                    },
+                    languages: None,
+                    latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+                    open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+                    open_milestones: None,
+                    pinned_issues: None,
+                    primary_language: None,
+                    pushed_at: None,
+                    readme: None,
+                    repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+                    stargazer_count: 0,
+                    watchers: RepoViewRepositoryWatchers { total_count: 0 },
+                })))
+            });
+        db.expect_update_repository_gh_data()
+            .withf(|repository| repository.has_issues_enabled == Some(false))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_update_repository_last_track_ts()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
 
         run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
     }
 
     #[tokio::test]
-    async fn run_error_getting_repository_data_from_gh() {
-        let cfg = setup_test_config(&[TOKEN1]);
+    async fn run_issue_error_isolated_when_enabled() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.isolateIssueErrors", true)
+            .unwrap()
+            .build()
+            .unwrap();
         let mut db = MockDB::new();
         let mut gh = MockGH::new();
 
         db.expect_get_repositories_to_track()
             .times(1)
-            .returning(|| {
+            .returning(|_, _| {
                 Box::pin(future::ready(Ok(vec![Repository {
+                    repository_id: *REPOSITORY_ID,
                     url: REPOSITORY_URL.to_string(),
                     ..Default::default()
                 }])))
             });
         gh.expect_repository()
-            .withf(|token, repository_url, issues_filter_label| {
-                token == TOKEN1 && repository_url == REPOSITORY_URL && issues_filter_label.is_none()
-            })
             .times(1)
-            .returning(|_, _, _| Box::pin(future::ready(Err(format_err!(FAKE_ERROR)))));
+            .returning(|_, _, _, _, _, _| {
+                Box::pin(future::ready(Ok(RepoViewRepository {
+                    closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+                    contributing_guide: None,
+                    created_at: "1980-01-01T00:00:00Z".to_string(),
+                    default_branch_ref: None,
+                    description: None,
+                    github_contributing_guide: None,
+                    has_issues_enabled: true,
+                    homepage_url: None,
+                    is_empty: false,
+                    is_private: false,
+                    issues: RepoViewRepositoryIssues {
+                        nodes: Some(vec![Some(RepoViewRepositoryIssuesNodes {
+                            assignees: RepoViewRepositoryIssuesNodesAssignees { total_count: 0 },
+                            closed_by_pull_requests_references: None,
+                            comments: RepoViewRepositoryIssuesNodesComments { total_count: 0 },
+                            database_id: Some(1),
+                            id: "issue1_id".to_string(),
+                            locked: false,
+                            title: "issue1".to_string(),
+                            url: "issue1_url".to_string(),
+                            number: 1,
+                            participants: RepoViewRepositoryIssuesNodesParticipants {
+                                total_count: 0,
+                            },
+                            published_at: Some("1985-04-12T23:20:50.52Z".to_string()),
+                            reactions: RepoViewRepositoryIssuesNodesReactions { total_count: 0 },
+                            reaction_groups: None,
+                            updated_at: "1985-04-12T23:20:50.52Z".to_string(),
+                            labels: Some(RepoViewRepositoryIssuesNodesLabels {
+                                nodes: Some(vec![]),
+                            }),
+                        })]),
+                        page_info: RepoViewRepositoryIssuesPageInfo {
+                            end_cursor: None,
+                            has_next_page: false,
+                        },
+                    },
+                    languages: None,
+                    latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+                    open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+                    open_milestones: None,
+                    pinned_issues: None,
+                    primary_language: None,
+                    pushed_at: None,
+                    readme: None,
+                    repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+                    stargazer_count: 0,
+                    watchers: RepoViewRepositoryWatchers { total_count: 0 },
+                })))
+            });
+        db.expect_update_repository_gh_data()
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_get_repository_issues()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(vec![]))));
+        db.expect_register_issue()
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Err(format_err!(FAKE_ERROR)))));
+        db.expect_update_repository_last_track_ts()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
 
-        let result = run(&cfg, Arc::new(db), Arc::new(gh)).await;
-        assert_eq!(result.unwrap_err().root_cause().to_string(), FAKE_ERROR);
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
     }
 
     #[tokio::test]
     #[allow(clippy::too_many_lines)]
-    async fn run_register_one_issue_and_unregister_another_successfully() {
-        let cfg = setup_test_config(&[TOKEN1]);
+    async fn run_issues_references_updated_when_enabled() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.fetchReferences", true)
+            .unwrap()
+            .build()
+            .unwrap();
         let mut db = MockDB::new();
         let mut gh = MockGH::new();
 
         db.expect_get_repositories_to_track()
             .times(1)
-            .returning(|| {
+            .returning(|_, _| {
                 Box::pin(future::ready(Ok(vec![Repository {
                     repository_id: *REPOSITORY_ID,
                     url: REPOSITORY_URL.to_string(),
@@ -685,130 +8373,220 @@ mod tests {
                 }])))
             });
         gh.expect_repository()
-            .withf(|token, repository_url, issues_filter_label| {
-                token == TOKEN1 && repository_url == REPOSITORY_URL && issues_filter_label.is_none()
-            })
             .times(1)
-            .returning(|_, _, _| {
+            .returning(|_, _, _, _, _, _| {
                 Box::pin(future::ready(Ok(RepoViewRepository {
-                    description: Some("description".to_string()),
+                    closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+                    contributing_guide: None,
+                    created_at: "1980-01-01T00:00:00Z".to_string(),
+                    default_branch_ref: None,
+                    description: None,
+                    github_contributing_guide: None,
+                    has_issues_enabled: true,
                     homepage_url: None,
+                    is_empty: false,
+                    is_private: false,
                     issues: RepoViewRepositoryIssues {
                         nodes: Some(vec![Some(RepoViewRepositoryIssuesNodes {
-                            closed_by_pull_requests_references: Some(
-                                RepoViewRepositoryIssuesNodesClosedByPullRequestsReferences {
-                                    nodes: Some(vec![
-                                        Some(
-                                            RepoViewRepositoryIssuesNodesClosedByPullRequestsReferencesNodes {
-                                                number: 1,
-                                            },
-                                        ),
-                                    ]),
-                                },
-                            ),
+                            assignees: RepoViewRepositoryIssuesNodesAssignees { total_count: 0 },
+                            closed_by_pull_requests_references: None,
+                            comments: RepoViewRepositoryIssuesNodesComments { total_count: 0 },
                             database_id: Some(1),
+                            id: "issue1_id".to_string(),
+                            locked: false,
                             title: "issue1".to_string(),
                             url: "issue1_url".to_string(),
                             number: 1,
+                            participants: RepoViewRepositoryIssuesNodesParticipants {
+                                total_count: 0,
+                            },
                             published_at: Some("1985-04-12T23:20:50.52Z".to_string()),
+                            reactions: RepoViewRepositoryIssuesNodesReactions { total_count: 0 },
+                            reaction_groups: None,
+                            updated_at: "1985-04-12T23:20:50.52Z".to_string(),
                             labels: Some(RepoViewRepositoryIssuesNodesLabels {
-                                nodes: Some(vec![
-                                    Some(RepoViewRepositoryIssuesNodesLabelsNodes {
-                                        name: "good first issue".to_string(),
-                                    }),
-                                    Some(RepoViewRepositoryIssuesNodesLabelsNodes {
-                                        name: "bug".to_string(),
-                                    }),
-                                    Some(RepoViewRepositoryIssuesNodesLabelsNodes {
-                                        name: "difficulty/easy".to_string(),
-                                    }),
-                                ]),
+                                nodes: Some(vec![]),
                             }),
                         })]),
+                        page_info: RepoViewRepositoryIssuesPageInfo {
+                            end_cursor: None,
+                            has_next_page: false,
+                        },
                     },
                     languages: None,
+                    latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+                    open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+                    open_milestones: None,
+                    pinned_issues: None,
+                    primary_language: None,
+                    pushed_at: None,
+                    readme: None,
                     repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
                     stargazer_count: 0,
+                    watchers: RepoViewRepositoryWatchers { total_count: 0 },
                 })))
             });
         db.expect_update_repository_gh_data()
-            .with(eq(Repository {
-                repository_id: *REPOSITORY_ID,
-                url: REPOSITORY_URL.to_string(),
-                description: Some("description".to_string()),
-                stars: Some(0),
-                digest: Some(
-                    "16139cdd47898d43806d0fd1fb6b2596dbf618362f6b9c22a5a2ec1ec0b882f9".to_string(),
-                ),
-                ..Default::default()
-            }))
             .times(1)
             .returning(|_| Box::pin(future::ready(Ok(()))));
         db.expect_get_repository_issues()
             .with(eq(*REPOSITORY_ID))
             .times(1)
-            .returning(|_| {
-                Box::pin(future::ready(Ok(vec![Issue {
-                    issue_id: 2,
-                    title: "issue2".to_string(),
-                    url: "issue2_url".to_string(),
-                    number: 2,
-                    labels: vec![],
-                    published_at: OffsetDateTime::now_utc(),
-                    has_linked_prs: true,
-                    digest: None,
-                    area: None,
-                    kind: None,
-                    difficulty: None,
-                    mentor_available: None,
-                    mentor: None,
-                    good_first_issue: None,
+            .returning(|_| Box::pin(future::ready(Ok(vec![]))));
+        db.expect_register_issue()
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Ok(()))));
+        gh.expect_issues_references()
+            .withf(|token, ids| token == TOKEN1 && ids == ["issue1_id".to_string()])
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![IssueReference {
+                    issue_id: 1,
+                    source_issue_id: 2,
+                    source_url: "issue2_url".to_string(),
                 }])))
             });
-        db.expect_register_issue()
-            .with(
-                eq(Repository {
+        db.expect_update_issues_references()
+            .withf(|issues_ids, references| {
+                issues_ids == [1]
+                    && references
+                        == [IssueReference {
+                            issue_id: 1,
+                            source_issue_id: 2,
+                            source_url: "issue2_url".to_string(),
+                        }]
+            })
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Ok(()))));
+        db.expect_update_repository_last_track_ts()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+
+        run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[allow(clippy::too_many_lines)]
+    async fn run_issues_registered_sorted_by_id_when_enabled() {
+        let cfg = Config::builder()
+            .add_source(setup_test_config(&[TOKEN1]))
+            .set_override("tracker.sortIssuesById", true)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut db = MockDB::new();
+        let mut gh = MockGH::new();
+
+        db.expect_get_repositories_to_track()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(future::ready(Ok(vec![Repository {
                     repository_id: *REPOSITORY_ID,
                     url: REPOSITORY_URL.to_string(),
-                    description: Some("description".to_string()),
-                    stars: Some(0),
-                    digest: Some(
-                        "16139cdd47898d43806d0fd1fb6b2596dbf618362f6b9c22a5a2ec1ec0b882f9"
-                            .to_string(),
-                    ),
                     ..Default::default()
-                }),
-                eq(Issue {
-                    issue_id: 1,
-                    title: "issue1".to_string(),
-                    url: "issue1_url".to_string(),
-                    number: 1,
-                    labels: vec![
-                        "good first issue".to_string(),
-                        "bug".to_string(),
-                        "difficulty/easy".to_string(),
-                    ],
-                    published_at: OffsetDateTime::parse("1985-04-12T23:20:50.52Z", &Rfc3339)
-                        .unwrap(),
-                    has_linked_prs: true,
-                    digest: Some(
-                        "b10bea4dd2f2cdc776db781bbfe376462eb395c859d916583555e61179f49007"
-                            .to_string(),
-                    ),
-                    area: None,
-                    kind: Some(IssueKind::Bug),
-                    difficulty: Some(IssueDifficulty::Easy),
-                    mentor_available: None,
-                    mentor: None,
-                    good_first_issue: Some(true),
-                }),
-            )
+                }])))
+            });
+        gh.expect_repository()
             .times(1)
-            .returning(|_, _| Box::pin(future::ready(Ok(()))));
-        db.expect_unregister_issue()
-            .with(eq(2))
+            .returning(|_, _, _, _, _, _| {
+                Box::pin(future::ready(Ok(RepoViewRepository {
+                    closed_issues: RepoViewRepositoryClosedIssues { total_count: 0 },
+                    contributing_guide: None,
+                    created_at: "1980-01-01T00:00:00Z".to_string(),
+                    default_branch_ref: None,
+                    description: None,
+                    github_contributing_guide: None,
+                    has_issues_enabled: true,
+                    homepage_url: None,
+                    is_empty: false,
+                    is_private: false,
+                    issues: RepoViewRepositoryIssues {
+                        nodes: Some(vec![
+                            Some(RepoViewRepositoryIssuesNodes {
+                                assignees: RepoViewRepositoryIssuesNodesAssignees {
+                                    total_count: 0,
+                                },
+                                closed_by_pull_requests_references: None,
+                                comments: RepoViewRepositoryIssuesNodesComments { total_count: 0 },
+                                database_id: Some(2),
+                                id: "issue2_id".to_string(),
+                                locked: false,
+                                title: "issue2".to_string(),
+                                url: "issue2_url".to_string(),
+                                number: 2,
+                                participants: RepoViewRepositoryIssuesNodesParticipants {
+                                    total_count: 0,
+                                },
+                                published_at: Some("1985-04-12T23:20:50.52Z".to_string()),
+                                reactions: RepoViewRepositoryIssuesNodesReactions {
+                                    total_count: 0,
+                                },
+                                reaction_groups: None,
+                                updated_at: "1985-04-12T23:20:50.52Z".to_string(),
+                                labels: Some(RepoViewRepositoryIssuesNodesLabels {
+                                    nodes: Some(vec![]),
+                                }),
+                            }),
+                            Some(RepoViewRepositoryIssuesNodes {
+                                assignees: RepoViewRepositoryIssuesNodesAssignees {
+                                    total_count: 0,
+                                },
+                                closed_by_pull_requests_references: None,
+                                comments: RepoViewRepositoryIssuesNodesComments { total_count: 0 },
+                                database_id: Some(1),
+                                id: "issue1_id".to_string(),
+                                locked: false,
+                                title: "issue1".to_string(),
+                                url: "issue1_url".to_string(),
+                                number: 1,
+                                participants: RepoViewRepositoryIssuesNodesParticipants {
+                                    total_count: 0,
+                                },
+                                published_at: Some("1985-04-12T23:20:50.52Z".to_string()),
+                                reactions: RepoViewRepositoryIssuesNodesReactions {
+                                    total_count: 0,
+                                },
+                                reaction_groups: None,
+                                updated_at: "1985-04-12T23:20:50.52Z".to_string(),
+                                labels: Some(RepoViewRepositoryIssuesNodesLabels {
+                                    nodes: Some(vec![]),
+                                }),
+                            }),
+                        ]),
+                        page_info: RepoViewRepositoryIssuesPageInfo {
+                            end_cursor: None,
+                            has_next_page: false,
+                        },
+                    },
+                    languages: None,
+                    latest_release: RepoViewRepositoryLatestRelease { nodes: None },
+                    open_issues: RepoViewRepositoryOpenIssues { total_count: 0 },
+                    open_milestones: None,
+                    pinned_issues: None,
+                    primary_language: None,
+                    pushed_at: None,
+                    readme: None,
+                    repository_topics: RepoViewRepositoryRepositoryTopics { nodes: None },
+                    stargazer_count: 0,
+                    watchers: RepoViewRepositoryWatchers { total_count: 0 },
+                })))
+            });
+        db.expect_update_repository_gh_data()
             .times(1)
             .returning(|_| Box::pin(future::ready(Ok(()))));
+        db.expect_get_repository_issues()
+            .with(eq(*REPOSITORY_ID))
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(vec![]))));
+        let mut seq = Sequence::new();
+        for issue_id in [1, 2] {
+            db.expect_register_issue()
+                .withf(move |_, issue| issue.issue_id == issue_id)
+                .times(1)
+                .in_sequence(&mut seq)
+                .returning(|_, _| Box::pin(future::ready(Ok(()))));
+        }
         db.expect_update_repository_last_track_ts()
             .with(eq(*REPOSITORY_ID))
             .times(1)
@@ -817,8 +8595,36 @@ mod tests {
         run(&cfg, Arc::new(db), Arc::new(gh)).await.unwrap();
     }
 
+    #[tokio::test]
+    async fn track_timings_measure_each_phase_separately() {
+        let mut timings = TrackTimings::default();
+
+        let output = timings
+            .gh(async {
+                sleep(Duration::from_millis(10)).await;
+                1
+            })
+            .await;
+        timings.db_write(future::ready(())).await;
+
+        assert_eq!(output, 1);
+        assert!(timings.gh >= Duration::from_millis(10));
+        assert_eq!(timings.db_read, Duration::ZERO);
+        assert!(timings.db_write < timings.gh);
+    }
+
     fn setup_test_config(tokens: &[&str]) -> Config {
         Config::builder()
+            .set_default("creds.githubOrgTokens", None::<String>)
+            .unwrap()
+            .set_default("creds.githubTokenLabels", None::<String>)
+            .unwrap()
+            .set_default("github.finalRateLimitCheck", true)
+            .unwrap()
+            .set_default("github.maxQps", None::<f64>)
+            .unwrap()
+            .set_default("metrics.pushgatewayUrl", None::<String>)
+            .unwrap()
             .set_default("tracker.concurrency", 1)
             .unwrap()
             .set_default(
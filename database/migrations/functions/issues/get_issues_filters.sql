@@ -47,6 +47,7 @@ returns json as $$
                         from project p
                         join repository r using (project_id)
                         join issue i using (repository_id)
+                        where i.converted_to_discussion_url is null
                         order by name asc
                     ) m
                 )
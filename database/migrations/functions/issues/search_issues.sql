@@ -97,7 +97,8 @@ begin
         from issue i
         join repository r using (repository_id)
         join project p using (project_id)
-        where
+        where i.converted_to_discussion_url is null
+        and
             case when v_tsquery_web is not null then
                 v_tsquery_web_with_prefix_matching @@ i.tsdoc
             else true end
@@ -0,0 +1,7 @@
+alter table repository add column open_issues_total integer;
+alter table repository add column closed_issues_total integer;
+
+---- create above / drop below ----
+
+alter table repository drop column open_issues_total;
+alter table repository drop column closed_issues_total;
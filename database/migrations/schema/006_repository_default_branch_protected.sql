@@ -0,0 +1,5 @@
+alter table repository add column default_branch_protected boolean;
+
+---- create above / drop below ----
+
+alter table repository drop column default_branch_protected;
@@ -0,0 +1,14 @@
+create table if not exists topic (
+    name text primary key,
+    display_name text,
+    short_description text,
+    description text,
+    featured boolean not null default false,
+    curated boolean not null default false,
+    created_at timestamptz not null default current_timestamp,
+    updated_at timestamptz not null default current_timestamp
+);
+
+---- create above / drop below ----
+
+drop table if exists topic;
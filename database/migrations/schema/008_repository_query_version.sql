@@ -0,0 +1,5 @@
+alter table repository add column query_version integer;
+
+---- create above / drop below ----
+
+alter table repository drop column query_version;
@@ -0,0 +1,6 @@
+alter table repository add column "group" text check ("group" <> '');
+create index repository_group_idx on repository ("group");
+
+---- create above / drop below ----
+
+alter table repository drop column "group";
@@ -0,0 +1,5 @@
+alter table repository add column is_active boolean;
+
+---- create above / drop below ----
+
+alter table repository drop column is_active;
@@ -0,0 +1,5 @@
+alter table repository add column issues_cursor text;
+
+---- create above / drop below ----
+
+alter table repository drop column issues_cursor;
@@ -0,0 +1,5 @@
+alter table issue add column reactions_by_type jsonb;
+
+---- create above / drop below ----
+
+alter table issue drop column reactions_by_type;
@@ -0,0 +1,5 @@
+alter table repository add column requires_cla boolean;
+
+---- create above / drop below ----
+
+alter table repository drop column requires_cla;
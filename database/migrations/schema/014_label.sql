@@ -0,0 +1,13 @@
+create table if not exists label (
+    repository_id uuid not null references repository on delete cascade,
+    name text not null check (name <> ''),
+    color text not null check (color <> ''),
+    description text,
+    created_at timestamptz not null default current_timestamp,
+    updated_at timestamptz not null default current_timestamp,
+    primary key (repository_id, name)
+);
+
+---- create above / drop below ----
+
+drop table if exists label;
@@ -0,0 +1,7 @@
+alter table issue add column score double precision;
+create index issue_score_idx on issue (score);
+
+---- create above / drop below ----
+
+drop index if exists issue_score_idx;
+alter table issue drop column score;
@@ -0,0 +1,5 @@
+alter table repository add column top_contributors jsonb;
+
+---- create above / drop below ----
+
+alter table repository drop column top_contributors;
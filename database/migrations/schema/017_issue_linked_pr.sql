@@ -0,0 +1,7 @@
+alter table issue add column linked_pr_url text;
+alter table issue add column linked_pr_state text;
+
+---- create above / drop below ----
+
+alter table issue drop column linked_pr_url;
+alter table issue drop column linked_pr_state;
@@ -0,0 +1,5 @@
+alter table issue add column last_activity_at timestamptz;
+
+---- create above / drop below ----
+
+alter table issue drop column last_activity_at;
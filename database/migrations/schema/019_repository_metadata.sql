@@ -0,0 +1,9 @@
+alter table repository add column metadata_category text;
+alter table repository add column metadata_subcategory text;
+alter table repository add column metadata_project_name text;
+
+---- create above / drop below ----
+
+alter table repository drop column metadata_category;
+alter table repository drop column metadata_subcategory;
+alter table repository drop column metadata_project_name;
@@ -0,0 +1,7 @@
+alter table repository add column primary_language text;
+alter table repository add column primary_language_color text;
+
+---- create above / drop below ----
+
+alter table repository drop column primary_language;
+alter table repository drop column primary_language_color;
@@ -0,0 +1,5 @@
+alter table repository add column commit_activity int[];
+
+---- create above / drop below ----
+
+alter table repository drop column commit_activity;
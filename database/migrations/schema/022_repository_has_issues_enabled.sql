@@ -0,0 +1,5 @@
+alter table repository add column has_issues_enabled boolean;
+
+---- create above / drop below ----
+
+alter table repository drop column has_issues_enabled;
@@ -0,0 +1,5 @@
+alter table issue add column participants integer not null default 0;
+
+---- create above / drop below ----
+
+alter table issue drop column participants;
@@ -0,0 +1,5 @@
+alter table issue add column is_pinned boolean not null default false;
+
+---- create above / drop below ----
+
+alter table issue drop column is_pinned;
@@ -0,0 +1,5 @@
+alter table repository add column open_milestones integer;
+
+---- create above / drop below ----
+
+alter table repository drop column open_milestones;
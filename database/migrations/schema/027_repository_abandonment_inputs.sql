@@ -0,0 +1,7 @@
+alter table repository add column is_empty boolean;
+alter table repository add column latest_release_at timestamptz;
+
+---- create above / drop below ----
+
+alter table repository drop column is_empty;
+alter table repository drop column latest_release_at;
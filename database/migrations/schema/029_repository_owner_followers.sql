@@ -0,0 +1,5 @@
+alter table repository add column owner_followers integer;
+
+---- create above / drop below ----
+
+alter table repository drop column owner_followers;
@@ -0,0 +1,24 @@
+create type track_failure_category as enum (
+    'auth',
+    'not-found',
+    'rate-limited',
+    'timeout',
+    'other'
+);
+
+create table if not exists track_failure (
+    track_failure_id uuid primary key default gen_random_uuid(),
+    repository_id uuid not null references repository on delete cascade,
+    category track_failure_category not null,
+    status integer,
+    message text not null,
+    failed_at timestamptz not null
+);
+
+create index track_failure_repository_id_idx on track_failure (repository_id);
+create index track_failure_failed_at_idx on track_failure (failed_at);
+
+---- create above / drop below ----
+
+drop table if exists track_failure;
+drop type if exists track_failure_category;
@@ -0,0 +1,10 @@
+create table if not exists topic_stats (
+    name text primary key,
+    repositories bigint not null,
+    stars bigint not null,
+    created_at timestamptz default current_timestamp not null
+);
+
+---- create above / drop below ----
+
+drop table if exists topic_stats;
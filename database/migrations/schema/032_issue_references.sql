@@ -0,0 +1,10 @@
+create table if not exists issue_references (
+    issue_id bigint not null references issue on delete cascade,
+    source_issue_id bigint not null,
+    source_url text not null check (source_url <> ''),
+    primary key (issue_id, source_issue_id)
+);
+
+---- create above / drop below ----
+
+drop table if exists issue_references;
@@ -0,0 +1,15 @@
+create table if not exists recently_closed_issue (
+    issue_id bigint primary key,
+    repository_id uuid not null references repository on delete cascade,
+    title text not null check (title <> ''),
+    url text not null check (url <> ''),
+    number integer not null,
+    closed_at timestamptz not null
+);
+
+create index recently_closed_issue_repository_id_idx on recently_closed_issue (repository_id);
+create index recently_closed_issue_closed_at_idx on recently_closed_issue (closed_at);
+
+---- create above / drop below ----
+
+drop table if exists recently_closed_issue;
@@ -0,0 +1,5 @@
+alter table issue add column is_available boolean;
+
+---- create above / drop below ----
+
+alter table issue drop column is_available;
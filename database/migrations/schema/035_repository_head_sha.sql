@@ -0,0 +1,5 @@
+alter table repository add column head_sha text;
+
+---- create above / drop below ----
+
+alter table repository drop column head_sha;
@@ -0,0 +1,5 @@
+alter table issue add column needs_triage boolean;
+
+---- create above / drop below ----
+
+alter table issue drop column needs_triage;
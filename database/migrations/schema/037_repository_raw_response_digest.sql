@@ -0,0 +1,5 @@
+alter table repository add column raw_response_digest text;
+
+---- create above / drop below ----
+
+alter table repository drop column raw_response_digest;
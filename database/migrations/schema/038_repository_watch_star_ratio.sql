@@ -0,0 +1,5 @@
+alter table repository add column watch_star_ratio real;
+
+---- create above / drop below ----
+
+alter table repository drop column watch_star_ratio;
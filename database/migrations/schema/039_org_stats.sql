@@ -0,0 +1,12 @@
+create table if not exists org_stats (
+    name text primary key,
+    repositories bigint not null,
+    stars bigint not null,
+    open_issues bigint not null,
+    most_active_repository_id uuid references repository on delete set null,
+    created_at timestamptz default current_timestamp not null
+);
+
+---- create above / drop below ----
+
+drop table if exists org_stats;
@@ -0,0 +1,12 @@
+create table if not exists issue_label_history (
+    issue_label_history_id uuid primary key default gen_random_uuid(),
+    issue_id bigint not null references issue on delete cascade,
+    labels text[] not null,
+    recorded_at timestamptz not null
+);
+
+create index issue_label_history_issue_id_idx on issue_label_history (issue_id);
+
+---- create above / drop below ----
+
+drop table if exists issue_label_history;
@@ -0,0 +1,5 @@
+alter table repository add column badges jsonb;
+
+---- create above / drop below ----
+
+alter table repository drop column badges;
@@ -0,0 +1,5 @@
+alter table issue add column first_response_seconds bigint;
+
+---- create above / drop below ----
+
+alter table issue drop column first_response_seconds;
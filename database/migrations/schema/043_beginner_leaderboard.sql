@@ -0,0 +1,9 @@
+create table if not exists beginner_leaderboard (
+    issue_id bigint primary key references issue on delete cascade,
+    score double precision not null,
+    created_at timestamptz default current_timestamp not null
+);
+
+---- create above / drop below ----
+
+drop table if exists beginner_leaderboard;
@@ -0,0 +1,5 @@
+alter table issue add column converted_to_discussion_url text;
+
+---- create above / drop below ----
+
+alter table issue drop column converted_to_discussion_url;
@@ -0,0 +1,5 @@
+alter table repository add column is_private boolean;
+
+---- create above / drop below ----
+
+alter table repository drop column is_private;
@@ -0,0 +1,5 @@
+alter table repository add column issue_close_rate real;
+
+---- create above / drop below ----
+
+alter table repository drop column issue_close_rate;